@@ -1,5 +1,5 @@
 use crate::models::Invoice;
-use cache_kit::CacheEntity;
+use cache_kit::{CacheEntity, CacheQuery};
 use uuid::Uuid;
 
 /// Cache configuration for Invoice
@@ -15,8 +15,28 @@ impl CacheEntity for Invoice {
     }
 }
 
-/// Helper to create a cache key for listing invoices by customer
-#[allow(dead_code)]
-pub fn invoice_list_cache_key(customer_id: &Uuid, limit: i64, offset: i64) -> String {
-    format!("invoice:list:{}:{}:{}", customer_id, limit, offset)
+/// Query for one page of a customer's invoices
+///
+/// Cached with `CacheService::query`; evicted when a listed invoice is written
+/// or invalidated, or when the customer's tag is invalidated.
+pub struct InvoiceListQuery {
+    pub customer_id: Uuid,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+impl CacheQuery for InvoiceListQuery {
+    type Entity = Invoice;
+
+    fn query_prefix() -> &'static str {
+        "invoice_list"
+    }
+
+    fn query_params(&self) -> String {
+        format!("{}:{}:{}", self.customer_id, self.limit, self.offset)
+    }
+
+    fn query_tags(&self) -> Vec<String> {
+        vec![format!("customer:{}", self.customer_id)]
+    }
 }
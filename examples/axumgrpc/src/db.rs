@@ -62,18 +62,19 @@ impl Database {
         }
     }
 
+    pub async fn count_invoices(pool: &PgPool, customer_id: &Uuid) -> sqlx::Result<i64> {
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM invoices WHERE customer_id = $1")
+            .bind(customer_id)
+            .fetch_one(pool)
+            .await
+    }
+
     pub async fn list_invoices(
         pool: &PgPool,
         customer_id: &Uuid,
         limit: i64,
         offset: i64,
-    ) -> sqlx::Result<(Vec<Invoice>, i64)> {
-        let total =
-            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM invoices WHERE customer_id = $1")
-                .bind(customer_id)
-                .fetch_one(pool)
-                .await?;
-
+    ) -> sqlx::Result<Vec<Invoice>> {
         let rows = sqlx::query_as::<
             _,
             (
@@ -132,7 +133,7 @@ impl Database {
             });
         }
 
-        Ok((invoices, total))
+        Ok(invoices)
     }
 
     pub async fn create_invoice(
@@ -1,5 +1,6 @@
 use crate::{
-    db::Database, feeders::InvoiceFeeder, models::Invoice, repository::InvoiceRepository, AppState,
+    cache_config::InvoiceListQuery, db::Database, feeders::InvoiceFeeder, models::Invoice,
+    repository::InvoiceRepository, AppState,
 };
 use cache_kit::strategy::CacheStrategy;
 use tonic::{transport::Server, Response, Status};
@@ -65,10 +66,27 @@ impl InvoicesService for InvoicesHandler {
         let limit = (req.limit as i64).clamp(1, 100);
         let offset = (req.offset as i64).clamp(0, i64::MAX);
 
-        let (invoices, total) =
-            Database::list_invoices(&self.state.db, &customer_id, limit, offset)
-                .await
-                .map_err(|e| Status::internal(e.to_string()))?;
+        let total = Database::count_invoices(&self.state.db, &customer_id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        // Cache the page; it is evicted when a listed invoice is invalidated or
+        // the customer's tag is (on invoice creation)
+        let query = InvoiceListQuery {
+            customer_id,
+            limit,
+            offset,
+        };
+        let invoices = self
+            .state
+            .cache_service
+            .query(&query, || async {
+                Database::list_invoices(&self.state.db, &customer_id, limit, offset)
+                    .await
+                    .map_err(|e| cache_kit::Error::RepositoryError(e.to_string()))
+            })
+            .await
+            .map_err(|e| Status::internal(format!("Cache error: {}", e)))?;
 
         let invoices = invoices
             .into_iter()
@@ -134,6 +152,20 @@ impl InvoicesService for InvoicesHandler {
             .invoice
             .ok_or_else(|| Status::internal("Invoice not found after creation"))?;
 
+        // The new invoice belongs on the customer's cached invoice lists
+        if let Err(e) = self
+            .state
+            .cache_service
+            .invalidate_tag(&format!("customer:{}", customer_id))
+            .await
+        {
+            tracing::warn!(
+                "Failed to invalidate invoice lists for customer {}: {}",
+                customer_id,
+                e
+            );
+        }
+
         tracing::info!("Created and cached invoice {}", invoice.id);
 
         Ok(Response::new(invoice_to_proto(&invoice)))
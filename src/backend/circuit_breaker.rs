@@ -0,0 +1,606 @@
+//! Circuit breaker wrapper for cache backends.
+//!
+//! [`CircuitBreakerBackend`] wraps any [`CacheBackend`] and counts consecutive
+//! failures (including calls that exceed `call_timeout`). Once the count
+//! reaches `failure_threshold` the circuit *opens*: calls return immediately,
+//! reads as misses and writes as no-ops, instead of waiting on a dead backend.
+//! Deletes and counter increments, which have no safe default, fail with
+//! `Error::BackendError`: a dropped delete would leave a stale entry to be
+//! served once the circuit closes.
+//!
+//! After `cooldown`, the next call moves the circuit to *half-open* and probes
+//! the inner backend with `health_check`. A healthy probe closes the circuit;
+//! a failed one re-opens it for another cooldown.
+//!
+//! ```text
+//! Closed --(threshold failures)--> Open --(cooldown)--> HalfOpen
+//!   ^                                ^                      |
+//!   |                                +----(probe fails)-----+
+//!   +-------------------(probe succeeds)--------------------+
+//! ```
+//!
+//! # Example
+//!
+//! ```ignore
+//! use cache_kit::backend::{CircuitBreakerBackend, CircuitBreakerConfig, RedisBackend};
+//!
+//! let redis = RedisBackend::new(config).await?;
+//! let backend = CircuitBreakerBackend::new(redis, CircuitBreakerConfig::default());
+//! let expander = CacheExpander::new(backend);
+//! ```
+
+use super::{CacheBackend, ValueWithTtl};
+use crate::error::{with_deadline, Error, Result, TimeoutPhase};
+use crate::observability::{CacheMetrics, NoOpMetrics};
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+/// State of a circuit breaker.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls pass through to the inner backend.
+    Closed,
+
+    /// Calls short-circuit without touching the inner backend.
+    Open,
+
+    /// A `health_check` probe is deciding whether to close the circuit.
+    HalfOpen,
+}
+
+impl fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CircuitState::Closed => write!(f, "Closed"),
+            CircuitState::Open => write!(f, "Open"),
+            CircuitState::HalfOpen => write!(f, "HalfOpen"),
+        }
+    }
+}
+
+/// Configuration for a [`CircuitBreakerBackend`].
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit. Default: 5.
+    pub failure_threshold: u32,
+
+    /// How long the circuit stays open before probing. Default: 30 seconds.
+    pub cooldown: Duration,
+
+    /// Per-call deadline; slower calls count as failures. Default: 1 second.
+    /// `None` disables the deadline.
+    pub call_timeout: Option<Duration>,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        CircuitBreakerConfig {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+            call_timeout: Some(Duration::from_secs(1)),
+        }
+    }
+}
+
+impl CircuitBreakerConfig {
+    /// Set the number of consecutive failures that open the circuit.
+    pub fn with_failure_threshold(mut self, threshold: u32) -> Self {
+        self.failure_threshold = threshold.max(1);
+        self
+    }
+
+    /// Set how long the circuit stays open before probing.
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Set the per-call deadline (`None` to disable).
+    pub fn with_call_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.call_timeout = timeout;
+        self
+    }
+}
+
+/// Mutable breaker state, shared between clones.
+struct Breaker {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Instant,
+}
+
+/// How a call is admitted by the breaker.
+enum Admission {
+    Call,
+    Probe,
+    ShortCircuit,
+}
+
+/// Cache backend wrapper that stops calling a failing backend.
+///
+/// While the circuit is open, reads (`get`, `mget`, `exists`, `smembers`) return
+/// misses and writes (`set`, `mset`, `sadd`) succeed without doing anything;
+/// `health_check` reports `false`. Deletes (`delete`, `mdelete`, `srem`,
+/// `msrem`, `delete_prefix`, `clear_all`) and `incr` fail with
+/// `Error::BackendError`, so callers know an invalidation did not happen.
+/// Errors from the inner backend while the circuit is closed are returned
+/// unchanged.
+///
+/// Clones share the same circuit.
+#[derive(Clone)]
+pub struct CircuitBreakerBackend<B: CacheBackend> {
+    inner: B,
+    config: CircuitBreakerConfig,
+    breaker: Arc<Mutex<Breaker>>,
+    metrics: Arc<dyn CacheMetrics>,
+}
+
+impl<B: CacheBackend> CircuitBreakerBackend<B> {
+    /// Wrap `inner` with a circuit breaker.
+    pub fn new(inner: B, config: CircuitBreakerConfig) -> Self {
+        CircuitBreakerBackend {
+            inner,
+            config,
+            breaker: Arc::new(Mutex::new(Breaker {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: Instant::now(),
+            })),
+            metrics: Arc::new(NoOpMetrics),
+        }
+    }
+
+    /// Report state transitions through `CacheMetrics::record_circuit_transition`.
+    pub fn with_metrics(mut self, metrics: Box<dyn CacheMetrics>) -> Self {
+        self.metrics = metrics.into();
+        self
+    }
+
+    /// Current circuit state.
+    pub fn state(&self) -> CircuitState {
+        self.breaker().state
+    }
+
+    /// Get inner backend reference.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    fn breaker(&self) -> MutexGuard<'_, Breaker> {
+        self.breaker.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn transition(&self, breaker: &mut Breaker, to: CircuitState) {
+        let from = breaker.state;
+        if from == to {
+            return;
+        }
+
+        breaker.state = to;
+        match to {
+            CircuitState::Open => {
+                breaker.opened_at = Instant::now();
+                warn!(
+                    "⚠ Circuit breaker {} -> {} after {} consecutive failures",
+                    from, to, breaker.consecutive_failures
+                );
+            }
+            CircuitState::Closed => {
+                breaker.consecutive_failures = 0;
+                info!("✓ Circuit breaker {} -> {}", from, to);
+            }
+            CircuitState::HalfOpen => info!("Circuit breaker {} -> {}, probing", from, to),
+        }
+        self.metrics.record_circuit_transition(from, to);
+    }
+
+    fn admit(&self) -> Admission {
+        let mut breaker = self.breaker();
+        match breaker.state {
+            CircuitState::Closed => Admission::Call,
+            CircuitState::HalfOpen => Admission::ShortCircuit,
+            CircuitState::Open if breaker.opened_at.elapsed() >= self.config.cooldown => {
+                self.transition(&mut breaker, CircuitState::HalfOpen);
+                Admission::Probe
+            }
+            CircuitState::Open => Admission::ShortCircuit,
+        }
+    }
+
+    /// Decide whether a call may reach the inner backend, probing if due.
+    async fn allow(&self) -> bool {
+        match self.admit() {
+            Admission::Call => true,
+            Admission::ShortCircuit => false,
+            Admission::Probe => {
+                let healthy = matches!(self.timed(self.inner.health_check()).await, Ok(true));
+                let mut breaker = self.breaker();
+                if healthy {
+                    self.transition(&mut breaker, CircuitState::Closed);
+                } else {
+                    self.transition(&mut breaker, CircuitState::Open);
+                }
+                healthy
+            }
+        }
+    }
+
+    /// Run an inner backend call, recording its outcome.
+    async fn call<V: Send>(&self, operation: impl Future<Output = Result<V>> + Send) -> Result<V> {
+        let result = self.timed(operation).await;
+
+        let mut breaker = self.breaker();
+        match &result {
+            Ok(_) => breaker.consecutive_failures = 0,
+            Err(_) => {
+                breaker.consecutive_failures += 1;
+                if breaker.state == CircuitState::Closed
+                    && breaker.consecutive_failures >= self.config.failure_threshold
+                {
+                    self.transition(&mut breaker, CircuitState::Open);
+                }
+            }
+        }
+
+        result
+    }
+
+    async fn timed<V: Send>(&self, operation: impl Future<Output = Result<V>> + Send) -> Result<V> {
+        with_deadline(TimeoutPhase::Backend, self.config.call_timeout, operation).await
+    }
+}
+
+impl<B: CacheBackend> CacheBackend for CircuitBreakerBackend<B> {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        if !self.allow().await {
+            debug!("Circuit open, GET {} -> MISS", key);
+            return Ok(None);
+        }
+        self.call(self.inner.get(key)).await
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Option<Duration>) -> Result<()> {
+        if !self.allow().await {
+            debug!("Circuit open, SET {} skipped", key);
+            return Ok(());
+        }
+        self.call(self.inner.set(key, value, ttl)).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        if !self.allow().await {
+            return Err(Error::BackendError(format!(
+                "circuit open, DELETE {} rejected",
+                key
+            )));
+        }
+        self.call(self.inner.delete(key)).await
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool> {
+        if !self.allow().await {
+            return Ok(false);
+        }
+        self.call(self.inner.exists(key)).await
+    }
+
+    async fn mget(&self, keys: &[&str]) -> Result<Vec<Option<Vec<u8>>>> {
+        if !self.allow().await {
+            debug!("Circuit open, MGET {} keys -> MISS", keys.len());
+            return Ok(vec![None; keys.len()]);
+        }
+        self.call(self.inner.mget(keys)).await
+    }
+
+    async fn mget_with_ttl(&self, keys: &[&str]) -> Result<Vec<Option<ValueWithTtl>>> {
+        if !self.allow().await {
+            debug!("Circuit open, MGET {} keys -> MISS", keys.len());
+            return Ok(vec![None; keys.len()]);
+        }
+        self.call(self.inner.mget_with_ttl(keys)).await
+    }
+
+    async fn mset(&self, items: &[(&str, Vec<u8>)], ttl: Option<Duration>) -> Result<()> {
+        if !self.allow().await {
+            debug!("Circuit open, MSET {} keys skipped", items.len());
+            return Ok(());
+        }
+        self.call(self.inner.mset(items, ttl)).await
+    }
+
+    async fn mdelete(&self, keys: &[&str]) -> Result<()> {
+        if !self.allow().await {
+            return Err(Error::BackendError(format!(
+                "circuit open, MDELETE of {} keys rejected",
+                keys.len()
+            )));
+        }
+        self.call(self.inner.mdelete(keys)).await
+    }
+
+    async fn incr(&self, key: &str) -> Result<u64> {
+        if !self.allow().await {
+            return Err(Error::BackendError(format!(
+                "circuit open, INCR {} rejected",
+                key
+            )));
+        }
+        self.call(self.inner.incr(key)).await
+    }
+
+    async fn sadd(&self, key: &str, members: &[&str], ttl: Option<Duration>) -> Result<()> {
+        if !self.allow().await {
+            debug!("Circuit open, SADD {} skipped", key);
+            return Ok(());
+        }
+        self.call(self.inner.sadd(key, members, ttl)).await
+    }
+
+    async fn smembers(&self, key: &str) -> Result<Vec<String>> {
+        if !self.allow().await {
+            debug!("Circuit open, SMEMBERS {} -> empty", key);
+            return Ok(Vec::new());
+        }
+        self.call(self.inner.smembers(key)).await
+    }
+
+    async fn srem(&self, key: &str, members: &[&str]) -> Result<()> {
+        if !self.allow().await {
+            return Err(Error::BackendError(format!(
+                "circuit open, SREM {} rejected",
+                key
+            )));
+        }
+        self.call(self.inner.srem(key, members)).await
+    }
+
+    async fn msadd(&self, items: &[(&str, Vec<&str>)], ttl: Option<Duration>) -> Result<()> {
+        if !self.allow().await {
+            debug!("Circuit open, SADD of {} sets skipped", items.len());
+            return Ok(());
+        }
+        self.call(self.inner.msadd(items, ttl)).await
+    }
+
+    async fn msmembers(&self, keys: &[&str]) -> Result<Vec<Vec<String>>> {
+        if !self.allow().await {
+            debug!("Circuit open, SMEMBERS of {} sets -> empty", keys.len());
+            return Ok(vec![Vec::new(); keys.len()]);
+        }
+        self.call(self.inner.msmembers(keys)).await
+    }
+
+    async fn msrem(&self, items: &[(&str, Vec<&str>)]) -> Result<()> {
+        if !self.allow().await {
+            return Err(Error::BackendError(format!(
+                "circuit open, SREM of {} sets rejected",
+                items.len()
+            )));
+        }
+        self.call(self.inner.msrem(items)).await
+    }
+
+    async fn health_check(&self) -> Result<bool> {
+        if !self.allow().await {
+            return Ok(false);
+        }
+        self.call(self.inner.health_check()).await
+    }
+
+    async fn delete_prefix(&self, prefix: &str) -> Result<usize> {
+        if !self.allow().await {
+            return Err(Error::BackendError(format!(
+                "circuit open, DELETE_PREFIX {} rejected",
+                prefix
+            )));
+        }
+        self.call(self.inner.delete_prefix(prefix)).await
+    }
+
+    async fn clear_all(&self) -> Result<()> {
+        if !self.allow().await {
+            return Err(Error::BackendError(
+                "circuit open, CLEAR_ALL rejected".to_string(),
+            ));
+        }
+        self.call(self.inner.clear_all()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::InMemoryBackend;
+    use crate::error::Error;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    /// In-memory backend that can be switched off.
+    #[derive(Clone, Default)]
+    struct FlakyBackend {
+        inner: InMemoryBackend,
+        down: Arc<AtomicBool>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl FlakyBackend {
+        fn check(&self) -> Result<()> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.down.load(Ordering::SeqCst) {
+                Err(Error::BackendError("connection refused".to_string()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    impl CacheBackend for FlakyBackend {
+        async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+            self.check()?;
+            self.inner.get(key).await
+        }
+
+        async fn set(&self, key: &str, value: Vec<u8>, ttl: Option<Duration>) -> Result<()> {
+            self.check()?;
+            self.inner.set(key, value, ttl).await
+        }
+
+        async fn delete(&self, key: &str) -> Result<()> {
+            self.check()?;
+            self.inner.delete(key).await
+        }
+
+        async fn health_check(&self) -> Result<bool> {
+            self.check()?;
+            Ok(true)
+        }
+    }
+
+    fn breaker(backend: FlakyBackend) -> CircuitBreakerBackend<FlakyBackend> {
+        CircuitBreakerBackend::new(
+            backend,
+            CircuitBreakerConfig::default()
+                .with_failure_threshold(3)
+                .with_cooldown(Duration::from_millis(50)),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_circuit_opens_after_threshold() {
+        let backend = FlakyBackend::default();
+        let circuit = breaker(backend.clone());
+        backend.down.store(true, Ordering::SeqCst);
+
+        for _ in 0..3 {
+            assert!(circuit.get("key").await.is_err());
+        }
+        assert_eq!(circuit.state(), CircuitState::Open);
+
+        // Short-circuits without touching the inner backend
+        let calls = backend.calls.load(Ordering::SeqCst);
+        assert_eq!(circuit.get("key").await.expect("Failed to get"), None);
+        circuit
+            .set("key", b"value".to_vec(), None)
+            .await
+            .expect("Failed to set");
+        assert!(!circuit.health_check().await.expect("Failed health check"));
+        assert_eq!(backend.calls.load(Ordering::SeqCst), calls);
+
+        // Deletes cannot be skipped without leaving stale entries behind
+        assert!(matches!(
+            circuit.delete("key").await,
+            Err(Error::BackendError(_))
+        ));
+        assert!(circuit.mdelete(&["key"]).await.is_err());
+        assert!(circuit.srem("tag", &["key"]).await.is_err());
+        assert!(circuit.clear_all().await.is_err());
+        assert_eq!(backend.calls.load(Ordering::SeqCst), calls);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_success_resets_failures() {
+        let backend = FlakyBackend::default();
+        let circuit = breaker(backend.clone());
+
+        for _ in 0..2 {
+            backend.down.store(true, Ordering::SeqCst);
+            assert!(circuit.get("key").await.is_err());
+        }
+        backend.down.store(false, Ordering::SeqCst);
+        circuit.get("key").await.expect("Failed to get");
+        backend.down.store(true, Ordering::SeqCst);
+        assert!(circuit.get("key").await.is_err());
+
+        assert_eq!(circuit.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_half_open_probe() {
+        struct TransitionMetrics {
+            transitions: Arc<Mutex<Vec<(CircuitState, CircuitState)>>>,
+        }
+
+        impl CacheMetrics for TransitionMetrics {
+            fn record_circuit_transition(&self, from: CircuitState, to: CircuitState) {
+                self.transitions
+                    .lock()
+                    .expect("Failed to lock transitions")
+                    .push((from, to));
+            }
+        }
+
+        let transitions = Arc::new(Mutex::new(Vec::new()));
+        let backend = FlakyBackend::default();
+        let circuit = breaker(backend.clone()).with_metrics(Box::new(TransitionMetrics {
+            transitions: transitions.clone(),
+        }));
+
+        backend.down.store(true, Ordering::SeqCst);
+        for _ in 0..3 {
+            let _ = circuit.get("key").await;
+        }
+
+        // Probe fails: circuit re-opens
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(circuit.get("key").await.expect("Failed to get"), None);
+        assert_eq!(circuit.state(), CircuitState::Open);
+
+        // Probe succeeds: circuit closes and the call goes through
+        backend.down.store(false, Ordering::SeqCst);
+        backend
+            .inner
+            .set("key", b"value".to_vec(), None)
+            .await
+            .expect("Failed to set");
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(
+            circuit.get("key").await.expect("Failed to get"),
+            Some(b"value".to_vec())
+        );
+        assert_eq!(circuit.state(), CircuitState::Closed);
+
+        use CircuitState::*;
+        assert_eq!(
+            *transitions.lock().expect("Failed to lock transitions"),
+            vec![
+                (Closed, Open),
+                (Open, HalfOpen),
+                (HalfOpen, Open),
+                (Open, HalfOpen),
+                (HalfOpen, Closed),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_circuit_counts_timeouts() {
+        #[derive(Clone)]
+        struct SlowBackend;
+
+        impl CacheBackend for SlowBackend {
+            async fn get(&self, _key: &str) -> Result<Option<Vec<u8>>> {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(None)
+            }
+
+            async fn set(&self, _key: &str, _value: Vec<u8>, _ttl: Option<Duration>) -> Result<()> {
+                Ok(())
+            }
+
+            async fn delete(&self, _key: &str) -> Result<()> {
+                Ok(())
+            }
+        }
+
+        let circuit = CircuitBreakerBackend::new(
+            SlowBackend,
+            CircuitBreakerConfig::default()
+                .with_failure_threshold(1)
+                .with_call_timeout(Some(Duration::from_millis(10))),
+        );
+
+        assert!(matches!(circuit.get("key").await, Err(Error::Timeout(_))));
+        assert_eq!(circuit.state(), CircuitState::Open);
+    }
+}
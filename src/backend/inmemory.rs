@@ -3,9 +3,10 @@
 //! Uses DashMap for lock-free concurrent access with per-key sharding.
 //! Automatically handles TTL expiration on access.
 
-use super::CacheBackend;
+use super::{parse_counter, CacheBackend, ValueWithTtl};
 use crate::error::Result;
 use dashmap::DashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
@@ -27,12 +28,28 @@ impl CacheEntry {
     }
 }
 
+/// In-memory set with optional expiration.
+#[derive(Default)]
+struct SetEntry {
+    members: HashSet<String>,
+    expires_at: Option<Instant>,
+}
+
+impl SetEntry {
+    fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|exp| Instant::now() > exp)
+    }
+}
+
 /// Thread-safe async in-memory cache backend.
 ///
 /// Uses DashMap for lock-free concurrent access with fine-grained per-key sharding.
 /// No async locks required - operations are non-blocking.
 /// Automatically handles TTL expiration on access.
 ///
+/// Sets (used for tag membership) are kept natively, separate from values,
+/// and expire like them.
+///
 /// # Example
 ///
 /// ```no_run
@@ -59,6 +76,7 @@ impl CacheEntry {
 #[derive(Clone)]
 pub struct InMemoryBackend {
     store: Arc<DashMap<String, CacheEntry>>,
+    sets: Arc<DashMap<String, SetEntry>>,
 }
 
 impl InMemoryBackend {
@@ -66,6 +84,7 @@ impl InMemoryBackend {
     pub fn new() -> Self {
         InMemoryBackend {
             store: Arc::new(DashMap::new()),
+            sets: Arc::new(DashMap::new()),
         }
     }
 
@@ -138,6 +157,7 @@ impl CacheBackend for InMemoryBackend {
 
     async fn delete(&self, key: &str) -> Result<()> {
         self.store.remove(key);
+        self.sets.remove(key);
         debug!("✓ InMemory DELETE {}", key);
         Ok(())
     }
@@ -170,22 +190,127 @@ impl CacheBackend for InMemoryBackend {
         Ok(results)
     }
 
+    async fn mget_with_ttl(&self, keys: &[&str]) -> Result<Vec<Option<ValueWithTtl>>> {
+        let now = Instant::now();
+        let results = keys
+            .iter()
+            .map(|k| {
+                let entry = self.store.get(*k)?;
+                if entry.is_expired() {
+                    return None;
+                }
+                let remaining = entry
+                    .expires_at
+                    .map(|exp| exp.saturating_duration_since(now));
+                Some((entry.data.clone(), remaining))
+            })
+            .collect();
+
+        debug!("✓ InMemory MGET {} keys (with TTL)", keys.len());
+        Ok(results)
+    }
+
+    async fn mset(&self, items: &[(&str, Vec<u8>)], ttl: Option<Duration>) -> Result<()> {
+        for (key, value) in items {
+            self.store
+                .insert(key.to_string(), CacheEntry::new(value.clone(), ttl));
+        }
+
+        debug!("✓ InMemory MSET {} keys", items.len());
+        Ok(())
+    }
+
     async fn mdelete(&self, keys: &[&str]) -> Result<()> {
         for key in keys {
             self.store.remove(*key);
+            self.sets.remove(*key);
         }
 
         debug!("✓ InMemory MDELETE {} keys", keys.len());
         Ok(())
     }
 
+    async fn incr(&self, key: &str) -> Result<u64> {
+        let mut entry = self
+            .store
+            .entry(key.to_string())
+            .or_insert_with(|| CacheEntry::new(b"0".to_vec(), None));
+        if entry.is_expired() {
+            *entry = CacheEntry::new(b"0".to_vec(), None);
+        }
+
+        let value = parse_counter(Some(&entry.data))? + 1;
+        entry.data = value.to_string().into_bytes();
+
+        debug!("✓ InMemory INCR {} -> {}", key, value);
+        Ok(value)
+    }
+
+    async fn sadd(&self, key: &str, members: &[&str], ttl: Option<Duration>) -> Result<()> {
+        let deadline = ttl.map(|d| Instant::now() + d);
+        let mut set = self
+            .sets
+            .entry(key.to_string())
+            .or_insert_with(|| SetEntry {
+                members: HashSet::new(),
+                expires_at: deadline,
+            });
+        if set.is_expired() {
+            *set = SetEntry {
+                members: HashSet::new(),
+                expires_at: deadline,
+            };
+        }
+
+        // Only ever extend the expiry; `None` keeps the set
+        set.expires_at = set.expires_at.zip(deadline).map(|(a, b)| a.max(b));
+        set.members
+            .extend(members.iter().map(|member| member.to_string()));
+
+        debug!("✓ InMemory SADD {} ({} members)", key, members.len());
+        Ok(())
+    }
+
+    async fn smembers(&self, key: &str) -> Result<Vec<String>> {
+        if let Some(set) = self.sets.get(key) {
+            if !set.is_expired() {
+                return Ok(set.members.iter().cloned().collect());
+            }
+        }
+
+        self.sets.remove_if(key, |_, set| set.is_expired());
+        Ok(Vec::new())
+    }
+
+    async fn srem(&self, key: &str, members: &[&str]) -> Result<()> {
+        if let Some(mut set) = self.sets.get_mut(key) {
+            for member in members {
+                set.members.remove(*member);
+            }
+        }
+        self.sets
+            .remove_if(key, |_, set| set.members.is_empty() || set.is_expired());
+
+        debug!("✓ InMemory SREM {} ({} members)", key, members.len());
+        Ok(())
+    }
+
     async fn health_check(&self) -> Result<bool> {
         // In-memory backend is always healthy
         Ok(true)
     }
 
+    async fn delete_prefix(&self, prefix: &str) -> Result<usize> {
+        let before = self.store.len();
+        self.store.retain(|key, _| !key.starts_with(prefix));
+        let deleted = before.saturating_sub(self.store.len());
+        debug!("✓ InMemory DELETE prefix {} ({} entries)", prefix, deleted);
+        Ok(deleted)
+    }
+
     async fn clear_all(&self) -> Result<()> {
         self.store.clear();
+        self.sets.clear();
         warn!("⚠ InMemory CLEAR_ALL executed - all cache cleared!");
         Ok(())
     }
@@ -244,6 +369,34 @@ mod tests {
             .expect("Failed to check exists"));
     }
 
+    #[tokio::test]
+    async fn test_inmemory_backend_delete_prefix() {
+        let backend = InMemoryBackend::new();
+
+        for key in ["user:1", "user:2", "product:1"] {
+            backend
+                .set(key, b"value".to_vec(), None)
+                .await
+                .expect("Failed to set");
+        }
+
+        assert_eq!(
+            backend
+                .delete_prefix("user:")
+                .await
+                .expect("Failed to delete prefix"),
+            2
+        );
+        assert!(!backend
+            .exists("user:1")
+            .await
+            .expect("Failed to check existence"));
+        assert!(backend
+            .exists("product:1")
+            .await
+            .expect("Failed to check existence"));
+    }
+
     #[tokio::test]
     async fn test_inmemory_backend_ttl_expiration() {
         let backend = InMemoryBackend::new();
@@ -287,6 +440,25 @@ mod tests {
         assert_eq!(results[2], None);
     }
 
+    #[tokio::test]
+    async fn test_inmemory_backend_mset() {
+        let backend = InMemoryBackend::new();
+
+        backend
+            .mset(
+                &[("key1", b"value1".to_vec()), ("key2", b"value2".to_vec())],
+                Some(Duration::from_secs(60)),
+            )
+            .await
+            .expect("Failed to mset");
+
+        assert_eq!(backend.len().await, 2);
+        assert_eq!(
+            backend.get("key2").await.expect("Failed to get"),
+            Some(b"value2".to_vec())
+        );
+    }
+
     #[tokio::test]
     async fn test_inmemory_backend_mdelete() {
         let backend = InMemoryBackend::new();
@@ -315,6 +487,137 @@ mod tests {
         assert!(backend.get("key3").await.expect("Failed to get").is_some());
     }
 
+    #[tokio::test]
+    async fn test_inmemory_backend_incr() {
+        let backend = InMemoryBackend::new();
+
+        assert_eq!(backend.incr("counter").await.expect("Failed to incr"), 1);
+        assert_eq!(backend.incr("counter").await.expect("Failed to incr"), 2);
+        assert_eq!(
+            backend.get("counter").await.expect("Failed to get"),
+            Some(b"2".to_vec())
+        );
+
+        backend
+            .set("text", b"abc".to_vec(), None)
+            .await
+            .expect("Failed to set");
+        assert!(backend.incr("text").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_inmemory_backend_sets() {
+        let backend = InMemoryBackend::new();
+
+        backend
+            .sadd("tag", &["key1", "key2"], None)
+            .await
+            .expect("Failed to sadd");
+        backend
+            .sadd("tag", &["key2"], None)
+            .await
+            .expect("Failed to sadd");
+
+        let mut members = backend.smembers("tag").await.expect("Failed to smembers");
+        members.sort();
+        assert_eq!(members, vec!["key1", "key2"]);
+
+        // Sets are not values
+        assert!(backend.get("tag").await.expect("Failed to get").is_none());
+
+        backend
+            .srem("tag", &["key1", "key2"])
+            .await
+            .expect("Failed to srem");
+        assert!(backend
+            .smembers("tag")
+            .await
+            .expect("Failed to smembers")
+            .is_empty());
+        assert!(backend.sets.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_inmemory_backend_mget_with_ttl() {
+        let backend = InMemoryBackend::new();
+
+        backend
+            .set("key1", b"1".to_vec(), Some(Duration::from_secs(60)))
+            .await
+            .expect("Failed to set");
+        backend
+            .set("key2", b"2".to_vec(), None)
+            .await
+            .expect("Failed to set");
+
+        let values = backend
+            .mget_with_ttl(&["key1", "key2", "key3"])
+            .await
+            .expect("Failed to mget");
+        let (value, remaining) = values[0].clone().expect("Missing key1");
+        assert_eq!(value, b"1".to_vec());
+        assert!(remaining.is_some_and(|ttl| ttl <= Duration::from_secs(60)));
+        assert_eq!(values[1], Some((b"2".to_vec(), None)));
+        assert_eq!(values[2], None);
+    }
+
+    #[tokio::test]
+    async fn test_inmemory_backend_set_expiration() {
+        let backend = InMemoryBackend::new();
+
+        backend
+            .sadd("short", &["key1"], Some(Duration::from_millis(50)))
+            .await
+            .expect("Failed to sadd");
+        // A longer TTL extends the expiry, a shorter one never shortens it
+        backend
+            .sadd("long", &["key1"], Some(Duration::from_millis(50)))
+            .await
+            .expect("Failed to sadd");
+        backend
+            .sadd("long", &["key2"], Some(Duration::from_secs(60)))
+            .await
+            .expect("Failed to sadd");
+        backend
+            .sadd("long", &["key3"], Some(Duration::from_millis(10)))
+            .await
+            .expect("Failed to sadd");
+        // Sets without a TTL persist
+        backend
+            .sadd("persistent", &["key1"], None)
+            .await
+            .expect("Failed to sadd");
+        backend
+            .sadd("persistent", &["key2"], Some(Duration::from_millis(10)))
+            .await
+            .expect("Failed to sadd");
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert!(backend
+            .smembers("short")
+            .await
+            .expect("Failed to smembers")
+            .is_empty());
+        assert_eq!(
+            backend
+                .smembers("long")
+                .await
+                .expect("Failed to smembers")
+                .len(),
+            3
+        );
+        assert_eq!(
+            backend
+                .smembers("persistent")
+                .await
+                .expect("Failed to smembers")
+                .len(),
+            2
+        );
+        assert!(!backend.sets.contains_key("short"));
+    }
+
     #[tokio::test]
     async fn test_inmemory_backend_clear_all() {
         let backend = InMemoryBackend::new();
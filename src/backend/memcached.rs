@@ -1,9 +1,9 @@
 //! Memcached cache backend implementation.
 
 use super::CacheBackend;
-use crate::error::{Error, Result};
+use crate::error::{with_deadline, Error, Result, TimeoutPhase};
 use async_memcached::AsciiProtocol;
-use deadpool_memcached::{Manager, Pool};
+use deadpool_memcached::{Manager, Object, Pool};
 use std::time::Duration;
 
 /// Default Memcached connection pool size.
@@ -16,6 +16,7 @@ const DEFAULT_POOL_SIZE: u32 = 16;
 #[derive(Clone, Debug)]
 pub struct MemcachedConfig {
     pub servers: Vec<String>, // e.g., ["localhost:11211", "cache2:11211"]
+    /// Deadline for checking out (or opening) a pooled connection.
     pub connection_timeout: Duration,
     pub pool_size: u32,
 }
@@ -34,6 +35,13 @@ impl Default for MemcachedConfig {
 ///
 /// Provides distributed caching using Memcached protocol via async connection pool.
 ///
+/// Memcached has no sets or atomic counters, so `sadd`, `srem` and `incr` use
+/// the non-atomic `get` + `set` defaults of `CacheBackend`. Concurrent writers
+/// to one set can lose each other's members: tag, dependency, query and
+/// pointer memberships recorded at the same time may be dropped, and a later
+/// invalidation then misses those entries until they expire. Prefer Redis or
+/// `InMemoryBackend` when those invalidations must be exact.
+///
 /// # Example
 ///
 /// ```no_run
@@ -54,6 +62,7 @@ impl Default for MemcachedConfig {
 #[derive(Clone)]
 pub struct MemcachedBackend {
     pool: Pool,
+    connection_timeout: Duration,
 }
 
 impl MemcachedBackend {
@@ -82,7 +91,10 @@ impl MemcachedBackend {
             addr, config.pool_size
         );
 
-        Ok(MemcachedBackend { pool })
+        Ok(MemcachedBackend {
+            pool,
+            connection_timeout: config.connection_timeout,
+        })
     }
 
     /// Create from server address directly.
@@ -106,13 +118,27 @@ impl MemcachedBackend {
         };
         Self::new(config).await
     }
+
+    /// Check out a pooled connection, opening one if needed.
+    ///
+    /// Bounded by `MemcachedConfig::connection_timeout`.
+    async fn connection(&self) -> Result<Object> {
+        with_deadline(
+            TimeoutPhase::Connect,
+            Some(self.connection_timeout),
+            async {
+                self.pool.get().await.map_err(|e| {
+                    Error::BackendError(format!("Failed to get Memcached connection: {}", e))
+                })
+            },
+        )
+        .await
+    }
 }
 
 impl CacheBackend for MemcachedBackend {
     async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
-        let mut conn = self.pool.get().await.map_err(|e| {
-            Error::BackendError(format!("Failed to get Memcached connection: {}", e))
-        })?;
+        let mut conn = self.connection().await?;
 
         match conn.get(key).await {
             Ok(Some(value)) => {
@@ -131,9 +157,7 @@ impl CacheBackend for MemcachedBackend {
     }
 
     async fn set(&self, key: &str, value: Vec<u8>, ttl: Option<Duration>) -> Result<()> {
-        let mut conn = self.pool.get().await.map_err(|e| {
-            Error::BackendError(format!("Failed to get Memcached connection: {}", e))
-        })?;
+        let mut conn = self.connection().await?;
 
         // Convert Duration to i64 seconds for Memcached TTL
         // Values < 2592000 (30 days) are interpreted as seconds from now
@@ -157,9 +181,7 @@ impl CacheBackend for MemcachedBackend {
     }
 
     async fn delete(&self, key: &str) -> Result<()> {
-        let mut conn = self.pool.get().await.map_err(|e| {
-            Error::BackendError(format!("Failed to get Memcached connection: {}", e))
-        })?;
+        let mut conn = self.connection().await?;
 
         conn.delete(key).await.map_err(|e| {
             Error::BackendError(format!("Memcached DELETE failed for key {}: {}", key, e))
@@ -171,9 +193,7 @@ impl CacheBackend for MemcachedBackend {
 
     async fn exists(&self, key: &str) -> Result<bool> {
         // Memcached doesn't have native EXISTS, use get to check
-        let mut conn = self.pool.get().await.map_err(|e| {
-            Error::BackendError(format!("Failed to get Memcached connection: {}", e))
-        })?;
+        let mut conn = self.connection().await?;
 
         match conn.get(key).await {
             Ok(Some(value)) => Ok(value.data.is_some()),
@@ -190,9 +210,7 @@ impl CacheBackend for MemcachedBackend {
             return Ok(Vec::new());
         }
 
-        let mut conn = self.pool.get().await.map_err(|e| {
-            Error::BackendError(format!("Failed to get Memcached connection: {}", e))
-        })?;
+        let mut conn = self.connection().await?;
 
         // Use native get_multi for batch retrieval - single round trip
         // Note: get_multi may return "not found" error if no keys exist
@@ -239,9 +257,7 @@ impl CacheBackend for MemcachedBackend {
     }
 
     async fn mdelete(&self, keys: &[&str]) -> Result<()> {
-        let mut conn = self.pool.get().await.map_err(|e| {
-            Error::BackendError(format!("Failed to get Memcached connection: {}", e))
-        })?;
+        let mut conn = self.connection().await?;
 
         for key in keys {
             // Ignore errors for individual deletions
@@ -254,7 +270,7 @@ impl CacheBackend for MemcachedBackend {
 
     async fn health_check(&self) -> Result<bool> {
         // Try to get a connection and perform a simple operation
-        match self.pool.get().await {
+        match self.connection().await {
             Ok(mut conn) => {
                 // Try a simple get operation to verify the connection works
                 match conn.get("__health_check__").await {
@@ -267,9 +283,7 @@ impl CacheBackend for MemcachedBackend {
     }
 
     async fn clear_all(&self) -> Result<()> {
-        let mut conn = self.pool.get().await.map_err(|e| {
-            Error::BackendError(format!("Failed to get Memcached connection: {}", e))
-        })?;
+        let mut conn = self.connection().await?;
 
         conn.flush_all()
             .await
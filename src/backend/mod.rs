@@ -1,19 +1,29 @@
 //! Cache backend implementations.
 
-use crate::error::Result;
+use crate::error::{Error, Result};
+use crate::serialization::unix_millis;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
 use std::time::Duration;
 
+pub mod circuit_breaker;
 pub mod inmemory;
 #[cfg(feature = "memcached")]
 pub mod memcached;
 #[cfg(feature = "redis")]
 pub mod redis;
+pub mod tiered;
 
+pub use circuit_breaker::{CircuitBreakerBackend, CircuitBreakerConfig, CircuitState};
 pub use inmemory::InMemoryBackend;
 #[cfg(feature = "memcached")]
 pub use memcached::{MemcachedBackend, MemcachedConfig};
 #[cfg(feature = "redis")]
 pub use redis::{PoolStats, RedisBackend, RedisConfig};
+pub use tiered::{CacheTier, TieredBackend, TieredConfig};
+
+/// Cached value and its remaining TTL, as returned by [`CacheBackend::mget_with_ttl`].
+pub type ValueWithTtl = (Vec<u8>, Option<Duration>);
 
 /// Trait for cache backend implementations.
 ///
@@ -23,8 +33,9 @@ pub use redis::{PoolStats, RedisBackend, RedisConfig};
 /// **IMPORTANT:** All methods use `&self` instead of `&mut self` to allow concurrent access.
 /// Backend implementations should use interior mutability (RwLock, Mutex, or external storage).
 ///
-/// **ASYNC:** All methods are async and must be awaited.
-#[allow(async_fn_in_trait)]
+/// **ASYNC:** All methods are async and must be awaited. The returned futures are
+/// `Send` so cache operations can run on spawned tasks; implementations can still
+/// use `async fn`.
 pub trait CacheBackend: Send + Sync + Clone {
     /// Retrieve value from cache by key.
     ///
@@ -34,7 +45,7 @@ pub trait CacheBackend: Send + Sync + Clone {
     ///
     /// # Errors
     /// Returns `Err` if backend error occurs (connection lost, etc.)
-    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+    fn get(&self, key: &str) -> impl Future<Output = Result<Option<Vec<u8>>>> + Send;
 
     /// Store value in cache with optional TTL.
     ///
@@ -45,20 +56,25 @@ pub trait CacheBackend: Send + Sync + Clone {
     ///
     /// # Errors
     /// Returns `Err` if backend error occurs
-    async fn set(&self, key: &str, value: Vec<u8>, ttl: Option<Duration>) -> Result<()>;
+    fn set(
+        &self,
+        key: &str,
+        value: Vec<u8>,
+        ttl: Option<Duration>,
+    ) -> impl Future<Output = Result<()>> + Send;
 
     /// Remove value from cache.
     ///
     /// # Errors
     /// Returns `Err` if backend error occurs
-    async fn delete(&self, key: &str) -> Result<()>;
+    fn delete(&self, key: &str) -> impl Future<Output = Result<()>> + Send;
 
     /// Check if key exists in cache (optional optimization).
     ///
     /// # Errors
     /// Returns `Err` if backend error occurs
-    async fn exists(&self, key: &str) -> Result<bool> {
-        Ok(self.get(key).await?.is_some())
+    fn exists(&self, key: &str) -> impl Future<Output = Result<bool>> + Send {
+        async move { Ok(self.get(key).await?.is_some()) }
     }
 
     /// Bulk get operation (optional optimization).
@@ -68,12 +84,56 @@ pub trait CacheBackend: Send + Sync + Clone {
     ///
     /// # Errors
     /// Returns `Err` if backend error occurs
-    async fn mget(&self, keys: &[&str]) -> Result<Vec<Option<Vec<u8>>>> {
-        let mut results = Vec::with_capacity(keys.len());
-        for key in keys {
-            results.push(self.get(key).await?);
+    fn mget(&self, keys: &[&str]) -> impl Future<Output = Result<Vec<Option<Vec<u8>>>>> + Send {
+        async move {
+            let mut results = Vec::with_capacity(keys.len());
+            for key in keys {
+                results.push(self.get(key).await?);
+            }
+            Ok(results)
+        }
+    }
+
+    /// Bulk get returning each value with its remaining TTL (optional optimization).
+    ///
+    /// The TTL is `None` if the key has no expiry or the backend cannot report
+    /// it. Used by [`TieredBackend`] to keep promoted L1 copies from outliving
+    /// the L2 entry. Default implementation calls `mget()` and reports no TTLs.
+    ///
+    /// # Errors
+    /// Returns `Err` if backend error occurs
+    fn mget_with_ttl(
+        &self,
+        keys: &[&str],
+    ) -> impl Future<Output = Result<Vec<Option<ValueWithTtl>>>> + Send {
+        async move {
+            let values = self.mget(keys).await?;
+            Ok(values
+                .into_iter()
+                .map(|value| value.map(|value| (value, None)))
+                .collect())
+        }
+    }
+
+    /// Bulk set operation (optional optimization).
+    ///
+    /// Stores every `(key, value)` pair with the same TTL.
+    /// Default implementation calls `set()` for each pair.
+    /// Override for batch efficiency (e.g., a Redis pipeline).
+    ///
+    /// # Errors
+    /// Returns `Err` if backend error occurs
+    fn mset(
+        &self,
+        items: &[(&str, Vec<u8>)],
+        ttl: Option<Duration>,
+    ) -> impl Future<Output = Result<()>> + Send {
+        async move {
+            for (key, value) in items {
+                self.set(key, value.clone(), ttl).await?;
+            }
+            Ok(())
         }
-        Ok(results)
     }
 
     /// Bulk delete operation (optional optimization).
@@ -83,11 +143,169 @@ pub trait CacheBackend: Send + Sync + Clone {
     ///
     /// # Errors
     /// Returns `Err` if backend error occurs
-    async fn mdelete(&self, keys: &[&str]) -> Result<()> {
-        for key in keys {
-            self.delete(key).await?;
+    fn mdelete(&self, keys: &[&str]) -> impl Future<Output = Result<()>> + Send {
+        async move {
+            for key in keys {
+                self.delete(key).await?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Add members to the set stored at `key` (optional optimization).
+    ///
+    /// Used to record tag, dependency, query and pointer membership. The set
+    /// expires no earlier than `ttl` from now: an existing expiry is only
+    /// extended, and `None` keeps the set until it is emptied. Callers pass the
+    /// longest TTL of the entries the members name, so sets lapse with them.
+    ///
+    /// Default implementation keeps the set as a serialized list under `key`
+    /// and updates it with `get()` + `set()`, which is not atomic: concurrent
+    /// writers can lose each other's members. Override with native set support
+    /// (e.g. Redis SADD).
+    ///
+    /// # Errors
+    /// Returns `Err` if backend error occurs
+    fn sadd(
+        &self,
+        key: &str,
+        members: &[&str],
+        ttl: Option<Duration>,
+    ) -> impl Future<Output = Result<()>> + Send {
+        async move {
+            let now = unix_millis();
+            let deadline = ttl.map(|ttl| now.saturating_add(ttl.as_millis() as u64));
+            let mut set = match self.get(key).await? {
+                Some(bytes) => {
+                    let mut set = decode_set(&bytes)?;
+                    set.expires_at_ms = set.expires_at_ms.zip(deadline).map(|(a, b)| a.max(b));
+                    set
+                }
+                None => StoredSet {
+                    members: Vec::new(),
+                    expires_at_ms: deadline,
+                },
+            };
+            for member in members {
+                if !set.members.iter().any(|existing| existing == member) {
+                    set.members.push(member.to_string());
+                }
+            }
+            self.set(key, encode_set(&set)?, set.remaining(now)).await
+        }
+    }
+
+    /// Return the members of the set stored at `key`, or an empty list.
+    ///
+    /// Default implementation reads the serialized list written by the default
+    /// `sadd()`.
+    ///
+    /// # Errors
+    /// Returns `Err` if backend error occurs or the stored value is not a set
+    fn smembers(&self, key: &str) -> impl Future<Output = Result<Vec<String>>> + Send {
+        async move {
+            match self.get(key).await? {
+                Some(bytes) => Ok(decode_set(&bytes)?.members),
+                None => Ok(Vec::new()),
+            }
+        }
+    }
+
+    /// Remove members from the set stored at `key`.
+    ///
+    /// Default implementation rewrites the serialized list, keeping its
+    /// expiry, and deletes it once empty.
+    ///
+    /// # Errors
+    /// Returns `Err` if backend error occurs
+    fn srem(&self, key: &str, members: &[&str]) -> impl Future<Output = Result<()>> + Send {
+        async move {
+            let Some(bytes) = self.get(key).await? else {
+                return Ok(());
+            };
+            let mut set = decode_set(&bytes)?;
+            set.members
+                .retain(|existing| !members.contains(&existing.as_str()));
+            if set.members.is_empty() {
+                self.delete(key).await
+            } else {
+                self.set(key, encode_set(&set)?, set.remaining(unix_millis()))
+                    .await
+            }
+        }
+    }
+
+    /// Bulk `sadd()` (optional optimization).
+    ///
+    /// Adds each `(key, members)` pair with the same TTL.
+    /// Default implementation calls `sadd()` for each pair.
+    /// Override for batch efficiency (e.g., a Redis pipeline).
+    ///
+    /// # Errors
+    /// Returns `Err` if backend error occurs
+    fn msadd(
+        &self,
+        items: &[(&str, Vec<&str>)],
+        ttl: Option<Duration>,
+    ) -> impl Future<Output = Result<()>> + Send {
+        async move {
+            for (key, members) in items {
+                self.sadd(key, members, ttl).await?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Bulk `smembers()` (optional optimization).
+    ///
+    /// Returns the members of each set, in the order of `keys`.
+    /// Default implementation calls `smembers()` for each key.
+    /// Override for batch efficiency (e.g., a Redis pipeline).
+    ///
+    /// # Errors
+    /// Returns `Err` if backend error occurs
+    fn msmembers(&self, keys: &[&str]) -> impl Future<Output = Result<Vec<Vec<String>>>> + Send {
+        async move {
+            let mut results = Vec::with_capacity(keys.len());
+            for key in keys {
+                results.push(self.smembers(key).await?);
+            }
+            Ok(results)
+        }
+    }
+
+    /// Bulk `srem()` (optional optimization).
+    ///
+    /// Default implementation calls `srem()` for each pair.
+    /// Override for batch efficiency (e.g., a Redis pipeline).
+    ///
+    /// # Errors
+    /// Returns `Err` if backend error occurs
+    fn msrem(&self, items: &[(&str, Vec<&str>)]) -> impl Future<Output = Result<()>> + Send {
+        async move {
+            for (key, members) in items {
+                self.srem(key, members).await?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Increment the counter stored at `key` and return the new value.
+    ///
+    /// A missing counter starts at 0. Counters are stored as decimal strings
+    /// without a TTL (as with Redis INCR), so `get()` returns their digits.
+    /// Used for namespace generations. Default implementation uses `get()` +
+    /// `set()`, which is not atomic: concurrent increments can be lost.
+    /// Override with a native counter.
+    ///
+    /// # Errors
+    /// Returns `Err` if backend error occurs or the stored value is not a counter
+    fn incr(&self, key: &str) -> impl Future<Output = Result<u64>> + Send {
+        async move {
+            let value = parse_counter(self.get(key).await?.as_deref())? + 1;
+            self.set(key, value.to_string().into_bytes(), None).await?;
+            Ok(value)
         }
-        Ok(())
     }
 
     /// Health check - verify backend is accessible.
@@ -96,21 +314,75 @@ pub trait CacheBackend: Send + Sync + Clone {
     ///
     /// # Errors
     /// Returns `Err` if backend is not accessible
-    async fn health_check(&self) -> Result<bool> {
-        Ok(true)
+    fn health_check(&self) -> impl Future<Output = Result<bool>> + Send {
+        async { Ok(true) }
+    }
+
+    /// Optional: Delete every entry whose key starts with `prefix`.
+    ///
+    /// Returns the number of entries deleted. Sets are not affected. Needed by
+    /// the L1 tier of a `TieredBackend` with an invalidation bus.
+    ///
+    /// # Errors
+    /// Returns `Err` if operation is not implemented or fails
+    fn delete_prefix(&self, _prefix: &str) -> impl Future<Output = Result<usize>> + Send {
+        async {
+            Err(crate::error::Error::NotImplemented(
+                "delete_prefix not implemented for this backend".to_string(),
+            ))
+        }
     }
 
     /// Optional: Clear all cache (use with caution).
     ///
     /// # Errors
     /// Returns `Err` if operation is not implemented or fails
-    async fn clear_all(&self) -> Result<()> {
-        Err(crate::error::Error::NotImplemented(
-            "clear_all not implemented for this backend".to_string(),
-        ))
+    fn clear_all(&self) -> impl Future<Output = Result<()>> + Send {
+        async {
+            Err(crate::error::Error::NotImplemented(
+                "clear_all not implemented for this backend".to_string(),
+            ))
+        }
+    }
+}
+
+/// Parse a counter written by `incr()`; a missing counter is 0.
+pub(crate) fn parse_counter(bytes: Option<&[u8]>) -> Result<u64> {
+    let Some(bytes) = bytes else {
+        return Ok(0);
+    };
+    std::str::from_utf8(bytes)
+        .ok()
+        .and_then(|digits| digits.parse().ok())
+        .ok_or_else(|| Error::DeserializationError("value is not a counter".to_string()))
+}
+
+/// A set stored by the default `sadd()` / `srem()` implementations.
+#[derive(Serialize, Deserialize)]
+struct StoredSet {
+    members: Vec<String>,
+    expires_at_ms: Option<u64>,
+}
+
+impl StoredSet {
+    /// TTL left at `now`, rounded up to whole seconds for backends that
+    /// truncate TTLs to seconds.
+    fn remaining(&self, now: u64) -> Option<Duration> {
+        self.expires_at_ms
+            .map(|deadline| Duration::from_secs(deadline.saturating_sub(now).div_ceil(1000).max(1)))
     }
 }
 
+/// Encode a set for the default `sadd()` / `srem()` implementations.
+fn encode_set(set: &StoredSet) -> Result<Vec<u8>> {
+    postcard::to_allocvec(set).map_err(|e| Error::SerializationError(e.to_string()))
+}
+
+/// Decode a set written by `encode_set()`.
+fn decode_set(bytes: &[u8]) -> Result<StoredSet> {
+    postcard::from_bytes(bytes).map_err(|e| Error::DeserializationError(e.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,4 +400,69 @@ mod tests {
             .await
             .expect("Failed to check exists"));
     }
+
+    #[tokio::test]
+    async fn test_backend_mset_default() {
+        #[derive(Clone, Default)]
+        struct SetOnlyBackend {
+            inner: InMemoryBackend,
+        }
+
+        impl CacheBackend for SetOnlyBackend {
+            async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+                self.inner.get(key).await
+            }
+
+            async fn set(&self, key: &str, value: Vec<u8>, ttl: Option<Duration>) -> Result<()> {
+                self.inner.set(key, value, ttl).await
+            }
+
+            async fn delete(&self, key: &str) -> Result<()> {
+                self.inner.delete(key).await
+            }
+        }
+
+        let backend = SetOnlyBackend::default();
+        backend
+            .mset(&[("a", vec![1]), ("b", vec![2])], None)
+            .await
+            .expect("Failed to mset");
+
+        let values = backend.mget(&["a", "b"]).await.expect("Failed to mget");
+        assert_eq!(values, vec![Some(vec![1]), Some(vec![2])]);
+
+        // Sets fall back to a serialized list
+        backend
+            .sadd("s", &["x", "y"], None)
+            .await
+            .expect("Failed to sadd");
+        backend
+            .sadd("s", &["y", "z"], None)
+            .await
+            .expect("Failed to sadd");
+        assert_eq!(
+            backend.smembers("s").await.expect("Failed to smembers"),
+            vec!["x", "y", "z"]
+        );
+
+        backend
+            .srem("s", &["x", "z"])
+            .await
+            .expect("Failed to srem");
+        assert_eq!(
+            backend.smembers("s").await.expect("Failed to smembers"),
+            vec!["y"]
+        );
+        backend.srem("s", &["y"]).await.expect("Failed to srem");
+        assert!(!backend.exists("s").await.expect("Failed to check exists"));
+
+        // Counters fall back to get + set
+        assert_eq!(backend.incr("n").await.expect("Failed to incr"), 1);
+        assert_eq!(backend.incr("n").await.expect("Failed to incr"), 2);
+        assert_eq!(
+            backend.get("n").await.expect("Failed to get"),
+            Some(b"2".to_vec())
+        );
+        assert!(backend.incr("a").await.is_err());
+    }
 }
@@ -1,8 +1,8 @@
 //! Redis cache backend implementation.
 
-use super::CacheBackend;
-use crate::error::{Error, Result};
-use deadpool_redis::{redis::AsyncCommands, Config as PoolConfig, Pool, Runtime};
+use super::{CacheBackend, ValueWithTtl};
+use crate::error::{with_deadline, Error, Result, TimeoutPhase};
+use deadpool_redis::{redis::AsyncCommands, Config as PoolConfig, Connection, Pool, Runtime};
 use std::time::Duration;
 
 /// Pool statistics information.
@@ -27,6 +27,7 @@ pub struct RedisConfig {
     pub password: Option<String>,
     pub database: u32,
     pub pool_size: u32,
+    /// Deadline for checking out (or opening) a pooled connection.
     pub connection_timeout: Duration,
 }
 
@@ -86,6 +87,7 @@ impl RedisConfig {
 #[derive(Clone)]
 pub struct RedisBackend {
     pool: Pool,
+    connection_timeout: Duration,
 }
 
 impl RedisBackend {
@@ -107,7 +109,10 @@ impl RedisBackend {
             config.host, config.port
         );
 
-        Ok(RedisBackend { pool })
+        Ok(RedisBackend {
+            pool,
+            connection_timeout: config.connection_timeout,
+        })
     }
 
     /// Create from connection string directly.
@@ -116,6 +121,8 @@ impl RedisBackend {
     /// 1. `REDIS_POOL_SIZE` environment variable (if set)
     /// 2. `DEFAULT_POOL_SIZE` constant (16)
     ///
+    /// Uses the default `RedisConfig::connection_timeout`.
+    ///
     /// # Errors
     /// Returns `Err` if pool creation fails or connection cannot be established.
     pub async fn from_connection_string(conn_str: &str) -> Result<Self> {
@@ -136,7 +143,26 @@ impl RedisBackend {
             pool_size
         );
 
-        Ok(RedisBackend { pool })
+        Ok(RedisBackend {
+            pool,
+            connection_timeout: RedisConfig::default().connection_timeout,
+        })
+    }
+
+    /// Check out a pooled connection, connecting if needed.
+    ///
+    /// Bounded by `RedisConfig::connection_timeout`.
+    pub(crate) async fn connection(&self) -> Result<Connection> {
+        with_deadline(
+            TimeoutPhase::Connect,
+            Some(self.connection_timeout),
+            async {
+                self.pool.get().await.map_err(|e| {
+                    Error::BackendError(format!("Failed to get Redis connection: {}", e))
+                })
+            },
+        )
+        .await
     }
 
     /// Get current pool statistics.
@@ -151,10 +177,7 @@ impl RedisBackend {
 
 impl CacheBackend for RedisBackend {
     async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
-        let mut conn =
-            self.pool.get().await.map_err(|e| {
-                Error::BackendError(format!("Failed to get Redis connection: {}", e))
-            })?;
+        let mut conn = self.connection().await?;
 
         let value: Option<Vec<u8>> = conn
             .get(key)
@@ -171,10 +194,7 @@ impl CacheBackend for RedisBackend {
     }
 
     async fn set(&self, key: &str, value: Vec<u8>, ttl: Option<Duration>) -> Result<()> {
-        let mut conn =
-            self.pool.get().await.map_err(|e| {
-                Error::BackendError(format!("Failed to get Redis connection: {}", e))
-            })?;
+        let mut conn = self.connection().await?;
 
         match ttl {
             Some(duration) => {
@@ -198,10 +218,7 @@ impl CacheBackend for RedisBackend {
     }
 
     async fn delete(&self, key: &str) -> Result<()> {
-        let mut conn =
-            self.pool.get().await.map_err(|e| {
-                Error::BackendError(format!("Failed to get Redis connection: {}", e))
-            })?;
+        let mut conn = self.connection().await?;
 
         conn.del::<_, ()>(key)
             .await
@@ -212,10 +229,7 @@ impl CacheBackend for RedisBackend {
     }
 
     async fn exists(&self, key: &str) -> Result<bool> {
-        let mut conn =
-            self.pool.get().await.map_err(|e| {
-                Error::BackendError(format!("Failed to get Redis connection: {}", e))
-            })?;
+        let mut conn = self.connection().await?;
 
         let exists: bool = conn.exists(key).await.map_err(|e| {
             Error::BackendError(format!("Redis EXISTS failed for key {}: {}", key, e))
@@ -225,10 +239,7 @@ impl CacheBackend for RedisBackend {
     }
 
     async fn mget(&self, keys: &[&str]) -> Result<Vec<Option<Vec<u8>>>> {
-        let mut conn =
-            self.pool.get().await.map_err(|e| {
-                Error::BackendError(format!("Failed to get Redis connection: {}", e))
-            })?;
+        let mut conn = self.connection().await?;
 
         let values: Vec<Option<Vec<u8>>> = conn
             .get(keys)
@@ -239,12 +250,61 @@ impl CacheBackend for RedisBackend {
         Ok(values)
     }
 
-    async fn mdelete(&self, keys: &[&str]) -> Result<()> {
-        let mut conn =
-            self.pool.get().await.map_err(|e| {
-                Error::BackendError(format!("Failed to get Redis connection: {}", e))
+    async fn mget_with_ttl(&self, keys: &[&str]) -> Result<Vec<Option<ValueWithTtl>>> {
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = self.connection().await?;
+
+        let mut pipe = deadpool_redis::redis::pipe();
+        for key in keys {
+            pipe.get(*key).pttl(*key);
+        }
+
+        let replies: Vec<(Option<Vec<u8>>, i64)> =
+            pipe.query_async(&mut *conn).await.map_err(|e| {
+                Error::BackendError(format!("Redis GET+PTTL (pipelined) failed: {}", e))
             })?;
 
+        debug!("✓ Redis MGET {} keys (with TTL)", keys.len());
+        // PTTL is -1 for keys without an expiry and -2 for missing keys
+        Ok(replies
+            .into_iter()
+            .map(|(value, pttl)| {
+                let remaining = u64::try_from(pttl).ok().map(Duration::from_millis);
+                value.map(|value| (value, remaining))
+            })
+            .collect())
+    }
+
+    async fn mset(&self, items: &[(&str, Vec<u8>)], ttl: Option<Duration>) -> Result<()> {
+        if items.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.connection().await?;
+
+        let mut pipe = deadpool_redis::redis::pipe();
+        for (key, value) in items {
+            match ttl {
+                Some(duration) => pipe.set_ex(*key, value.as_slice(), duration.as_secs()),
+                None => pipe.set(*key, value.as_slice()),
+            }
+            .ignore();
+        }
+
+        pipe.query_async::<()>(&mut *conn)
+            .await
+            .map_err(|e| Error::BackendError(format!("Redis SET (pipelined) failed: {}", e)))?;
+
+        debug!("✓ Redis MSET {} keys", items.len());
+        Ok(())
+    }
+
+    async fn mdelete(&self, keys: &[&str]) -> Result<()> {
+        let mut conn = self.connection().await?;
+
         conn.del::<_, ()>(keys)
             .await
             .map_err(|e| Error::BackendError(format!("Redis DEL (bulk) failed: {}", e)))?;
@@ -253,12 +313,130 @@ impl CacheBackend for RedisBackend {
         Ok(())
     }
 
-    async fn health_check(&self) -> Result<bool> {
-        let mut conn =
-            self.pool.get().await.map_err(|e| {
-                Error::BackendError(format!("Failed to get Redis connection: {}", e))
+    async fn incr(&self, key: &str) -> Result<u64> {
+        let mut conn = self.connection().await?;
+
+        let value: u64 = conn.incr(key, 1_u64).await.map_err(|e| {
+            Error::BackendError(format!("Redis INCR failed for key {}: {}", key, e))
+        })?;
+
+        debug!("✓ Redis INCR {} -> {}", key, value);
+        Ok(value)
+    }
+
+    async fn sadd(&self, key: &str, members: &[&str], ttl: Option<Duration>) -> Result<()> {
+        if members.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.connection().await?;
+
+        sadd_cmd(key, members, ttl)
+            .query_async::<()>(&mut *conn)
+            .await
+            .map_err(|e| {
+                Error::BackendError(format!("Redis SADD failed for key {}: {}", key, e))
             })?;
 
+        debug!("✓ Redis SADD {} ({} members)", key, members.len());
+        Ok(())
+    }
+
+    async fn smembers(&self, key: &str) -> Result<Vec<String>> {
+        let mut conn = self.connection().await?;
+
+        let members: Vec<String> = conn.smembers(key).await.map_err(|e| {
+            Error::BackendError(format!("Redis SMEMBERS failed for key {}: {}", key, e))
+        })?;
+
+        Ok(members)
+    }
+
+    async fn srem(&self, key: &str, members: &[&str]) -> Result<()> {
+        if members.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.connection().await?;
+
+        conn.srem::<_, _, ()>(key, members).await.map_err(|e| {
+            Error::BackendError(format!("Redis SREM failed for key {}: {}", key, e))
+        })?;
+
+        debug!("✓ Redis SREM {} ({} members)", key, members.len());
+        Ok(())
+    }
+
+    async fn msadd(&self, items: &[(&str, Vec<&str>)], ttl: Option<Duration>) -> Result<()> {
+        let items: Vec<_> = items
+            .iter()
+            .filter(|(_, members)| !members.is_empty())
+            .collect();
+        if items.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.connection().await?;
+
+        let mut pipe = deadpool_redis::redis::pipe();
+        for (key, members) in &items {
+            pipe.add_command(sadd_cmd(key, members, ttl)).ignore();
+        }
+
+        pipe.query_async::<()>(&mut *conn)
+            .await
+            .map_err(|e| Error::BackendError(format!("Redis SADD (pipelined) failed: {}", e)))?;
+
+        debug!("✓ Redis MSADD {} sets", items.len());
+        Ok(())
+    }
+
+    async fn msmembers(&self, keys: &[&str]) -> Result<Vec<Vec<String>>> {
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = self.connection().await?;
+
+        let mut pipe = deadpool_redis::redis::pipe();
+        for key in keys {
+            pipe.smembers(*key);
+        }
+
+        let members: Vec<Vec<String>> = pipe.query_async(&mut *conn).await.map_err(|e| {
+            Error::BackendError(format!("Redis SMEMBERS (pipelined) failed: {}", e))
+        })?;
+
+        Ok(members)
+    }
+
+    async fn msrem(&self, items: &[(&str, Vec<&str>)]) -> Result<()> {
+        let items: Vec<_> = items
+            .iter()
+            .filter(|(_, members)| !members.is_empty())
+            .collect();
+        if items.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.connection().await?;
+
+        let mut pipe = deadpool_redis::redis::pipe();
+        for (key, members) in &items {
+            pipe.srem(*key, members.as_slice()).ignore();
+        }
+
+        pipe.query_async::<()>(&mut *conn)
+            .await
+            .map_err(|e| Error::BackendError(format!("Redis SREM (pipelined) failed: {}", e)))?;
+
+        debug!("✓ Redis MSREM {} sets", items.len());
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<bool> {
+        let mut conn = self.connection().await?;
+
         // Use deadpool_redis::redis::cmd for PING command
         let pong: String = deadpool_redis::redis::cmd("PING")
             .query_async(&mut *conn)
@@ -269,10 +447,7 @@ impl CacheBackend for RedisBackend {
     }
 
     async fn clear_all(&self) -> Result<()> {
-        let mut conn =
-            self.pool.get().await.map_err(|e| {
-                Error::BackendError(format!("Failed to get Redis connection: {}", e))
-            })?;
+        let mut conn = self.connection().await?;
 
         deadpool_redis::redis::cmd("FLUSHDB")
             .query_async::<()>(&mut *conn)
@@ -284,6 +459,37 @@ impl CacheBackend for RedisBackend {
     }
 }
 
+/// Adds `ARGV[2..]` to the set at `KEYS[1]` and extends its expiry to at
+/// least `ARGV[1]` milliseconds, or removes it when `ARGV[1]` is negative.
+/// An existing set without an expiry keeps none.
+const SADD_SCRIPT: &str = r"
+local existed = redis.call('EXISTS', KEYS[1])
+redis.call('SADD', KEYS[1], unpack(ARGV, 2))
+local ttl = tonumber(ARGV[1])
+if ttl < 0 then
+    redis.call('PERSIST', KEYS[1])
+elseif existed == 0 then
+    redis.call('PEXPIRE', KEYS[1], ttl)
+else
+    local current = redis.call('PTTL', KEYS[1])
+    if current >= 0 and current < ttl then
+        redis.call('PEXPIRE', KEYS[1], ttl)
+    end
+end
+";
+
+/// Build an `EVAL` of `SADD_SCRIPT`.
+fn sadd_cmd(key: &str, members: &[&str], ttl: Option<Duration>) -> deadpool_redis::redis::Cmd {
+    let ttl_ms = ttl.map_or(-1, |ttl| ttl.as_millis().max(1) as i64);
+    let mut cmd = deadpool_redis::redis::cmd("EVAL");
+    cmd.arg(SADD_SCRIPT)
+        .arg(1)
+        .arg(key)
+        .arg(ttl_ms)
+        .arg(members);
+    cmd
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -463,6 +669,33 @@ mod tests {
         assert_eq!(results[2], None);
     }
 
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_backend_mset() {
+        let backend = RedisBackend::from_connection_string("redis://localhost:6379/0")
+            .await
+            .expect("Failed to create backend");
+
+        backend
+            .mset(
+                &[
+                    ("mset_key1", b"value1".to_vec()),
+                    ("mset_key2", b"value2".to_vec()),
+                ],
+                Some(Duration::from_secs(60)),
+            )
+            .await
+            .expect("Failed to mset");
+
+        let results = backend
+            .mget(&["mset_key1", "mset_key2"])
+            .await
+            .expect("Failed to mget");
+
+        assert_eq!(results[0], Some(b"value1".to_vec()));
+        assert_eq!(results[1], Some(b"value2".to_vec()));
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_redis_backend_mdelete() {
@@ -490,6 +723,119 @@ mod tests {
         assert_eq!(result2, None);
     }
 
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_backend_incr() {
+        let backend = RedisBackend::from_connection_string("redis://localhost:6379/0")
+            .await
+            .expect("Failed to create backend");
+
+        backend.delete("incr_key").await.expect("Failed to delete");
+        assert_eq!(backend.incr("incr_key").await.expect("Failed to incr"), 1);
+        assert_eq!(backend.incr("incr_key").await.expect("Failed to incr"), 2);
+        assert_eq!(
+            backend.get("incr_key").await.expect("Failed to get"),
+            Some(b"2".to_vec())
+        );
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_backend_sets() {
+        let backend = RedisBackend::from_connection_string("redis://localhost:6379/0")
+            .await
+            .expect("Failed to create backend");
+
+        backend.delete("set_key").await.expect("Failed to delete");
+        backend
+            .sadd("set_key", &["a", "b", "c"], None)
+            .await
+            .expect("Failed to sadd");
+        backend
+            .srem("set_key", &["b"])
+            .await
+            .expect("Failed to srem");
+
+        let mut members = backend
+            .smembers("set_key")
+            .await
+            .expect("Failed to smembers");
+        members.sort();
+        assert_eq!(members, vec!["a", "c"]);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_backend_mget_with_ttl() {
+        let backend = RedisBackend::from_connection_string("redis://localhost:6379/0")
+            .await
+            .expect("Failed to create backend");
+
+        backend
+            .set("ttl_key1", b"1".to_vec(), Some(Duration::from_secs(60)))
+            .await
+            .expect("Failed to set");
+        backend
+            .set("ttl_key2", b"2".to_vec(), None)
+            .await
+            .expect("Failed to set");
+        backend.delete("ttl_key3").await.expect("Failed to delete");
+
+        let values = backend
+            .mget_with_ttl(&["ttl_key1", "ttl_key2", "ttl_key3"])
+            .await
+            .expect("Failed to mget");
+        let (value, remaining) = values[0].clone().expect("Missing ttl_key1");
+        assert_eq!(value, b"1".to_vec());
+        assert!(remaining.is_some_and(|ttl| ttl <= Duration::from_secs(60)));
+        assert_eq!(values[1], Some((b"2".to_vec(), None)));
+        assert_eq!(values[2], None);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_backend_set_expiry_and_batches() {
+        let backend = RedisBackend::from_connection_string("redis://localhost:6379/0")
+            .await
+            .expect("Failed to create backend");
+
+        backend
+            .mdelete(&["set_ttl1", "set_ttl2"])
+            .await
+            .expect("Failed to delete");
+        backend
+            .msadd(
+                &[("set_ttl1", vec!["a", "b"]), ("set_ttl2", vec!["c"])],
+                Some(Duration::from_secs(1)),
+            )
+            .await
+            .expect("Failed to msadd");
+        // A longer TTL extends the expiry
+        backend
+            .sadd("set_ttl2", &["d"], Some(Duration::from_secs(60)))
+            .await
+            .expect("Failed to sadd");
+        backend
+            .msrem(&[("set_ttl1", vec!["a"])])
+            .await
+            .expect("Failed to msrem");
+
+        let members = backend
+            .msmembers(&["set_ttl1", "set_ttl2"])
+            .await
+            .expect("Failed to msmembers");
+        assert_eq!(members[0], vec!["b"]);
+        assert_eq!(members[1].len(), 2);
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        let members = backend
+            .msmembers(&["set_ttl1", "set_ttl2"])
+            .await
+            .expect("Failed to msmembers");
+        assert!(members[0].is_empty());
+        assert_eq!(members[1].len(), 2);
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_redis_backend_ttl() {
@@ -0,0 +1,817 @@
+//! Two-tier cache backend.
+//!
+//! [`TieredBackend`] puts a small, fast cache (L1, typically an in-process
+//! [`InMemoryBackend`](super::InMemoryBackend)) in front of a shared one (L2,
+//! typically Redis). Reads try L1 first and only go to L2 on an L1 miss; L2
+//! hits are promoted into L1. Writes and deletes go to both tiers.
+//!
+//! L1 entries live at most `l1_ttl`, which bounds how long one instance can
+//! serve a value another instance has since overwritten or deleted in L2.
+//! Promoted copies also expire no later than the L2 entry, as far as L2 can
+//! report its remaining TTL (see `CacheBackend::mget_with_ttl`).
+//!
+//! Sets and counters (`sadd`, `smembers`, `srem`, `incr`) are shared
+//! bookkeeping and only use L2. `incr` also drops the counter from L1, so the
+//! next read sees the new value on this instance; other instances see it
+//! within `l1_ttl`.
+//!
+//! With an [`InvalidationBus`] (see
+//! [`with_invalidation_bus`](TieredBackend::with_invalidation_bus)), writes,
+//! deletes, `clear_all` and `incr` are also published to the other instances,
+//! which evict the keys from their L1 instead of waiting out `l1_ttl`. The L1
+//! tier must then implement `CacheBackend::delete_prefix` and `clear_all`, as
+//! [`InMemoryBackend`](super::InMemoryBackend) does.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use cache_kit::backend::{InMemoryBackend, RedisBackend, TieredBackend, TieredConfig};
+//!
+//! let redis = RedisBackend::new(config).await?;
+//! let backend = TieredBackend::new(
+//!     InMemoryBackend::new(),
+//!     redis,
+//!     TieredConfig::default().with_l1_ttl(Duration::from_secs(5)),
+//! );
+//! let expander = CacheExpander::new(backend);
+//! ```
+
+use super::CacheBackend;
+use crate::error::Result;
+use crate::invalidation::{Invalidation, InvalidationBus, Subscription};
+use crate::observability::{CacheMetrics, NoOpMetrics};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Delay before resubscribing after an invalidation subscription is lost.
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+
+/// Tier of a [`TieredBackend`] that served a read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheTier {
+    /// The in-process tier.
+    L1,
+
+    /// The shared tier.
+    L2,
+}
+
+impl fmt::Display for CacheTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CacheTier::L1 => write!(f, "L1"),
+            CacheTier::L2 => write!(f, "L2"),
+        }
+    }
+}
+
+/// Configuration for a [`TieredBackend`].
+#[derive(Clone, Debug)]
+pub struct TieredConfig {
+    /// Longest time an entry stays in L1. Writes with a shorter TTL keep it.
+    /// Default: 10 seconds.
+    pub l1_ttl: Duration,
+}
+
+impl Default for TieredConfig {
+    fn default() -> Self {
+        TieredConfig {
+            l1_ttl: Duration::from_secs(10),
+        }
+    }
+}
+
+impl TieredConfig {
+    /// Set the longest time an entry stays in L1.
+    pub fn with_l1_ttl(mut self, ttl: Duration) -> Self {
+        self.l1_ttl = ttl;
+        self
+    }
+}
+
+/// Cache backend reading through an L1 tier to an L2 tier.
+///
+/// L1 errors on reads are treated as misses, so a failing L1 only costs
+/// L2 round trips. Errors from either tier on writes and deletes are
+/// returned; both tiers are always attempted for deletes.
+///
+/// Clones share both tiers and the invalidation bus.
+#[derive(Clone)]
+pub struct TieredBackend<L1: CacheBackend, L2: CacheBackend> {
+    l1: L1,
+    l2: L2,
+    config: TieredConfig,
+    metrics: Arc<dyn CacheMetrics>,
+    bus: Option<Arc<BusLink>>,
+}
+
+/// Connection of a [`TieredBackend`] to its invalidation bus.
+struct BusLink {
+    outbox: mpsc::UnboundedSender<Invalidation>,
+    listener: JoinHandle<()>,
+}
+
+impl Drop for BusLink {
+    fn drop(&mut self) {
+        // The publish task stops on its own once `outbox` is dropped
+        self.listener.abort();
+    }
+}
+
+impl<L1: CacheBackend, L2: CacheBackend> TieredBackend<L1, L2> {
+    /// Put `l1` in front of `l2`.
+    pub fn new(l1: L1, l2: L2, config: TieredConfig) -> Self {
+        TieredBackend {
+            l1,
+            l2,
+            config,
+            metrics: Arc::new(NoOpMetrics),
+            bus: None,
+        }
+    }
+
+    /// Report which tier served each hit through `CacheMetrics::record_tier_hit`.
+    pub fn with_metrics(mut self, metrics: Box<dyn CacheMetrics>) -> Self {
+        self.metrics = metrics.into();
+        self
+    }
+
+    /// Get L1 backend reference.
+    pub fn l1(&self) -> &L1 {
+        &self.l1
+    }
+
+    /// Get L2 backend reference.
+    pub fn l2(&self) -> &L2 {
+        &self.l2
+    }
+
+    /// TTL for an L1 copy of an entry written with `ttl`.
+    fn l1_ttl(&self, ttl: Option<Duration>) -> Option<Duration> {
+        Some(ttl.map_or(self.config.l1_ttl, |ttl| ttl.min(self.config.l1_ttl)))
+    }
+
+    /// Drop `keys` from L1 after a failed L2 write, logging any failure.
+    async fn evict_l1(&self, keys: &[&str]) {
+        if let Err(e) = self.l1.mdelete(keys).await {
+            warn!(
+                "Failed to drop {} keys from L1 after L2 write error: {}",
+                keys.len(),
+                e
+            );
+        }
+    }
+
+    /// Queue `invalidation` for the other instances, if there is a bus.
+    fn publish(&self, invalidation: Invalidation) {
+        if let Some(bus) = &self.bus {
+            // Fails only if the publish task is gone, i.e. the runtime is shutting down
+            let _ = bus.outbox.send(invalidation);
+        }
+    }
+}
+
+impl<L1: CacheBackend + 'static, L2: CacheBackend> TieredBackend<L1, L2> {
+    /// Keep the L1 tiers of all instances sharing `bus` in step.
+    ///
+    /// Writes, deletes, `clear_all` and `incr` are published in the background
+    /// after they reach L2, and invalidations received from the bus are
+    /// evicted from this instance's L1. Since the bus echoes this instance's
+    /// own invalidations, a written key is also dropped from the local L1 and
+    /// the next local read goes to L2. If the subscription is lost, L1 is
+    /// cleared once it is re-established, since messages may have been missed.
+    ///
+    /// Received prefix invalidations are applied with `L1::delete_prefix` and
+    /// full ones with `L1::clear_all`, so L1 must implement both (as
+    /// [`InMemoryBackend`](super::InMemoryBackend) does); failures are logged.
+    ///
+    /// Must be called inside a Tokio runtime. The background tasks stop once
+    /// every clone of the backend has been dropped.
+    ///
+    /// # Errors
+    /// Returns `Err` if subscribing to `bus` fails.
+    pub async fn with_invalidation_bus<Bus: InvalidationBus>(mut self, bus: Bus) -> Result<Self> {
+        let subscription = bus.subscribe().await?;
+        let bus = Arc::new(bus);
+        let (outbox, queued) = mpsc::unbounded_channel();
+
+        tokio::spawn(publish_invalidations(Arc::clone(&bus), queued));
+        let listener = tokio::spawn(apply_invalidations(bus, self.l1.clone(), subscription));
+        self.bus = Some(Arc::new(BusLink { outbox, listener }));
+        Ok(self)
+    }
+
+    /// Drop every L1 entry whose key starts with `prefix`, on this instance
+    /// and on every instance sharing the invalidation bus. L2 is not affected.
+    ///
+    /// Returns the number of entries dropped on this instance.
+    ///
+    /// # Errors
+    /// Returns `Err` if L1 does not implement `delete_prefix` or it fails. The
+    /// invalidation is published either way.
+    pub async fn invalidate_l1_prefix(&self, prefix: &str) -> Result<usize> {
+        let deleted = self.l1.delete_prefix(prefix).await;
+        self.publish(Invalidation::Prefix(prefix.to_string()));
+        deleted
+    }
+}
+
+/// Publish queued invalidations in order until the backend is dropped.
+async fn publish_invalidations<Bus: InvalidationBus>(
+    bus: Arc<Bus>,
+    mut queued: mpsc::UnboundedReceiver<Invalidation>,
+) {
+    while let Some(invalidation) = queued.recv().await {
+        if let Err(e) = bus.publish(&invalidation).await {
+            warn!("Failed to publish invalidation {:?}: {}", invalidation, e);
+        }
+    }
+    debug!("Tiered backend dropped, stopping invalidation publisher");
+}
+
+/// Evict received invalidations from `l1`, resubscribing when the
+/// subscription is lost.
+async fn apply_invalidations<Bus: InvalidationBus, L1: CacheBackend>(
+    bus: Arc<Bus>,
+    l1: L1,
+    mut subscription: Subscription,
+) {
+    loop {
+        while let Some(invalidation) = subscription.recv().await {
+            let result = match &invalidation {
+                Invalidation::Keys(keys) => {
+                    let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+                    l1.mdelete(&keys).await
+                }
+                Invalidation::Prefix(prefix) => l1.delete_prefix(prefix).await.map(|_| ()),
+                Invalidation::All => l1.clear_all().await,
+            };
+            if let Err(e) = result {
+                warn!(
+                    "Failed to apply invalidation {:?} to L1: {}",
+                    invalidation, e
+                );
+            }
+        }
+
+        warn!("Invalidation subscription lost, resubscribing");
+        subscription = loop {
+            tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+            match bus.subscribe().await {
+                Ok(subscription) => break subscription,
+                Err(e) => warn!("Failed to resubscribe to invalidations: {}", e),
+            }
+        };
+        if let Err(e) = l1.clear_all().await {
+            warn!("Failed to clear L1 after resubscribing: {}", e);
+        }
+    }
+}
+
+impl<L1: CacheBackend, L2: CacheBackend> CacheBackend for TieredBackend<L1, L2> {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match self.l1.get(key).await {
+            Ok(Some(value)) => {
+                self.metrics.record_tier_hit(key, CacheTier::L1);
+                return Ok(Some(value));
+            }
+            Ok(None) => {}
+            Err(e) => debug!("L1 GET {} failed, reading L2: {}", key, e),
+        }
+
+        let Some((value, remaining)) = self.l2.mget_with_ttl(&[key]).await?.pop().flatten() else {
+            return Ok(None);
+        };
+        self.metrics.record_tier_hit(key, CacheTier::L2);
+        if let Err(e) = self
+            .l1
+            .set(key, value.clone(), self.l1_ttl(remaining))
+            .await
+        {
+            debug!("Failed to promote {} to L1: {}", key, e);
+        }
+        Ok(Some(value))
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Option<Duration>) -> Result<()> {
+        if let Err(e) = self.l2.set(key, value.clone(), ttl).await {
+            // Don't keep serving the value the failed write meant to replace
+            self.evict_l1(&[key]).await;
+            return Err(e);
+        }
+        self.publish(Invalidation::Keys(vec![key.to_string()]));
+        self.l1.set(key, value, self.l1_ttl(ttl)).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let l1 = self.l1.delete(key).await;
+        let l2 = self.l2.delete(key).await;
+        self.publish(Invalidation::Keys(vec![key.to_string()]));
+        l1.and(l2)
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool> {
+        if let Ok(true) = self.l1.exists(key).await {
+            return Ok(true);
+        }
+        self.l2.exists(key).await
+    }
+
+    async fn mget(&self, keys: &[&str]) -> Result<Vec<Option<Vec<u8>>>> {
+        let mut values = match self.l1.mget(keys).await {
+            Ok(values) => values,
+            Err(e) => {
+                debug!("L1 MGET of {} keys failed, reading L2: {}", keys.len(), e);
+                vec![None; keys.len()]
+            }
+        };
+
+        let missed: Vec<usize> = (0..keys.len()).filter(|&i| values[i].is_none()).collect();
+        for (i, key) in keys.iter().enumerate() {
+            if values[i].is_some() {
+                self.metrics.record_tier_hit(key, CacheTier::L1);
+            }
+        }
+        if missed.is_empty() {
+            return Ok(values);
+        }
+
+        let missed_keys: Vec<&str> = missed.iter().map(|&i| keys[i]).collect();
+        // Promotions grouped by L1 TTL; most share `l1_ttl`
+        let mut promoted: HashMap<Option<Duration>, Vec<(&str, Vec<u8>)>> = HashMap::new();
+        for (i, entry) in missed
+            .into_iter()
+            .zip(self.l2.mget_with_ttl(&missed_keys).await?)
+        {
+            if let Some((value, remaining)) = entry {
+                self.metrics.record_tier_hit(keys[i], CacheTier::L2);
+                promoted
+                    .entry(self.l1_ttl(remaining))
+                    .or_default()
+                    .push((keys[i], value.clone()));
+                values[i] = Some(value);
+            }
+        }
+
+        for (ttl, items) in promoted {
+            if let Err(e) = self.l1.mset(&items, ttl).await {
+                debug!("Failed to promote {} keys to L1: {}", items.len(), e);
+            }
+        }
+        Ok(values)
+    }
+
+    async fn mset(&self, items: &[(&str, Vec<u8>)], ttl: Option<Duration>) -> Result<()> {
+        if let Err(e) = self.l2.mset(items, ttl).await {
+            let keys: Vec<&str> = items.iter().map(|(key, _)| *key).collect();
+            self.evict_l1(&keys).await;
+            return Err(e);
+        }
+        if !items.is_empty() {
+            self.publish(Invalidation::Keys(
+                items.iter().map(|(key, _)| key.to_string()).collect(),
+            ));
+        }
+        self.l1.mset(items, self.l1_ttl(ttl)).await
+    }
+
+    async fn mdelete(&self, keys: &[&str]) -> Result<()> {
+        let l1 = self.l1.mdelete(keys).await;
+        let l2 = self.l2.mdelete(keys).await;
+        if !keys.is_empty() {
+            self.publish(Invalidation::Keys(
+                keys.iter().map(|key| key.to_string()).collect(),
+            ));
+        }
+        l1.and(l2)
+    }
+
+    async fn sadd(&self, key: &str, members: &[&str], ttl: Option<Duration>) -> Result<()> {
+        self.l2.sadd(key, members, ttl).await
+    }
+
+    async fn smembers(&self, key: &str) -> Result<Vec<String>> {
+        self.l2.smembers(key).await
+    }
+
+    async fn srem(&self, key: &str, members: &[&str]) -> Result<()> {
+        self.l2.srem(key, members).await
+    }
+
+    async fn msadd(&self, items: &[(&str, Vec<&str>)], ttl: Option<Duration>) -> Result<()> {
+        self.l2.msadd(items, ttl).await
+    }
+
+    async fn msmembers(&self, keys: &[&str]) -> Result<Vec<Vec<String>>> {
+        self.l2.msmembers(keys).await
+    }
+
+    async fn msrem(&self, items: &[(&str, Vec<&str>)]) -> Result<()> {
+        self.l2.msrem(items).await
+    }
+
+    async fn incr(&self, key: &str) -> Result<u64> {
+        let value = self.l2.incr(key).await?;
+        if let Err(e) = self.l1.delete(key).await {
+            debug!("Failed to drop counter {} from L1: {}", key, e);
+        }
+        self.publish(Invalidation::Keys(vec![key.to_string()]));
+        Ok(value)
+    }
+
+    async fn health_check(&self) -> Result<bool> {
+        Ok(self.l1.health_check().await? && self.l2.health_check().await?)
+    }
+
+    async fn clear_all(&self) -> Result<()> {
+        let l1 = self.l1.clear_all().await;
+        let l2 = self.l2.clear_all().await;
+        self.publish(Invalidation::All);
+        l1.and(l2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{InMemoryBackend, ValueWithTtl};
+    use crate::error::Error;
+    use crate::invalidation::InProcessBus;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    /// In-memory backend counting the keys it is asked for.
+    #[derive(Clone, Default)]
+    struct CountingBackend {
+        inner: InMemoryBackend,
+        reads: Arc<AtomicUsize>,
+        fail_writes: Arc<AtomicBool>,
+    }
+
+    impl CacheBackend for CountingBackend {
+        async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            self.inner.get(key).await
+        }
+
+        async fn set(&self, key: &str, value: Vec<u8>, ttl: Option<Duration>) -> Result<()> {
+            if self.fail_writes.load(Ordering::SeqCst) {
+                return Err(Error::BackendError("L2 down".to_string()));
+            }
+            self.inner.set(key, value, ttl).await
+        }
+
+        async fn delete(&self, key: &str) -> Result<()> {
+            self.inner.delete(key).await
+        }
+
+        async fn mget(&self, keys: &[&str]) -> Result<Vec<Option<Vec<u8>>>> {
+            self.reads.fetch_add(keys.len(), Ordering::SeqCst);
+            self.inner.mget(keys).await
+        }
+
+        async fn mget_with_ttl(&self, keys: &[&str]) -> Result<Vec<Option<ValueWithTtl>>> {
+            self.reads.fetch_add(keys.len(), Ordering::SeqCst);
+            self.inner.mget_with_ttl(keys).await
+        }
+
+        async fn incr(&self, key: &str) -> Result<u64> {
+            self.inner.incr(key).await
+        }
+
+        async fn clear_all(&self) -> Result<()> {
+            self.inner.clear_all().await
+        }
+    }
+
+    fn tiered(l2: CountingBackend) -> TieredBackend<InMemoryBackend, CountingBackend> {
+        TieredBackend::new(
+            InMemoryBackend::new(),
+            l2,
+            TieredConfig::default().with_l1_ttl(Duration::from_millis(50)),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_tiered_get_promotes_l2_hits() {
+        let l2 = CountingBackend::default();
+        let backend = tiered(l2.clone());
+        l2.inner
+            .set("key", b"value".to_vec(), None)
+            .await
+            .expect("Failed to set");
+
+        assert_eq!(
+            backend.get("key").await.expect("Failed to get"),
+            Some(b"value".to_vec())
+        );
+        assert_eq!(
+            backend.l1().get("key").await.expect("Failed to get"),
+            Some(b"value".to_vec())
+        );
+        assert_eq!(
+            backend.get("key").await.expect("Failed to get"),
+            Some(b"value".to_vec())
+        );
+        assert_eq!(l2.reads.load(Ordering::SeqCst), 1);
+
+        // The L1 copy expires after l1_ttl; L2 still has the entry
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert!(backend
+            .l1()
+            .get("key")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        assert_eq!(
+            backend.get("key").await.expect("Failed to get"),
+            Some(b"value".to_vec())
+        );
+        assert_eq!(l2.reads.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_tiered_mget_reads_l2_for_l1_misses_only() {
+        let l2 = CountingBackend::default();
+        let backend = tiered(l2.clone());
+        backend
+            .set("a", b"1".to_vec(), None)
+            .await
+            .expect("Failed to set");
+        l2.inner
+            .set("b", b"2".to_vec(), None)
+            .await
+            .expect("Failed to set");
+
+        let values = backend
+            .mget(&["a", "b", "c"])
+            .await
+            .expect("Failed to mget");
+        assert_eq!(values, vec![Some(b"1".to_vec()), Some(b"2".to_vec()), None]);
+        assert_eq!(l2.reads.load(Ordering::SeqCst), 2);
+
+        // "b" was promoted, so only "c" goes to L2 now
+        backend
+            .mget(&["a", "b", "c"])
+            .await
+            .expect("Failed to mget");
+        assert_eq!(l2.reads.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_tiered_writes_and_deletes_hit_both_tiers() {
+        let l2 = CountingBackend::default();
+        let backend = tiered(l2.clone());
+
+        backend
+            .set("key", b"value".to_vec(), Some(Duration::from_secs(60)))
+            .await
+            .expect("Failed to set");
+        backend
+            .mset(&[("other", b"value".to_vec())], None)
+            .await
+            .expect("Failed to mset");
+        for key in ["key", "other"] {
+            assert!(backend
+                .l1()
+                .get(key)
+                .await
+                .expect("Failed to get")
+                .is_some());
+            assert!(l2.inner.get(key).await.expect("Failed to get").is_some());
+        }
+
+        // L1 copies are capped at l1_ttl
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert!(backend
+            .l1()
+            .get("key")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        assert!(l2.inner.get("key").await.expect("Failed to get").is_some());
+
+        backend.get("key").await.expect("Failed to get");
+        backend.delete("key").await.expect("Failed to delete");
+        backend
+            .mdelete(&["other"])
+            .await
+            .expect("Failed to mdelete");
+        for key in ["key", "other"] {
+            assert!(backend
+                .l1()
+                .get(key)
+                .await
+                .expect("Failed to get")
+                .is_none());
+            assert!(l2.inner.get(key).await.expect("Failed to get").is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tiered_promotion_keeps_l2_expiry() {
+        let l2 = CountingBackend::default();
+        let backend = TieredBackend::new(
+            InMemoryBackend::new(),
+            l2.clone(),
+            TieredConfig::default().with_l1_ttl(Duration::from_secs(60)),
+        );
+        for key in ["a", "b"] {
+            l2.inner
+                .set(key, b"value".to_vec(), Some(Duration::from_millis(50)))
+                .await
+                .expect("Failed to set");
+        }
+
+        backend.get("a").await.expect("Failed to get");
+        backend.mget(&["b"]).await.expect("Failed to mget");
+        assert!(backend
+            .l1()
+            .get("a")
+            .await
+            .expect("Failed to get")
+            .is_some());
+
+        // The L1 copies expire with the L2 entries, not after l1_ttl
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert_eq!(
+            backend
+                .l1()
+                .mget(&["a", "b"])
+                .await
+                .expect("Failed to mget"),
+            vec![None, None]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tiered_failed_l2_write_evicts_l1() {
+        let l2 = CountingBackend::default();
+        let backend = tiered(l2.clone());
+        backend
+            .set("key", b"old".to_vec(), None)
+            .await
+            .expect("Failed to set");
+        backend
+            .set("other", b"old".to_vec(), None)
+            .await
+            .expect("Failed to set");
+
+        l2.fail_writes.store(true, Ordering::SeqCst);
+        assert!(backend.set("key", b"new".to_vec(), None).await.is_err());
+        assert!(backend
+            .mset(&[("other", b"new".to_vec())], None)
+            .await
+            .is_err());
+
+        // Reads fall through to L2, which still has the old values
+        for key in ["key", "other"] {
+            assert!(backend
+                .l1()
+                .get(key)
+                .await
+                .expect("Failed to get")
+                .is_none());
+            assert_eq!(
+                backend.get(key).await.expect("Failed to get"),
+                Some(b"old".to_vec())
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tiered_incr_drops_l1_copy() {
+        let backend = tiered(CountingBackend::default());
+
+        assert_eq!(backend.incr("counter").await.expect("Failed to incr"), 1);
+        assert_eq!(
+            backend.get("counter").await.expect("Failed to get"),
+            Some(b"1".to_vec())
+        );
+        assert_eq!(backend.incr("counter").await.expect("Failed to incr"), 2);
+        assert_eq!(
+            backend.get("counter").await.expect("Failed to get"),
+            Some(b"2".to_vec())
+        );
+    }
+
+    /// Wait for invalidations to be published and applied in the background.
+    async fn settle() {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    #[tokio::test]
+    async fn test_tiered_invalidation_bus_evicts_peer_l1() {
+        let l2 = CountingBackend::default();
+        let bus = InProcessBus::new();
+        let config = TieredConfig::default().with_l1_ttl(Duration::from_secs(60));
+        let local = TieredBackend::new(InMemoryBackend::new(), l2.clone(), config.clone())
+            .with_invalidation_bus(bus.clone())
+            .await
+            .expect("Failed to subscribe");
+        let peer = TieredBackend::new(InMemoryBackend::new(), l2.clone(), config)
+            .with_invalidation_bus(bus)
+            .await
+            .expect("Failed to subscribe");
+
+        for key in ["user:1", "user:2", "user:3", "product:1"] {
+            local
+                .set(key, b"value".to_vec(), None)
+                .await
+                .expect("Failed to set");
+        }
+        // Let the writes' own invalidations land before filling both L1s
+        settle().await;
+        for key in ["user:1", "user:2", "user:3", "product:1"] {
+            local.get(key).await.expect("Failed to get");
+            peer.get(key).await.expect("Failed to get");
+        }
+
+        local.delete("user:1").await.expect("Failed to delete");
+        local.mdelete(&["user:2"]).await.expect("Failed to mdelete");
+        settle().await;
+        assert!(peer
+            .l1()
+            .get("user:1")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        assert!(peer
+            .l1()
+            .get("user:2")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        assert!(peer
+            .l1()
+            .get("user:3")
+            .await
+            .expect("Failed to get")
+            .is_some());
+
+        assert_eq!(
+            local
+                .invalidate_l1_prefix("user:")
+                .await
+                .expect("Failed to invalidate prefix"),
+            1
+        );
+        settle().await;
+        assert!(peer
+            .l1()
+            .get("user:3")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        assert!(peer
+            .l1()
+            .get("product:1")
+            .await
+            .expect("Failed to get")
+            .is_some());
+        assert!(l2
+            .inner
+            .get("user:3")
+            .await
+            .expect("Failed to get")
+            .is_some());
+
+        local.clear_all().await.expect("Failed to clear");
+        settle().await;
+        assert!(peer.l1().is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn test_tiered_metrics_per_tier() {
+        #[derive(Clone, Default)]
+        struct TierMetrics {
+            hits: Arc<Mutex<Vec<CacheTier>>>,
+        }
+
+        impl CacheMetrics for TierMetrics {
+            fn record_tier_hit(&self, _key: &str, tier: CacheTier) {
+                self.hits.lock().expect("Failed to lock hits").push(tier);
+            }
+        }
+
+        let metrics = TierMetrics::default();
+        let l2 = CountingBackend::default();
+        let backend = tiered(l2.clone()).with_metrics(Box::new(metrics.clone()));
+        l2.inner
+            .set("key", b"value".to_vec(), None)
+            .await
+            .expect("Failed to set");
+
+        backend.get("key").await.expect("Failed to get");
+        backend.get("key").await.expect("Failed to get");
+        backend.get("missing").await.expect("Failed to get");
+        assert_eq!(
+            *metrics.hits.lock().expect("Failed to lock hits"),
+            vec![CacheTier::L2, CacheTier::L1]
+        );
+    }
+}
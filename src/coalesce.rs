@@ -0,0 +1,411 @@
+//! Single-flight request coalescing for cache misses.
+//!
+//! When a popular key expires, every concurrent caller would otherwise fall
+//! through to the repository at the same time. [`SingleFlight`] groups those
+//! callers by cache key so that exactly one of them (the *leader*) performs the
+//! load, while the others (the *waiters*) await and share its result.
+//!
+//! Results are shared as serialized cache bytes, so a single `SingleFlight`
+//! can coalesce loads for any number of entity types.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use cache_kit::{CacheExpander, CacheService, backend::InMemoryBackend};
+//!
+//! let expander = CacheExpander::new(InMemoryBackend::new()).with_request_coalescing();
+//! let cache = CacheService::from_expander(expander);
+//! ```
+
+use crate::error::Result;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::sync::OnceCell;
+
+/// Shared outcome of a single in-flight load.
+type Flight = Arc<OnceCell<Result<Option<Vec<u8>>>>>;
+
+/// Outcome of [`SingleFlight::run`].
+#[derive(Debug)]
+pub struct FlightResult {
+    /// Serialized entity bytes (or error) produced by the leader.
+    pub value: Result<Option<Vec<u8>>>,
+
+    /// `true` if this caller waited on another caller's load.
+    pub coalesced: bool,
+}
+
+/// Groups concurrent loads of the same key so only one runs at a time.
+///
+/// Thread-safe; share one instance per expander.
+#[derive(Default)]
+pub struct SingleFlight {
+    flights: Mutex<HashMap<String, Flight>>,
+}
+
+impl SingleFlight {
+    /// Create an empty single-flight group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of keys with a load currently in flight.
+    pub fn in_flight(&self) -> usize {
+        self.flights
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Run `load` for `key`, or join a load already in flight for it.
+    ///
+    /// The first caller for a key runs `load`; callers arriving while it is
+    /// running receive a clone of the same result (including errors). If the
+    /// leader is cancelled, one of the waiters takes over the load.
+    pub async fn run<F, Fut>(&self, key: &str, load: F) -> FlightResult
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Option<Vec<u8>>>>,
+    {
+        let flight = self
+            .flights
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(key.to_string())
+            .or_default()
+            .clone();
+        self.fly(key, flight, load).await
+    }
+
+    /// Run `load` for `key` as a new flight, even if a load is already in
+    /// flight for it.
+    ///
+    /// Callers arriving afterwards join the new flight, while callers of the
+    /// replaced flight still receive its result. Use this when a load in
+    /// flight may have read data that has since been invalidated.
+    pub async fn run_fresh<F, Fut>(&self, key: &str, load: F) -> FlightResult
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Option<Vec<u8>>>>,
+    {
+        let flight = Flight::default();
+        self.flights
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(key.to_string(), Arc::clone(&flight));
+        self.fly(key, flight, load).await
+    }
+
+    /// Lead or join `flight` for `key`, then retire it.
+    async fn fly<F, Fut>(&self, key: &str, flight: Flight, load: F) -> FlightResult
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Option<Vec<u8>>>>,
+    {
+        let mut led = false;
+        let value = flight
+            .get_or_init(|| {
+                led = true;
+                load()
+            })
+            .await
+            .clone();
+
+        // Only remove the entry if it still belongs to this flight; a newer
+        // flight may already have replaced it.
+        let mut flights = self.flights.lock().unwrap_or_else(PoisonError::into_inner);
+        if flights
+            .get(key)
+            .is_some_and(|current| Arc::ptr_eq(current, &flight))
+        {
+            flights.remove(key);
+        }
+
+        FlightResult {
+            value,
+            coalesced: !led,
+        }
+    }
+
+    /// Run one `load` for every key in `keys` not already in flight, and join
+    /// the loads in flight for the rest.
+    ///
+    /// `load` receives the indices of the keys this caller leads and must
+    /// return their values in the same order; it is not called if every key is
+    /// already in flight. Results are returned in the order of `keys`. A failed
+    /// `load` fails every key it leads. If the leader of a joined load is
+    /// cancelled, `load` is called again for that key alone.
+    pub async fn run_many<F, Fut>(&self, keys: &[&str], load: F) -> Vec<FlightResult>
+    where
+        F: Fn(Vec<usize>) -> Fut,
+        Fut: Future<Output = Result<Vec<Option<Vec<u8>>>>>,
+    {
+        let mut led = Vec::new();
+        let flights: Vec<Flight> = {
+            let mut flights = self.flights.lock().unwrap_or_else(PoisonError::into_inner);
+            keys.iter()
+                .enumerate()
+                .map(|(index, key)| {
+                    let flight = flights.entry(key.to_string()).or_insert_with(|| {
+                        led.push(index);
+                        Flight::default()
+                    });
+                    Arc::clone(flight)
+                })
+                .collect()
+        };
+
+        // The batch load runs once, on behalf of every flight it leads
+        let batch = OnceCell::new();
+        let load = &load;
+        let led = &led;
+
+        let results =
+            futures::future::join_all(flights.iter().enumerate().map(|(index, flight)| {
+                let batch = &batch;
+                async move {
+                    let mut leading = false;
+                    let value = flight
+                        .get_or_init(|| {
+                            leading = true;
+                            async move {
+                                let Some(position) = led.iter().position(|&led| led == index)
+                                else {
+                                    // Took over from a cancelled leader
+                                    let values = load(vec![index]).await?;
+                                    return Ok(values.into_iter().next().flatten());
+                                };
+                                let values = batch
+                                    .get_or_init(|| load(led.clone()))
+                                    .await
+                                    .as_ref()
+                                    .map_err(Clone::clone)?;
+                                Ok(values.get(position).cloned().flatten())
+                            }
+                        })
+                        .await
+                        .clone();
+                    FlightResult {
+                        value,
+                        coalesced: !leading,
+                    }
+                }
+            }))
+            .await;
+
+        let mut current = self.flights.lock().unwrap_or_else(PoisonError::into_inner);
+        for (key, flight) in keys.iter().zip(&flights) {
+            if current
+                .get(*key)
+                .is_some_and(|entry| Arc::ptr_eq(entry, flight))
+            {
+                current.remove(*key);
+            }
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_single_flight_runs_load_once() {
+        let flights = Arc::new(SingleFlight::new());
+        let loads = Arc::new(AtomicUsize::new(0));
+
+        let mut handles = vec![];
+        for _ in 0..10 {
+            let flights = flights.clone();
+            let loads = loads.clone();
+            handles.push(tokio::spawn(async move {
+                flights
+                    .run("test:1", || async {
+                        loads.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        Ok(Some(b"value".to_vec()))
+                    })
+                    .await
+            }));
+        }
+
+        let mut coalesced = 0;
+        for handle in handles {
+            let result = handle.await.expect("Task failed");
+            assert_eq!(result.value.expect("Load failed"), Some(b"value".to_vec()));
+            if result.coalesced {
+                coalesced += 1;
+            }
+        }
+
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        assert_eq!(coalesced, 9);
+        assert_eq!(flights.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_single_flight_shares_errors() {
+        let flights = Arc::new(SingleFlight::new());
+
+        let leader = {
+            let flights = flights.clone();
+            tokio::spawn(async move {
+                flights
+                    .run("test:1", || async {
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        Err(Error::RepositoryError("db down".to_string()))
+                    })
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let waiter = flights
+            .run("test:1", || async { Ok(Some(b"unused".to_vec())) })
+            .await;
+
+        assert!(waiter.coalesced);
+        assert!(matches!(waiter.value, Err(Error::RepositoryError(_))));
+        assert!(leader.await.expect("Task failed").value.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_single_flight_sequential_calls_reload() {
+        let flights = SingleFlight::new();
+        let loads = AtomicUsize::new(0);
+
+        for _ in 0..3 {
+            let result = flights
+                .run("test:1", || async {
+                    loads.fetch_add(1, Ordering::SeqCst);
+                    Ok(None)
+                })
+                .await;
+            assert!(!result.coalesced);
+        }
+
+        assert_eq!(loads.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_single_flight_run_fresh_replaces_flight() {
+        let flights = Arc::new(SingleFlight::new());
+
+        let stale = {
+            let flights = flights.clone();
+            tokio::spawn(async move {
+                flights
+                    .run("test:1", || async {
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                        Ok(Some(b"old".to_vec()))
+                    })
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let fresh = {
+            let flights = flights.clone();
+            tokio::spawn(async move {
+                flights
+                    .run_fresh("test:1", || async {
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        Ok(Some(b"new".to_vec()))
+                    })
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        // Later callers join the fresh flight, not the stale one
+        let waiter = flights
+            .run("test:1", || async { Ok(Some(b"unused".to_vec())) })
+            .await;
+        assert!(waiter.coalesced);
+        assert_eq!(waiter.value.expect("Load failed"), Some(b"new".to_vec()));
+
+        let fresh = fresh.await.expect("Task failed");
+        assert!(!fresh.coalesced);
+        assert_eq!(fresh.value.expect("Load failed"), Some(b"new".to_vec()));
+        let stale = stale.await.expect("Task failed");
+        assert_eq!(stale.value.expect("Load failed"), Some(b"old".to_vec()));
+        assert_eq!(flights.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_single_flight_run_many_joins_flights() {
+        let flights = Arc::new(SingleFlight::new());
+
+        let single = {
+            let flights = flights.clone();
+            tokio::spawn(async move {
+                flights
+                    .run("test:1", || async {
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        Ok(Some(b"one".to_vec()))
+                    })
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let requested = Mutex::new(Vec::new());
+        let results = flights
+            .run_many(&["test:1", "test:2", "test:3"], |led| {
+                requested
+                    .lock()
+                    .expect("Failed to lock requested")
+                    .push(led.clone());
+                async move {
+                    Ok(led
+                        .iter()
+                        .map(|&i| (i == 1).then(|| b"two".to_vec()))
+                        .collect())
+                }
+            })
+            .await;
+
+        // Only the keys not already in flight were loaded, in one call
+        assert_eq!(
+            *requested.lock().expect("Failed to lock requested"),
+            vec![vec![1, 2]]
+        );
+        assert!(results[0].coalesced);
+        assert_eq!(
+            results[0].value.as_ref().expect("Load failed"),
+            &Some(b"one".to_vec())
+        );
+        assert!(!results[1].coalesced);
+        assert_eq!(
+            results[1].value.as_ref().expect("Load failed"),
+            &Some(b"two".to_vec())
+        );
+        assert_eq!(results[2].value.as_ref().expect("Load failed"), &None);
+        assert!(single.await.expect("Task failed").value.is_ok());
+        assert_eq!(flights.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_single_flight_run_many_shares_errors() {
+        let flights = SingleFlight::new();
+        let loads = AtomicUsize::new(0);
+
+        let results = flights
+            .run_many(&["test:1", "test:2"], |_| async {
+                loads.fetch_add(1, Ordering::SeqCst);
+                Err(Error::RepositoryError("db down".to_string()))
+            })
+            .await;
+
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        assert!(results
+            .iter()
+            .all(|result| matches!(result.value, Err(Error::RepositoryError(_)))));
+    }
+}
@@ -1,9 +1,12 @@
 //! Core entity trait that all cached entities must implement.
 
 use crate::error::Result;
+use crate::index::SecondaryKey;
+use crate::key::EntryRef;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use std::hash::Hash;
+use std::time::Duration;
 
 /// Trait that all entities stored in cache must implement.
 ///
@@ -96,6 +99,65 @@ pub trait CacheEntity: Send + Sync + Serialize + for<'de> Deserialize<'de> + Clo
     fn validate(&self) -> Result<()> {
         Ok(())
     }
+
+    /// Optional: TTL of every cache entry of this type.
+    ///
+    /// Takes precedence over the expander's `TtlPolicy`, and is used as is
+    /// (policy jitter does not apply). `OperationConfig::ttl_override` and
+    /// `cache_instance_ttl` take precedence over it.
+    /// Example: `Some(Duration::from_secs(300))`
+    fn cache_ttl() -> Option<Duration> {
+        None
+    }
+
+    /// Optional: TTL of this entity's cache entry.
+    ///
+    /// Takes precedence over `cache_ttl` and the expander's `TtlPolicy`; only
+    /// `OperationConfig::ttl_override` takes precedence over it.
+    /// Example: a short TTL for drafts and a long one for published records
+    fn cache_instance_ttl(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Optional: Namespace of the entity with key `id`, e.g. its tenant.
+    ///
+    /// Only used when generation counters are enabled
+    /// (`CacheExpander::with_generations`): entries in a namespace are also
+    /// keyed by the namespace's generation, so
+    /// `CacheService::invalidate_namespace(ns)` drops all of them at once.
+    /// Derived from the key alone, since reads don't have the entity yet.
+    /// Example: `Some(format!("tenant:{}", id.tenant_id))`
+    fn cache_namespace(_id: &Self::Key) -> Option<String> {
+        None
+    }
+
+    /// Optional: Tags for group invalidation.
+    ///
+    /// Every cache entry written for this entity is recorded under each tag,
+    /// and `CacheService::invalidate_tag(tag)` evicts all entries carrying it.
+    /// Example: `vec![format!("customer:{}", self.customer_id)]` on an invoice
+    fn cache_tags(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Optional: Entries this entity's cache entry depends on.
+    ///
+    /// Only used when dependency cascading is enabled
+    /// (`CacheExpander::with_dependency_cascade`): invalidating or rewriting
+    /// any of these entries also evicts this one.
+    /// Example: `vec![EntryRef::of::<Customer>(&self.customer_id)]` on an invoice
+    fn cache_dependencies(&self) -> Vec<EntryRef> {
+        Vec::new()
+    }
+
+    /// Optional: Alternate unique keys, for lookups by a field other than the key.
+    ///
+    /// Every cache entry written for this entity also stores a pointer from
+    /// each alternate key to it, used by `CacheService::execute_by`.
+    /// Example: `vec![SecondaryKey::of::<User, ByEmail>(&self.email)]`
+    fn cache_secondary_keys(&self) -> Vec<SecondaryKey> {
+        Vec::new()
+    }
 }
 
 #[cfg(test)]
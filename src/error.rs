@@ -1,6 +1,9 @@
 //! Error types for the cache framework.
 
 use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
 
 /// Result type for cache operations.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -73,10 +76,13 @@ pub enum Error {
     /// Operation exceeded configured timeout threshold.
     ///
     /// This occurs when cache or repository operations take too long.
+    /// Deadlines set with `OperationConfig` or `connection_timeout` name the
+    /// phase that timed out and the limit, e.g.
+    /// `"repository call exceeded 250ms"`.
     /// Common causes:
     /// - Network latency
     /// - Slow database query
-    /// - Backend overload
+    /// - Backend overload or exhausted connection pool
     ///
     /// **Recovery:** Retry with exponential backoff.
     Timeout(String),
@@ -136,6 +142,51 @@ pub enum Error {
     Other(String),
 }
 
+/// Phase of a cache operation that a deadline applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TimeoutPhase {
+    /// Checking out (or opening) a backend connection.
+    #[cfg_attr(not(any(feature = "redis", feature = "memcached")), allow(dead_code))]
+    Connect,
+    /// A single cache backend call.
+    Backend,
+    /// A single repository call.
+    Repository,
+    /// The whole operation, including retries.
+    Operation,
+}
+
+impl fmt::Display for TimeoutPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeoutPhase::Connect => write!(f, "connection"),
+            TimeoutPhase::Backend => write!(f, "backend call"),
+            TimeoutPhase::Repository => write!(f, "repository call"),
+            TimeoutPhase::Operation => write!(f, "operation"),
+        }
+    }
+}
+
+/// Run `future` with an optional deadline, failing with `Error::Timeout` naming `phase`.
+///
+/// The future is boxed so that deadlines wrapped around backend calls, which
+/// are themselves wrapped by the expander, do not nest future types without
+/// bound.
+pub(crate) fn with_deadline<'a, V: Send + 'a>(
+    phase: TimeoutPhase,
+    limit: Option<Duration>,
+    future: impl Future<Output = Result<V>> + Send + 'a,
+) -> Pin<Box<dyn Future<Output = Result<V>> + Send + 'a>> {
+    match limit {
+        Some(limit) => Box::pin(async move {
+            tokio::time::timeout(limit, future)
+                .await
+                .unwrap_or_else(|_| Err(Error::Timeout(format!("{} exceeded {:?}", phase, limit))))
+        }),
+        None => Box::pin(future),
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -165,6 +216,19 @@ impl fmt::Display for Error {
 
 impl std::error::Error for Error {}
 
+impl Error {
+    /// Whether the failure is transient, so that retrying may succeed.
+    ///
+    /// Backend and repository errors and timeouts are retryable. Data,
+    /// validation and configuration errors are not.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Error::BackendError(_) | Error::RepositoryError(_) | Error::Timeout(_)
+        )
+    }
+}
+
 // ============================================================================
 // Conversions from other error types
 // ============================================================================
@@ -216,6 +280,43 @@ mod tests {
         assert_eq!(err.to_string(), "Validation error: Test");
     }
 
+    #[tokio::test]
+    async fn test_with_deadline() {
+        let ok = with_deadline(TimeoutPhase::Backend, Some(Duration::from_secs(1)), async {
+            Ok(1)
+        })
+        .await;
+        assert_eq!(ok.expect("Deadline exceeded"), 1);
+
+        let late = with_deadline(
+            TimeoutPhase::Backend,
+            Some(Duration::from_millis(10)),
+            async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(1)
+            },
+        )
+        .await;
+        let err = late.expect_err("Deadline not enforced");
+        assert!(matches!(err, Error::Timeout(_)));
+        assert_eq!(err.to_string(), "Timeout: backend call exceeded 10ms");
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(Error::BackendError("down".to_string()).is_retryable());
+        assert!(Error::RepositoryError("down".to_string()).is_retryable());
+        assert!(Error::Timeout("slow".to_string()).is_retryable());
+
+        assert!(!Error::ValidationError("bad".to_string()).is_retryable());
+        assert!(!Error::DeserializationError("bad".to_string()).is_retryable());
+        assert!(!Error::VersionMismatch {
+            expected: 2,
+            found: 1
+        }
+        .is_retryable());
+    }
+
     #[test]
     fn test_error_from_string() {
         let err: Error = "test error".into();
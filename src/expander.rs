@@ -1,16 +1,34 @@
 //! Cache expander - main entry point for cache operations.
 
-use crate::backend::CacheBackend;
+use crate::backend::{parse_counter, CacheBackend};
+use crate::coalesce::SingleFlight;
 use crate::entity::CacheEntity;
-use crate::error::{Error, Result};
-use crate::feed::CacheFeed;
-use crate::key::CacheKeyBuilder;
-use crate::observability::{CacheMetrics, NoOpMetrics, TtlPolicy};
-use crate::repository::DataRepository;
+use crate::error::{with_deadline, Error, Result, TimeoutPhase};
+use crate::feed::{CacheFeed, GenericFeeder};
+use crate::index::{SecondaryIndex, SecondaryKey};
+use crate::key::{CacheKeyBuilder, EntryRef};
+use crate::observability::{CacheMetrics, EarlyExpiration, NoOpMetrics, TtlPolicy};
+use crate::query::{CacheQuery, QueryEntry, QueryStorage};
+use crate::repository::{DataRepository, IndexedRepository, LoaderRepository, WritableRepository};
+use crate::retry::{RetryPolicy, RetryScope};
+use crate::serialization::{self, EntryMetadata};
 use crate::strategy::CacheStrategy;
-use std::str::FromStr;
+use crate::warm::{WarmOptions, WarmProgress};
+use futures::stream::{self, StreamExt};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::any::Any;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError, Weak};
 use std::time::{Duration, Instant};
 
+/// Spawns a background refresh for a stale entry, given its cache key and entity ID.
+type BackgroundRefresh<'a, K> = dyn Fn(String, K) + Send + Sync + 'a;
+
+/// Entries sharing a TTL in a batch write, with the indices of their entities.
+type TtlGroup<'a> = (Vec<(&'a str, Vec<u8>)>, Vec<usize>);
+
 /// Configuration for per-operation overrides.
 ///
 /// This allows you to override TTL and retry behavior for individual cache operations,
@@ -42,9 +60,10 @@ pub struct OperationConfig {
     ///
     /// # Precedence and Conflict Resolution
     ///
-    /// When both `ttl_override` and the expander's `ttl_policy` could apply:
+    /// When several TTLs could apply:
     /// - **If `Some(duration)`**: Use this override (takes precedence)
-    /// - **If `None`**: Fall back to the expander's `ttl_policy`
+    /// - **If `None`**: Fall back to the entity's `CacheEntity::cache_instance_ttl`,
+    ///   then the type's `CacheEntity::cache_ttl`, then the expander's `ttl_policy`
     ///
     /// This allows per-operation exceptions without changing global settings.
     ///
@@ -68,11 +87,37 @@ pub struct OperationConfig {
     /// ```
     pub ttl_override: Option<Duration>,
 
-    /// Number of retry attempts for this operation (0 = no retry).
+    /// Retry policy for this operation (default: no retries).
+    ///
+    /// Only errors for which `Error::is_retryable` holds are retried, with
+    /// jittered exponential backoff. `RetryPolicy::scope` selects whether the
+    /// whole operation, or only its backend or repository calls, are retried.
+    pub retry: RetryPolicy,
+
+    /// Override the expander's failure mode for this operation only.
+    ///
+    /// - **If `Some(mode)`**: Use this mode
+    /// - **If `None`**: Fall back to the expander's failure mode
+    pub failure_mode: Option<FailureMode>,
+
+    /// Deadline for each cache backend call in this operation.
+    ///
+    /// A backend call that exceeds it fails with `Error::Timeout` naming the
+    /// backend call, which is then handled like any other backend error under
+    /// the operation's `FailureMode`.
+    pub backend_timeout: Option<Duration>,
+
+    /// Deadline for each repository call in this operation.
     ///
-    /// If the operation fails, it will be retried up to this many times with
-    /// exponential backoff.
-    pub retry_count: u32,
+    /// Exceeding it fails the attempt with `Error::Timeout` naming the
+    /// repository call.
+    pub repository_timeout: Option<Duration>,
+
+    /// Deadline for the whole operation, including retries.
+    ///
+    /// Exceeding it fails the operation with `Error::Timeout` naming the
+    /// operation.
+    pub timeout: Option<Duration>,
 }
 
 impl OperationConfig {
@@ -91,6 +136,9 @@ impl OperationConfig {
 
     /// Set retry count for this operation.
     ///
+    /// Shorthand for a retry policy with `count + 1` attempts and otherwise
+    /// default settings.
+    ///
     /// # Example
     ///
     /// ```ignore
@@ -98,11 +146,104 @@ impl OperationConfig {
     ///     .with_retry(3);  // Retry up to 3 times on failure
     /// ```
     pub fn with_retry(mut self, count: u32) -> Self {
-        self.retry_count = count;
+        self.retry.max_attempts = count.saturating_add(1);
+        self
+    }
+
+    /// Set the retry policy for this operation.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use cache_kit::retry::{Jitter, RetryScope};
+    ///
+    /// // Retry only the repository call, with decorrelated jitter
+    /// let config = OperationConfig::default().with_retry_policy(
+    ///     RetryPolicy::new(4)
+    ///         .with_jitter(Jitter::Decorrelated)
+    ///         .with_scope(RetryScope::Repository),
+    /// );
+    /// ```
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Override the failure mode for this operation.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // This read must not silently skip the cache
+    /// let config = OperationConfig::default()
+    ///     .with_failure_mode(FailureMode::Closed);
+    /// ```
+    pub fn with_failure_mode(mut self, mode: FailureMode) -> Self {
+        self.failure_mode = Some(mode);
+        self
+    }
+
+    /// Set the deadline for each cache backend call.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let config = OperationConfig::default()
+    ///     .with_backend_timeout(Duration::from_millis(50))
+    ///     .with_repository_timeout(Duration::from_millis(500))
+    ///     .with_timeout(Duration::from_secs(1));
+    /// ```
+    pub fn with_backend_timeout(mut self, timeout: Duration) -> Self {
+        self.backend_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the deadline for each repository call.
+    pub fn with_repository_timeout(mut self, timeout: Duration) -> Self {
+        self.repository_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the deadline for the whole operation, including retries.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
         self
     }
 }
 
+/// Receiver of entity reads, registered with `CacheExpander::add_access_recorder`.
+///
+/// Lets components such as the refresh-ahead scheduler see reads made through
+/// the expander (and so through `CacheService`), not just their own.
+pub(crate) trait AccessRecorder: Send + Sync {
+    /// Record a read of the entity with key `id`, a `T::Key` of the
+    /// registered entity type.
+    fn record_access(&self, id: &dyn Any);
+}
+
+/// Recorders by entity cache prefix; dropped recorders are pruned on the next read.
+type AccessRecorders = HashMap<&'static str, Vec<Weak<dyn AccessRecorder>>>;
+
+/// How the expander reacts when the cache backend is unavailable.
+///
+/// | Mode | Backend read error | Backend write/delete error |
+/// |------|--------------------|----------------------------|
+/// | `Closed` | Operation fails | Invalidation and write-through APIs fail |
+/// | `Open` | Treated as a miss; repository is consulted | Ignored |
+///
+/// Cache population after a repository load never fails an operation in
+/// either mode. Errors tolerated in `Open` mode are logged and reported through
+/// `CacheMetrics::record_fail_open`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum FailureMode {
+    /// Propagate backend errors to the caller (default).
+    #[default]
+    Closed,
+
+    /// Degrade to the repository when the backend fails.
+    Open,
+}
+
 /// Core cache expander - handles cache lookup and fallback logic.
 ///
 /// This is the main entry point for cache operations.
@@ -120,6 +261,16 @@ pub struct CacheExpander<B: CacheBackend> {
     backend: B,
     metrics: Box<dyn CacheMetrics>,
     pub(crate) ttl_policy: TtlPolicy,
+    single_flight: Option<SingleFlight>,
+    early_expiration: Option<EarlyExpiration>,
+    negative_ttl_policy: Option<TtlPolicy>,
+    failure_mode: FailureMode,
+    self_healing: bool,
+    generations: Option<Arc<Generations>>,
+    cascade_depth: Option<usize>,
+    ttl_rng: Mutex<StdRng>,
+    refreshing: Arc<Mutex<HashSet<String>>>,
+    access_recorders: Mutex<AccessRecorders>,
 }
 
 impl<B: CacheBackend> CacheExpander<B> {
@@ -129,6 +280,16 @@ impl<B: CacheBackend> CacheExpander<B> {
             backend,
             metrics: Box::new(NoOpMetrics),
             ttl_policy: TtlPolicy::default(),
+            single_flight: None,
+            early_expiration: None,
+            negative_ttl_policy: None,
+            failure_mode: FailureMode::default(),
+            self_healing: false,
+            generations: None,
+            cascade_depth: None,
+            ttl_rng: Mutex::new(StdRng::from_os_rng()),
+            refreshing: Arc::new(Mutex::new(HashSet::new())),
+            access_recorders: Mutex::new(HashMap::new()),
         }
     }
 
@@ -144,6 +305,164 @@ impl<B: CacheBackend> CacheExpander<B> {
         self
     }
 
+    /// Seed the RNG used for TTL jitter (`TtlPolicy::Jittered`).
+    ///
+    /// By default the RNG is seeded from the OS. A fixed seed makes jittered
+    /// TTLs reproducible, e.g. in tests.
+    pub fn with_ttl_seed(mut self, seed: u64) -> Self {
+        self.ttl_rng = Mutex::new(StdRng::seed_from_u64(seed));
+        self
+    }
+
+    /// Enable single-flight request coalescing for cache misses.
+    ///
+    /// When enabled, concurrent `Refresh` and `Invalidate` operations that miss
+    /// on the same cache key share one `DataRepository::fetch_by_id` call and
+    /// one `backend.set`. Waiting callers receive the leader's result (or error),
+    /// and each one is reported through `CacheMetrics::record_coalesced`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let expander = CacheExpander::new(backend).with_request_coalescing();
+    /// ```
+    pub fn with_request_coalescing(mut self) -> Self {
+        self.single_flight = Some(SingleFlight::new());
+        self
+    }
+
+    /// Enable probabilistic early expiration (XFetch) for `Refresh` hits.
+    ///
+    /// Each cache hit may be treated as a miss shortly before the entry's TTL
+    /// elapses, weighted by how long the entry took to load from the repository.
+    /// This keeps entries written together (with the same TTL) from expiring and
+    /// stampeding the database at the same moment. Entries without a TTL are
+    /// never refreshed early.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use cache_kit::observability::EarlyExpiration;
+    ///
+    /// let expander = CacheExpander::new(backend)
+    ///     .with_ttl_policy(TtlPolicy::Fixed(Duration::from_secs(3600)))
+    ///     .with_early_expiration(EarlyExpiration::default());
+    /// ```
+    pub fn with_early_expiration(mut self, early_expiration: EarlyExpiration) -> Self {
+        self.early_expiration = Some(early_expiration);
+        self
+    }
+
+    /// Enable negative caching of entities missing from the repository.
+    ///
+    /// When `DataRepository::fetch_by_id` returns `Ok(None)`, a tombstone is
+    /// stored under the entity's cache key with a TTL from `ttl_policy`. Later
+    /// reads of that key return `None` without touching the repository, and are
+    /// reported through `CacheMetrics::record_negative_hit` (as well as
+    /// `record_miss`). Keep the TTL short: an entity created while its tombstone
+    /// is live stays invisible until the tombstone expires or is invalidated.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let expander = CacheExpander::new(backend)
+    ///     .with_negative_caching(TtlPolicy::Fixed(Duration::from_secs(30)));
+    /// ```
+    pub fn with_negative_caching(mut self, ttl_policy: TtlPolicy) -> Self {
+        self.negative_ttl_policy = Some(ttl_policy);
+        self
+    }
+
+    /// Set how backend failures are handled (default: `FailureMode::Closed`).
+    ///
+    /// In `FailureMode::Open`, a cache outage degrades reads to the repository
+    /// instead of failing them. Individual operations can override this with
+    /// `OperationConfig::with_failure_mode`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let expander = CacheExpander::new(redis_backend)
+    ///     .with_failure_mode(FailureMode::Open);
+    /// ```
+    pub fn with_failure_mode(mut self, mode: FailureMode) -> Self {
+        self.failure_mode = mode;
+        self
+    }
+
+    /// Heal unreadable cache entries instead of failing on them.
+    ///
+    /// When a cache hit cannot be decoded (`Error::InvalidCacheEntry`,
+    /// `Error::VersionMismatch`, `Error::DeserializationError`) or fails
+    /// `CacheEntity::validate()`, the entry is deleted and the read is treated
+    /// as a miss: `Refresh` and `StaleWhileRevalidate` reload it from the
+    /// repository and rewrite it in the current format, `Fresh` returns `None`.
+    /// Each healed entry is reported through `CacheMetrics::record_heal`.
+    ///
+    /// Without self-healing these errors are returned to the caller until the
+    /// entry expires or is invalidated.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let expander = CacheExpander::new(backend).with_self_healing();
+    /// ```
+    pub fn with_self_healing(mut self) -> Self {
+        self.self_healing = true;
+        self
+    }
+
+    /// Include generation counters stored in the backend in cache keys.
+    ///
+    /// Keys become `"{prefix}:g{generation}:{id}"`, where the generation is a
+    /// counter per entity type; entities with a `CacheEntity::cache_namespace`
+    /// (e.g. a tenant) are also keyed by that namespace's generation. Bumping a
+    /// generation with `invalidate_all::<T>()` or `invalidate_namespace()`
+    /// makes every entry written under the old one unreachable at once,
+    /// without scanning or deleting keys. The orphaned entries are left to
+    /// expire, so pair this with a TTL policy.
+    ///
+    /// Each operation reads the generations it needs with one extra `mget`.
+    /// If that read fails in `FailureMode::Open`, the last generations this
+    /// expander saw are used instead.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let expander = CacheExpander::new(memcached_backend)
+    ///     .with_ttl_policy(TtlPolicy::Fixed(Duration::from_secs(3600)))
+    ///     .with_generations();
+    ///
+    /// expander.invalidate_all::<Product>().await?;
+    /// ```
+    pub fn with_generations(mut self) -> Self {
+        self.generations = Some(Arc::new(Generations::default()));
+        self
+    }
+
+    /// Evict the entries that depend on an entry whenever it is invalidated or rewritten.
+    ///
+    /// Entities declare dependencies with `CacheEntity::cache_dependencies()`.
+    /// Each cached entry is recorded in a set per dependency stored in the
+    /// backend, so every instance sharing the backend sees the same edges.
+    /// `invalidate()`, `write_through()`, `write_around()`, `delete_through()`
+    /// and the `Invalidate` strategy then evict dependents transitively, up to
+    /// `max_depth` levels; cycles are visited once. Bumping a generation with
+    /// `invalidate_all()` does not cascade.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Invoice::cache_dependencies() returns [EntryRef::of::<Customer>(&invoice.customer_id)]
+    /// let expander = CacheExpander::new(backend).with_dependency_cascade(3);
+    ///
+    /// expander.write_through(&customer, &customer_repo).await?; // evicts the customer's invoices
+    /// ```
+    pub fn with_dependency_cascade(mut self, max_depth: usize) -> Self {
+        self.cascade_depth = Some(max_depth.max(1));
+        self
+    }
+
     /// Generic cache operation with strategy.
     ///
     /// This is the primary method used in 80% of cases.
@@ -177,7 +496,9 @@ impl<B: CacheBackend> CacheExpander<B> {
     /// - `Error::VersionMismatch`: Schema version mismatch between code and cached data
     /// - `Error::BackendError`: Cache backend is unavailable or network error
     /// - `Error::RepositoryError`: Database access fails
-    /// - `Error::Timeout`: Operation exceeds timeout threshold
+    /// - `Error::Timeout`: Backend connection checkout exceeds its timeout
+    /// - `Error::ConfigError`: `strategy` is `StaleWhileRevalidate`, which needs
+    ///   [`with_background_refresh`](Self::with_background_refresh)
     /// - `Error::SerializationError`: Entity serialization for caching fails
     pub async fn with<T, F, R>(
         &self,
@@ -189,7 +510,6 @@ impl<B: CacheBackend> CacheExpander<B> {
         T: CacheEntity,
         F: CacheFeed<T>,
         R: DataRepository<T>,
-        T::Key: FromStr,
     {
         // Delegate to with_config with default configuration
         self.with_config::<T, F, R>(feeder, repository, strategy, OperationConfig::default())
@@ -234,10 +554,13 @@ impl<B: CacheBackend> CacheExpander<B> {
     /// - `Error::VersionMismatch`: Schema version mismatch between code and cached data
     /// - `Error::BackendError`: Cache backend is unavailable or network error
     /// - `Error::RepositoryError`: Database access fails
-    /// - `Error::Timeout`: Operation exceeds timeout threshold
+    /// - `Error::Timeout`: A deadline in `config` is exceeded; the error names the phase
+    /// - `Error::ConfigError`: `strategy` is `StaleWhileRevalidate`, which needs
+    ///   [`with_background_refresh`](Self::with_background_refresh)
     /// - `Error::SerializationError`: Entity serialization for caching fails
     ///
-    /// Failed operations are retried up to `config.retry_count` times with exponential backoff.
+    /// Retryable failures are retried according to `config.retry`; the feeder is
+    /// validated once, before the first attempt.
     pub async fn with_config<T, F, R>(
         &self,
         feeder: &mut F,
@@ -249,374 +572,5070 @@ impl<B: CacheBackend> CacheExpander<B> {
         T: CacheEntity,
         F: CacheFeed<T>,
         R: DataRepository<T>,
-        T::Key: FromStr,
     {
-        // Retry logic
-        let mut attempts = 0;
-        let max_attempts = config.retry_count + 1; // +1 for initial attempt
+        self.with_config_inner::<T, F, R>(feeder, repository, strategy, config, None)
+            .await
+    }
+
+    /// Execute cache operation, refreshing stale entries in the background.
+    ///
+    /// Behaves like [`with_config`](Self::with_config), except that stale hits
+    /// under `CacheStrategy::StaleWhileRevalidate` are returned immediately and
+    /// refreshed on a spawned Tokio task. This is the only entry point for
+    /// that strategy. Background refreshes load like a foreground miss under
+    /// `config` (deadlines, retries, single-flight, TTLs), and at most one
+    /// refresh per key runs at a time.
+    ///
+    /// The expander and the repository are cloned into the background task,
+    /// so the expander must be in an `Arc` and the repository `Clone + 'static`;
+    /// wrap it in an `Arc` if it is not cheap to clone.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let expander = Arc::new(CacheExpander::new(backend));
+    /// let repo = Arc::new(UserRepository::new(pool));
+    /// let strategy = CacheStrategy::StaleWhileRevalidate {
+    ///     stale_after: Duration::from_secs(60),
+    /// };
+    ///
+    /// expander
+    ///     .with_background_refresh(&mut feeder, &repo, strategy, OperationConfig::default())
+    ///     .await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same error cases as [`with_config`](Self::with_config). Errors from a
+    /// background refresh are logged and do not affect the caller.
+    pub async fn with_background_refresh<T, F, R>(
+        self: &Arc<Self>,
+        feeder: &mut F,
+        repository: &R,
+        strategy: CacheStrategy,
+        config: OperationConfig,
+    ) -> Result<()>
+    where
+        B: 'static,
+        T: CacheEntity + 'static,
+        F: CacheFeed<T>,
+        R: DataRepository<T> + Clone + 'static,
+    {
+        let refresh_config = config.clone();
+        let spawn_refresh = |cache_key: String, id: T::Key| {
+            self.spawn_refresh::<T, R>(cache_key, id, repository.clone(), refresh_config.clone());
+        };
 
-        loop {
-            attempts += 1;
+        self.with_config_inner::<T, F, R>(
+            feeder,
+            repository,
+            strategy,
+            config,
+            Some(&spawn_refresh),
+        )
+        .await
+    }
 
-            let result = self
-                .execute_operation::<T, F, R>(feeder, repository, strategy.clone(), &config)
-                .await;
+    /// Read one entity, loading it with a closure on a miss.
+    ///
+    /// A shortcut for one-off lookups that don't warrant a `CacheFeed` and a
+    /// `DataRepository`: behaves exactly like [`with`](Self::with) with a
+    /// repository whose `fetch_by_id` calls `load`, and returns the entity
+    /// directly. `load` may run more than once if the operation is retried.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let user = expander
+    ///     .get_or_load::<User, _>("user_123".to_string(), CacheStrategy::Refresh, || async {
+    ///         db.find_user("user_123").await
+    ///     })
+    ///     .await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same error cases as [`with`](Self::with); errors returned by `load` are
+    /// passed through unchanged.
+    pub async fn get_or_load<T, Fut>(
+        &self,
+        id: T::Key,
+        strategy: CacheStrategy,
+        load: impl Fn() -> Fut + Send + Sync,
+    ) -> Result<Option<T>>
+    where
+        T: CacheEntity,
+        Fut: Future<Output = Result<Option<T>>> + Send,
+    {
+        let mut feeder = GenericFeeder::new(id);
+        self.with::<T, _, _>(&mut feeder, &LoaderRepository(load), strategy)
+            .await?;
+        Ok(feeder.data)
+    }
 
-            match result {
-                Ok(()) => return Ok(()),
-                Err(e) => {
-                    if attempts >= max_attempts {
-                        return Err(e);
-                    }
+    /// Read one entity from the cache only.
+    ///
+    /// Equivalent to `CacheStrategy::Fresh`: returns `None` on a miss without
+    /// consulting any repository.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let session: Option<Session> = expander.get::<Session>(token).await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `Error::DeserializationError`, `Error::InvalidCacheEntry`, `Error::VersionMismatch`:
+    ///   The cached entry is unreadable (unless self-healing is enabled)
+    /// - `Error::BackendError`: Cache backend is unavailable (unless failing open)
+    pub async fn get<T>(&self, id: T::Key) -> Result<Option<T>>
+    where
+        T: CacheEntity,
+    {
+        self.get_or_load::<T, _>(id, CacheStrategy::Fresh, || async { Ok(None) })
+            .await
+    }
 
-                    debug!(
-                        "Cache operation failed (attempt {}/{}), retrying...",
-                        attempts, max_attempts
-                    );
+    /// Read one entity by an alternate unique key.
+    ///
+    /// Follows the pointer `"{index_prefix}:{key}"` to the entity's primary
+    /// cache entry. If either is missing, or the cached entity no longer has
+    /// `key` (the field changed since the pointer was written), the entity is
+    /// loaded with `IndexedRepository::fetch_by_index` and cached with fresh
+    /// pointers. See the [`index`](crate::index) module.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let user = expander
+    ///     .with_index::<User, ByEmail, _>(&"ada@example.com".to_string(), &user_repo)
+    ///     .await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `Error::ValidationError`: The entity fails validation
+    /// - `Error::DeserializationError`, `Error::InvalidCacheEntry`, `Error::VersionMismatch`:
+    ///   The cached entry is unreadable (unless self-healing is enabled)
+    /// - `Error::BackendError`: Cache backend is unavailable (unless failing open)
+    /// - `Error::RepositoryError`: Repository lookup fails
+    pub async fn with_index<T, I, R>(&self, key: &I::Key, repository: &R) -> Result<Option<T>>
+    where
+        T: CacheEntity,
+        I: SecondaryIndex<T>,
+        R: IndexedRepository<T, I>,
+    {
+        let config = OperationConfig::default();
+        let timer = Instant::now();
+        let secondary_key = SecondaryKey::of::<T, I>(key);
+        let pointer_key = secondary_key.pointer_key();
 
-                    // Exponential backoff
-                    if config.retry_count > 0 {
-                        let delay =
-                            tokio::time::Duration::from_millis(100 * 2_u64.pow(attempts - 1));
-                        tokio::time::sleep(delay).await;
+        let target = self
+            .read_cache(&pointer_key, &config)
+            .await?
+            .and_then(|bytes| String::from_utf8(bytes).ok());
+        if let Some(cache_key) = target {
+            match self.read_cache(&cache_key, &config).await? {
+                Some(bytes) if !serialization::is_tombstone(&bytes) => {
+                    match Self::decode_hit::<T>(&bytes) {
+                        Ok((entity, _))
+                            if entity.cache_secondary_keys().contains(&secondary_key) =>
+                        {
+                            self.metrics.record_hit(&pointer_key, timer.elapsed());
+                            self.record_access::<T>(&[entity.cache_key()]);
+                            return Ok(Some(entity));
+                        }
+                        Ok(_) => {
+                            debug!("Pointer {} is stale, deleting", pointer_key);
+                            let _ = self.backend.delete(&pointer_key).await;
+                        }
+                        Err(e) => self.heal(&cache_key, &config, e).await?,
                     }
                 }
+                _ => debug!("Pointer {} leads to a missing entry", pointer_key),
             }
         }
+        self.metrics.record_miss(&pointer_key, timer.elapsed());
+
+        let started = Instant::now();
+        let Some(entity) = repository.fetch_by_index(key).await? else {
+            return Ok(None);
+        };
+        entity.validate()?;
+
+        let id = entity.cache_key();
+        self.record_access::<T>(std::slice::from_ref(&id));
+        let cache_key = self.cache_key::<T>(&id, &config).await?;
+        self.populate_entity(&cache_key, &entity, started.elapsed(), &config)
+            .await?;
+        Ok(Some(entity))
     }
 
-    /// Internal method to execute a single cache operation (without retry).
-    async fn execute_operation<T, F, R>(
+    /// Batch cache operation for many entities of one type.
+    ///
+    /// Builds every cache key, reads them with one `backend.mget`, loads only
+    /// the missing IDs with one `DataRepository::fetch_by_ids`, and back-fills
+    /// the cache with one `backend.mset`. Results are returned in input order;
+    /// `None` means the entity doesn't exist.
+    ///
+    /// Strategies behave as for [`with`](Self::with), except that
+    /// `StaleWhileRevalidate` is treated as `Refresh` and early expiration is
+    /// not applied. Each key is reported to metrics as a hit if it was served
+    /// from the cache, or a miss otherwise. With a jittered TTL policy, the
+    /// entries back-filled by one call share a single TTL draw.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let ids: Vec<String> = vec!["p1".into(), "p2".into(), "p3".into()];
+    /// let products: Vec<Option<Product>> = expander
+    ///     .with_many(&ids, &repo, CacheStrategy::Refresh)
+    ///     .await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `Error::ValidationError`: An entity fails validation
+    /// - `Error::DeserializationError`: Cached data is corrupted or has wrong format
+    /// - `Error::InvalidCacheEntry`: Cache magic header mismatch or invalid envelope
+    /// - `Error::VersionMismatch`: Schema version mismatch between code and cached data
+    /// - `Error::BackendError`: Cache backend is unavailable or network error
+    /// - `Error::RepositoryError`: Repository fails, or returns the wrong number of results
+    pub async fn with_many<T, R>(
         &self,
-        feeder: &mut F,
+        ids: &[T::Key],
         repository: &R,
         strategy: CacheStrategy,
-        config: &OperationConfig,
-    ) -> Result<()>
+    ) -> Result<Vec<Option<T>>>
     where
         T: CacheEntity,
-        F: CacheFeed<T>,
         R: DataRepository<T>,
-        T::Key: FromStr,
     {
-        let timer = Instant::now();
-
-        // Step 1: Validate feeder
-        feeder.validate()?;
+        self.with_many_config::<T, R>(ids, repository, strategy, OperationConfig::default())
+            .await
+    }
 
-        // Step 2: Get entity ID and build cache key
-        let entity_id = feeder.entity_id();
-        let cache_key = CacheKeyBuilder::build::<T>(&entity_id);
+    /// Batch cache operation with custom configuration.
+    ///
+    /// Behaves like [`with_many`](Self::with_many), with `config` applied as in
+    /// [`with_config`](Self::with_config): the `mget`, `mset` and
+    /// `fetch_by_ids` calls run under its backend and repository deadlines and
+    /// retry policy, the whole batch under `config.timeout`, and backend read
+    /// failures follow its failure mode. With request coalescing enabled,
+    /// misses join loads already in flight for the same keys, and only the
+    /// remaining IDs go to `fetch_by_ids`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let config = OperationConfig::default()
+    ///     .with_repository_timeout(Duration::from_millis(500))
+    ///     .with_failure_mode(FailureMode::Open);
+    /// let products = expander
+    ///     .with_many_config(&ids, &repo, CacheStrategy::Refresh, config)
+    ///     .await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same error cases as [`with_many`](Self::with_many), plus
+    /// `Error::Timeout` when a deadline in `config` is exceeded.
+    pub async fn with_many_config<T, R>(
+        &self,
+        ids: &[T::Key],
+        repository: &R,
+        strategy: CacheStrategy,
+        config: OperationConfig,
+    ) -> Result<Vec<Option<T>>>
+    where
+        T: CacheEntity,
+        R: DataRepository<T>,
+    {
+        self.record_access::<T>(ids);
 
-        debug!(
-            "» Cache operation for key: {} (strategy: {})",
-            cache_key, strategy
-        );
+        let operation = async {
+            let mut backoff = config.retry.backoff();
+            loop {
+                let result = self
+                    .execute_many::<T, R>(ids, repository, strategy.clone(), &config)
+                    .await;
 
-        // Step 3: Execute strategy
-        let result = match strategy {
-            CacheStrategy::Fresh => {
-                self.strategy_fresh::<T, R>(&cache_key, repository, config)
-                    .await
+                let Err(e) = result else {
+                    return result;
+                };
+                if config.retry.scope != RetryScope::Operation || !backoff.retry(&e).await {
+                    return Err(e);
+                }
             }
-            CacheStrategy::Refresh => {
-                self.strategy_refresh::<T, R>(&cache_key, repository, config)
+        };
+
+        with_deadline(TimeoutPhase::Operation, config.timeout, operation).await
+    }
+
+    /// Internal method to execute a single batch operation (without retry).
+    async fn execute_many<T, R>(
+        &self,
+        ids: &[T::Key],
+        repository: &R,
+        strategy: CacheStrategy,
+        config: &OperationConfig,
+    ) -> Result<Vec<Option<T>>>
+    where
+        T: CacheEntity,
+        R: DataRepository<T>,
+    {
+        let timer = Instant::now();
+        let cache_keys = self.cache_keys::<T>(ids, config).await?;
+        let key_refs: Vec<&str> = cache_keys.iter().map(String::as_str).collect();
+        let failure_mode = self.failure_mode_for(config);
+
+        debug!(
+            "» Batch cache operation for {} {} keys (strategy: {})",
+            ids.len(),
+            T::cache_prefix(),
+            strategy
+        );
+
+        let mut results: Vec<Option<T>> = ids.iter().map(|_| None).collect();
+        let mut missing: Vec<usize> = Vec::new();
+
+        match strategy {
+            CacheStrategy::Fresh
+            | CacheStrategy::Refresh
+            | CacheStrategy::StaleWhileRevalidate { .. } => {
+                let cached = match self
+                    .backend_call(config, || self.backend.mget(&key_refs))
                     .await
+                {
+                    Ok(cached) => cached,
+                    Err(e) => {
+                        self.tolerate(T::cache_prefix(), failure_mode, e)?;
+                        vec![None; ids.len()]
+                    }
+                };
+                for (index, bytes) in cached.into_iter().enumerate() {
+                    let cache_key = &cache_keys[index];
+                    match bytes {
+                        Some(bytes) if self.is_negative_hit(cache_key, &bytes) => {
+                            self.metrics.record_miss(cache_key, timer.elapsed());
+                        }
+                        Some(bytes) => match Self::decode_hit::<T>(&bytes) {
+                            Ok((entity, _)) => {
+                                results[index] = Some(entity);
+                                self.metrics.record_hit(cache_key, timer.elapsed());
+                            }
+                            Err(e) => {
+                                self.heal(cache_key, config, e).await?;
+                                missing.push(index);
+                            }
+                        },
+                        None => missing.push(index),
+                    }
+                }
+
+                if matches!(strategy, CacheStrategy::Fresh) {
+                    for index in missing.drain(..) {
+                        self.metrics
+                            .record_miss(&cache_keys[index], timer.elapsed());
+                    }
+                }
             }
             CacheStrategy::Invalidate => {
-                self.strategy_invalidate::<T, R>(&cache_key, repository, config)
+                if let Err(e) = self
+                    .backend_call(config, || self.backend.mdelete(&key_refs))
                     .await
+                {
+                    self.tolerate(T::cache_prefix(), failure_mode, e)?;
+                }
+                missing = (0..ids.len()).collect();
             }
             CacheStrategy::Bypass => {
-                self.strategy_bypass::<T, R>(&cache_key, repository, config)
-                    .await
+                missing = (0..ids.len()).collect();
             }
-        };
+        }
 
-        // Step 4: Handle result
-        match result {
-            Ok(Some(entity)) => {
-                entity.validate()?;
-                feeder.on_hit(&cache_key)?;
-                feeder.on_loaded(&entity)?;
-                feeder.feed(Some(entity));
-                self.metrics.record_hit(&cache_key, timer.elapsed());
-                info!("✓ Cache operation succeeded in {:?}", timer.elapsed());
-            }
-            Ok(None) => {
-                feeder.on_miss(&cache_key)?;
-                feeder.feed(None);
-                self.metrics.record_miss(&cache_key, timer.elapsed());
-                debug!("Entity not found after cache operation for {}", cache_key);
-            }
-            Err(e) => {
-                self.metrics.record_error(&cache_key, &e.to_string());
-                return Err(e);
-            }
+        if missing.is_empty() {
+            return Ok(results);
         }
 
-        Ok(())
+        let missing_keys: Vec<&str> = missing.iter().map(|&index| key_refs[index]).collect();
+        let missing_ids: Vec<T::Key> = missing.iter().map(|&index| ids[index].clone()).collect();
+        let loaded = self
+            .load_many::<T, R>(&missing_keys, &missing_ids, repository, config)
+            .await?;
+
+        for (index, entity) in missing.into_iter().zip(loaded) {
+            results[index] = entity;
+            self.metrics.record_miss(key_refs[index], timer.elapsed());
+        }
+
+        info!(
+            "✓ Batch cache operation for {} keys succeeded in {:?}",
+            ids.len(),
+            timer.elapsed()
+        );
+        Ok(results)
     }
 
-    /// Fresh strategy: Cache only, no database fallback.
-    async fn strategy_fresh<T: CacheEntity, R: DataRepository<T>>(
+    /// Load the entities missing from the cache and store them.
+    ///
+    /// Goes through the single-flight group when request coalescing is enabled,
+    /// so keys already being loaded by other operations are joined rather than
+    /// fetched again.
+    async fn load_many<T: CacheEntity, R: DataRepository<T>>(
         &self,
-        cache_key: &str,
-        _repository: &R,
-        _config: &OperationConfig,
-    ) -> Result<Option<T>> {
-        debug!("Executing Fresh strategy for {}", cache_key);
+        cache_keys: &[&str],
+        ids: &[T::Key],
+        repository: &R,
+        config: &OperationConfig,
+    ) -> Result<Vec<Option<T>>> {
+        let Some(single_flight) = &self.single_flight else {
+            let loaded = self
+                .fetch_many::<T, R>(cache_keys, ids, repository, config)
+                .await?;
+            return Ok(loaded
+                .into_iter()
+                .map(|entry| entry.map(|(entity, _)| entity))
+                .collect());
+        };
 
-        match self.backend.get(cache_key).await? {
-            Some(bytes) => {
-                debug!("✓ Cache hit (Fresh strategy)");
-                T::deserialize_from_cache(&bytes).map(Some)
-            }
-            None => {
-                debug!("✗ Cache miss (Fresh strategy) - no fallback");
-                Ok(None)
-            }
-        }
+        let loaded: Mutex<Vec<Option<T>>> = Mutex::new(ids.iter().map(|_| None).collect());
+        let flights = single_flight
+            .run_many(cache_keys, |led| {
+                let loaded = &loaded;
+                async move {
+                    let led_keys: Vec<&str> = led.iter().map(|&index| cache_keys[index]).collect();
+                    let led_ids: Vec<T::Key> =
+                        led.iter().map(|&index| ids[index].clone()).collect();
+                    let fetched = self
+                        .fetch_many::<T, R>(&led_keys, &led_ids, repository, config)
+                        .await?;
+
+                    let mut loaded = loaded.lock().unwrap_or_else(PoisonError::into_inner);
+                    Ok(led
+                        .into_iter()
+                        .zip(fetched)
+                        .map(|(index, entry)| {
+                            entry.map(|(entity, bytes)| {
+                                loaded[index] = Some(entity);
+                                bytes
+                            })
+                        })
+                        .collect())
+                }
+            })
+            .await;
+
+        let mut loaded = loaded.into_inner().unwrap_or_else(PoisonError::into_inner);
+        flights
+            .into_iter()
+            .enumerate()
+            .map(|(index, flight)| {
+                if flight.coalesced {
+                    debug!(
+                        "Coalesced cache miss for {} onto in-flight load",
+                        cache_keys[index]
+                    );
+                    self.metrics.record_coalesced(cache_keys[index]);
+                }
+                match (loaded[index].take(), flight.value?) {
+                    (Some(entity), _) => Ok(Some(entity)),
+                    (None, Some(bytes)) => T::deserialize_from_cache(&bytes).map(Some),
+                    (None, None) => Ok(None),
+                }
+            })
+            .collect()
     }
 
-    /// Refresh strategy: Try cache, fallback to database on miss.
-    async fn strategy_refresh<T: CacheEntity, R: DataRepository<T>>(
+    /// Fetch entities with one `fetch_by_ids` and back-fill the cache.
+    ///
+    /// Returns each entity with its serialized entry, in the order of `ids`.
+    /// Cache write failures are ignored as in `populate()`.
+    async fn fetch_many<T: CacheEntity, R: DataRepository<T>>(
         &self,
-        cache_key: &str,
+        cache_keys: &[&str],
+        ids: &[T::Key],
         repository: &R,
         config: &OperationConfig,
-    ) -> Result<Option<T>>
-    where
-        T::Key: FromStr,
-    {
-        debug!("Executing Refresh strategy for {}", cache_key);
+    ) -> Result<Vec<Option<(T, Vec<u8>)>>> {
+        let started = Instant::now();
+        let loaded = config
+            .retry
+            .run_in(RetryScope::Repository, || {
+                let fetch = repository.fetch_by_ids(ids);
+                with_deadline(TimeoutPhase::Repository, config.repository_timeout, fetch)
+            })
+            .await?;
+        let compute = started.elapsed();
 
-        // Try cache first
-        if let Some(bytes) = self.backend.get(cache_key).await? {
-            debug!("✓ Cache hit (Refresh strategy)");
-            return T::deserialize_from_cache(&bytes).map(Some);
+        if loaded.len() != ids.len() {
+            return Err(Error::RepositoryError(format!(
+                "fetch_by_ids returned {} results for {} ids",
+                loaded.len(),
+                ids.len()
+            )));
         }
 
-        debug!("Cache miss, falling back to database");
+        // Entities are written with one `mset` per distinct TTL
+        let mut by_ttl: BTreeMap<Option<Duration>, TtlGroup<'_>> = BTreeMap::new();
+        let mut not_found: Vec<&str> = Vec::new();
+        let mut results = Vec::with_capacity(ids.len());
 
-        // Cache miss - fetch from database
-        let id = self.extract_id_from_key::<T>(cache_key)?;
-        match repository.fetch_by_id(&id).await? {
-            Some(entity) => {
-                // Store in cache for future use
-                // Use config override if provided, otherwise use default TTL policy
-                let ttl = config
-                    .ttl_override
-                    .or_else(|| self.ttl_policy.get_ttl(T::cache_prefix()));
-                let bytes = entity.serialize_for_cache()?;
-                let _ = self.backend.set(cache_key, bytes, ttl).await;
-                Ok(Some(entity))
+        for (index, entity) in loaded.into_iter().enumerate() {
+            let cache_key = cache_keys[index];
+            match entity {
+                Some(entity) => {
+                    entity.validate()?;
+                    let ttl = self.entity_ttl(&entity, config);
+                    let bytes = Self::encode(&entity, ttl, compute)?;
+                    let (entries, stored) = by_ttl.entry(ttl).or_default();
+                    entries.push((cache_key, bytes.clone()));
+                    stored.push(index);
+                    results.push(Some((entity, bytes)));
+                }
+                None => {
+                    not_found.push(cache_key);
+                    results.push(None);
+                }
+            }
+        }
+
+        for (ttl, (entries, stored)) in by_ttl {
+            let write = self.backend_call(config, || self.backend.mset(&entries, ttl));
+            if let Err(e) = write.await {
+                debug!("Failed to populate cache for {} keys: {}", entries.len(), e);
+                continue;
+            }
+            let tagged: Vec<(&str, &T)> = stored
+                .iter()
+                .filter_map(|&index| Some((cache_keys[index], &results[index].as_ref()?.0)))
+                .collect();
+            self.index_entries(&tagged, ttl).await;
+        }
+
+        if let Some(tombstone_ttl) = self.tombstone_ttl::<T>() {
+            if !not_found.is_empty() {
+                let tombstone = serialization::serialize_tombstone(T::cache_prefix())?;
+                let tombstones: Vec<(&str, Vec<u8>)> = not_found
+                    .into_iter()
+                    .map(|cache_key| (cache_key, tombstone.clone()))
+                    .collect();
+                let write =
+                    self.backend_call(config, || self.backend.mset(&tombstones, tombstone_ttl));
+                if let Err(e) = write.await {
+                    debug!(
+                        "Failed to store {} negative cache entries: {}",
+                        tombstones.len(),
+                        e
+                    );
+                }
             }
-            None => Ok(None),
         }
+
+        Ok(results)
     }
 
-    /// Invalidate strategy: Clear cache and refresh from database.
-    async fn strategy_invalidate<T: CacheEntity, R: DataRepository<T>>(
-        &self,
-        cache_key: &str,
-        repository: &R,
-        config: &OperationConfig,
-    ) -> Result<Option<T>>
+    /// Pre-populate the cache with every entity in `repository`.
+    ///
+    /// Reads the repository a page at a time with `fetch_page` (or all at once
+    /// with `fetch_all` if paging isn't implemented) and writes each page to
+    /// the cache in batches of `options.batch_size`, with up to
+    /// `options.concurrency` batches in flight. Every entry gets its own TTL
+    /// (the entity's, or else one from the TTL policy), so a jittered policy
+    /// spreads out the expiry of a freshly warmed cache.
+    ///
+    /// Entities rejected by the ID filter are skipped. Entities that fail
+    /// validation or serialization, and batches the backend fails to write,
+    /// are counted in `WarmProgress::failed` without stopping the run.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let progress = expander
+    ///     .warm::<Product, _>(&repo, WarmOptions::default().with_concurrency(8))
+    ///     .await?;
+    /// info!("Warmed {} products", progress.written);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `Error::RepositoryError`: Reading a page from the repository fails
+    /// - `Error::NotImplemented`: The repository implements neither `fetch_page` nor `fetch_all`
+    pub async fn warm<T, R>(&self, repository: &R, options: WarmOptions<T>) -> Result<WarmProgress>
     where
-        T::Key: FromStr,
+        T: CacheEntity,
+        R: DataRepository<T>,
     {
-        debug!("Executing Invalidate strategy for {}", cache_key);
+        let timer = Instant::now();
+        let mut progress = WarmProgress {
+            total: repository.count().await.ok(),
+            ..WarmProgress::default()
+        };
 
-        // Delete from cache
-        self.backend.delete(cache_key).await?;
-        debug!("✓ Cache invalidated for {}", cache_key);
-
-        // Fetch fresh from database
-        let id = self.extract_id_from_key::<T>(cache_key)?;
-        match repository.fetch_by_id(&id).await? {
-            Some(entity) => {
-                // Re-populate cache
-                // Use config override if provided, otherwise use default TTL policy
-                let ttl = config
-                    .ttl_override
-                    .or_else(|| self.ttl_policy.get_ttl(T::cache_prefix()));
-                let bytes = entity.serialize_for_cache()?;
-                let _ = self.backend.set(cache_key, bytes, ttl).await;
-                Ok(Some(entity))
+        info!("» Warming {} cache entries", T::cache_prefix());
+
+        let mut offset = 0;
+        loop {
+            let started = Instant::now();
+            let page = match repository.fetch_page(offset, options.page_size).await {
+                Err(Error::NotImplemented(_)) if offset == 0 => {
+                    debug!(
+                        "fetch_page not implemented for {}, falling back to fetch_all",
+                        T::cache_prefix()
+                    );
+                    let all = repository.fetch_all().await?;
+                    let compute = started.elapsed();
+                    progress.total = progress.total.or(Some(all.len() as u64));
+                    for page in all.chunks(options.page_size) {
+                        self.warm_page(page, compute, &options, &mut progress, timer)
+                            .await;
+                    }
+                    break;
+                }
+                page => page?,
+            };
+
+            offset += page.len() as u64;
+            self.warm_page(&page, started.elapsed(), &options, &mut progress, timer)
+                .await;
+            if page.len() < options.page_size {
+                break;
             }
-            None => Ok(None),
         }
+
+        info!(
+            "✓ Warmed {} of {} {} entries in {:?} ({} skipped, {} failed)",
+            progress.written,
+            progress.fetched,
+            T::cache_prefix(),
+            timer.elapsed(),
+            progress.skipped,
+            progress.failed
+        );
+        Ok(progress)
     }
 
-    /// Bypass strategy: Skip cache, always hit database.
-    async fn strategy_bypass<T: CacheEntity, R: DataRepository<T>>(
+    /// Write one page of a warming run to the cache and report progress.
+    async fn warm_page<T: CacheEntity>(
         &self,
-        cache_key: &str,
-        repository: &R,
-        config: &OperationConfig,
-    ) -> Result<Option<T>>
-    where
-        T::Key: FromStr,
-    {
-        debug!("Executing Bypass strategy for {}", cache_key);
-        debug!("Bypassing cache entirely for {}", cache_key);
+        page: &[T],
+        compute: Duration,
+        options: &WarmOptions<T>,
+        progress: &mut WarmProgress,
+        timer: Instant,
+    ) {
+        progress.fetched += page.len() as u64;
 
-        // Fetch from database without checking cache
-        let id = self.extract_id_from_key::<T>(cache_key)?;
-        match repository.fetch_by_id(&id).await? {
-            Some(entity) => {
-                // Still populate cache for others
-                // Use config override if provided, otherwise use default TTL policy
-                let ttl = config
-                    .ttl_override
-                    .or_else(|| self.ttl_policy.get_ttl(T::cache_prefix()));
-                let bytes = entity.serialize_for_cache()?;
-                let _ = self.backend.set(cache_key, bytes, ttl).await;
-                Ok(Some(entity))
+        let (accepted, ids): (Vec<&T>, Vec<T::Key>) = page
+            .iter()
+            .map(|entity| (entity, entity.cache_key()))
+            .filter(|(_, id)| options.accepts(id))
+            .unzip();
+        progress.skipped += (page.len() - accepted.len()) as u64;
+
+        let cache_keys = match self
+            .cache_keys::<T>(&ids, &OperationConfig::default())
+            .await
+        {
+            Ok(cache_keys) => cache_keys,
+            Err(e) => {
+                warn!("Failed to resolve cache keys while warming: {}", e);
+                progress.failed += accepted.len() as u64;
+                progress.elapsed = timer.elapsed();
+                options.report(progress);
+                return;
+            }
+        };
+
+        let mut entries: Vec<(String, Vec<u8>, Option<Duration>)> = Vec::new();
+        let mut warmed: Vec<&T> = Vec::new();
+        for (entity, cache_key) in accepted.into_iter().zip(cache_keys) {
+            let ttl = self.entity_ttl(entity, &OperationConfig::default());
+            match entity
+                .validate()
+                .and_then(|()| Self::encode(entity, ttl, compute))
+            {
+                Ok(bytes) => {
+                    entries.push((cache_key, bytes, ttl));
+                    warmed.push(entity);
+                }
+                Err(e) => {
+                    warn!("Skipping {} while warming: {}", cache_key, e);
+                    progress.failed += 1;
+                }
+            }
+        }
+
+        let mut writes = stream::iter(entries.chunks(options.batch_size))
+            .map(|batch| self.warm_batch(batch))
+            .buffer_unordered(options.concurrency);
+        while let Some((written, result)) = writes.next().await {
+            match result {
+                Ok(()) => progress.written += written,
+                Err(e) => {
+                    warn!("Failed to write {} warmed entries: {}", written, e);
+                    progress.failed += written;
+                }
             }
-            None => Ok(None),
         }
+
+        let tagged: Vec<(&str, &T)> = entries
+            .iter()
+            .map(|(cache_key, _, _)| cache_key.as_str())
+            .zip(warmed)
+            .collect();
+        let ttl = longest_ttl(entries.iter().map(|(_, _, ttl)| *ttl));
+        self.index_entries(&tagged, ttl).await;
+
+        progress.elapsed = timer.elapsed();
+        options.report(progress);
+    }
+
+    /// Write one batch of warmed entries, with one `mset` per distinct TTL.
+    ///
+    /// Returns the batch size along with the result.
+    async fn warm_batch(&self, batch: &[(String, Vec<u8>, Option<Duration>)]) -> (u64, Result<()>) {
+        let mut by_ttl: BTreeMap<Option<Duration>, Vec<(&str, Vec<u8>)>> = BTreeMap::new();
+        for (cache_key, bytes, ttl) in batch {
+            by_ttl
+                .entry(*ttl)
+                .or_default()
+                .push((cache_key.as_str(), bytes.clone()));
+        }
+
+        let timer = Instant::now();
+        for (ttl, items) in by_ttl {
+            if let Err(e) = self.backend.mset(&items, ttl).await {
+                return (batch.len() as u64, Err(e));
+            }
+        }
+        for (cache_key, _, _) in batch {
+            self.metrics.record_set(cache_key, timer.elapsed());
+        }
+        (batch.len() as u64, Ok(()))
     }
 
-    /// Extract the ID portion from a cache key.
-    /// Format: "prefix:id" → "id"
-    fn extract_id_from_key<T: CacheEntity>(&self, cache_key: &str) -> Result<T::Key>
+    /// Persist an entity, then store it in the cache (write-through).
+    ///
+    /// Ordering and failure handling:
+    /// 1. The entity is validated and saved via `WritableRepository::save`. If
+    ///    either fails, the error is returned and the cache is left untouched.
+    /// 2. The entity is written to the cache under `entity.cache_key()` with the
+    ///    entity's TTL, or else one from the expander's TTL policy. If this
+    ///    fails, the stale entry is evicted on a best-effort basis and
+    ///    `Error::BackendError` is returned (or the error is ignored in
+    ///    `FailureMode::Open`); the repository write is **not** rolled back.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// user.name = "Alice".to_string();
+    /// expander.write_through(&user, &repo).await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `Error::ValidationError`: Entity validation fails
+    /// - `Error::RepositoryError`: Repository write fails
+    /// - `Error::SerializationError`: Entity serialization fails
+    /// - `Error::BackendError`: Cache write fails after the repository write succeeded
+    pub async fn write_through<T, R>(&self, entity: &T, repository: &R) -> Result<()>
     where
-        T::Key: FromStr,
+        T: CacheEntity,
+        R: WritableRepository<T>,
     {
-        let parts: Vec<&str> = cache_key.split(':').collect();
-        if parts.len() > 1 {
-            let id_str = parts[1..].join(":");
-            id_str.parse().ok().ok_or_else(|| {
-                Error::ValidationError(format!("Failed to parse ID from cache key: {}", cache_key))
-            })
-        } else {
-            Err(Error::ValidationError(format!(
-                "Invalid cache key format: {}",
-                cache_key
-            )))
+        entity.validate()?;
+        let cache_key = self
+            .cache_key::<T>(&entity.cache_key(), &OperationConfig::default())
+            .await?;
+
+        let started = Instant::now();
+        repository.save(entity).await?;
+        let compute = started.elapsed();
+
+        match self.store_entity(&cache_key, entity, compute).await {
+            Ok(()) => debug!("✓ Write-through stored {}", cache_key),
+            Err(e) => {
+                warn!(
+                    "Write-through to cache failed for {}, evicting: {}",
+                    cache_key, e
+                );
+                let _ = self.backend.delete(&cache_key).await;
+                self.tolerate(
+                    &cache_key,
+                    self.failure_mode,
+                    Self::cache_write_error(&cache_key, e),
+                )?;
+            }
         }
+        self.cascade(&cache_key, false).await
     }
 
-    /// Get backend reference (for advanced use).
-    pub fn backend(&self) -> &B {
-        &self.backend
-    }
+    /// Persist an entity, then evict its cache entry (write-around).
+    ///
+    /// The next read reloads the entity from the repository. Use this for
+    /// entities that are written often but read rarely.
+    ///
+    /// Ordering and failure handling:
+    /// 1. The entity is validated and saved via `WritableRepository::save`. If
+    ///    either fails, the error is returned and the cache is left untouched.
+    /// 2. The cache entry is deleted. If this fails, `Error::BackendError` is
+    ///    returned (or the error is ignored in `FailureMode::Open`); the cache
+    ///    may serve the old entity until its TTL elapses.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::ValidationError`: Entity validation fails
+    /// - `Error::RepositoryError`: Repository write fails
+    /// - `Error::BackendError`: Cache eviction fails after the repository write succeeded
+    pub async fn write_around<T, R>(&self, entity: &T, repository: &R) -> Result<()>
+    where
+        T: CacheEntity,
+        R: WritableRepository<T>,
+    {
+        entity.validate()?;
+        let cache_key = self
+            .cache_key::<T>(&entity.cache_key(), &OperationConfig::default())
+            .await?;
 
-    /// Get mutable backend reference (for advanced use).
-    pub fn backend_mut(&mut self) -> &mut B {
-        &mut self.backend
+        repository.save(entity).await?;
+        self.evict(&cache_key).await
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::backend::InMemoryBackend;
-    use crate::feed::GenericFeeder;
-    use crate::repository::InMemoryRepository;
-    use serde::{Deserialize, Serialize};
+    /// Delete an entity from the repository, then evict its cache entry.
+    ///
+    /// Ordering and failure handling match [`write_around`](Self::write_around):
+    /// the repository is written first, and the cache is only touched once the
+    /// delete succeeds.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::RepositoryError`: Repository delete fails
+    /// - `Error::BackendError`: Cache eviction fails after the repository delete succeeded
+    pub async fn delete_through<T, R>(&self, id: &T::Key, repository: &R) -> Result<()>
+    where
+        T: CacheEntity,
+        R: WritableRepository<T>,
+    {
+        let cache_key = self.cache_key::<T>(id, &OperationConfig::default()).await?;
 
-    #[derive(Clone, Serialize, Deserialize)]
-    struct TestEntity {
-        id: String,
-        value: String,
+        repository.delete(id).await?;
+        self.evict(&cache_key).await
     }
 
-    impl CacheEntity for TestEntity {
-        type Key = String;
+    /// Read a query result, loading it with a closure on a miss.
+    ///
+    /// The result is cached under the query's key (see [`CacheQuery`]) with
+    /// the TTL policy for `Q::query_prefix()`, and is evicted when one of its
+    /// members is written or invalidated through this expander. With
+    /// `QueryStorage::Ids`, members are also written to the entity cache and a
+    /// hit reads them back with one `mget`; if any has left the cache, the
+    /// result is reloaded.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let query = InvoiceList { customer_id, limit: 20, offset: 0 };
+    /// let invoices = expander
+    ///     .query(&query, || async { db.list_invoices(&customer_id, 20, 0).await })
+    ///     .await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `Error::ValidationError`: A loaded entity fails validation
+    /// - `Error::DeserializationError`, `Error::InvalidCacheEntry`, `Error::VersionMismatch`:
+    ///   The cached result or a member is unreadable (unless self-healing is enabled)
+    /// - `Error::BackendError`: Cache backend is unavailable (unless failing open)
+    ///
+    /// Errors returned by `load` are passed through unchanged.
+    pub async fn query<Q, Fut>(
+        &self,
+        query: &Q,
+        load: impl FnOnce() -> Fut,
+    ) -> Result<Vec<Q::Entity>>
+    where
+        Q: CacheQuery,
+        Fut: Future<Output = Result<Vec<Q::Entity>>>,
+    {
+        let config = OperationConfig::default();
+        let timer = Instant::now();
+        let query_key = self.query_key(query, &config).await?;
 
-        fn cache_key(&self) -> Self::Key {
-            self.id.clone()
+        if let Some(bytes) = self.read_cache(&query_key, &config).await? {
+            let resolved =
+                match serialization::deserialize_from_cache::<QueryEntry<Vec<Q::Entity>>>(&bytes) {
+                    Ok(entry) => self.resolve_query_entry(entry, &config).await,
+                    Err(e) => Err(e),
+                };
+            match resolved {
+                Ok(Some(entities)) => {
+                    self.metrics.record_hit(&query_key, timer.elapsed());
+                    return Ok(entities);
+                }
+                Ok(None) => debug!("Members of {} left the cache, reloading", query_key),
+                Err(e) => self.heal(&query_key, &config, e).await?,
+            }
         }
+        self.metrics.record_miss(&query_key, timer.elapsed());
 
-        fn cache_prefix() -> &'static str {
-            "test"
+        let started = Instant::now();
+        let entities = load().await?;
+        for entity in &entities {
+            entity.validate()?;
         }
+        self.store_query(query, &query_key, &entities, started.elapsed(), &config)
+            .await;
+        Ok(entities)
     }
 
-    #[tokio::test]
-    async fn test_expander_with_fresh_strategy_hit() {
-        let backend = InMemoryBackend::new();
-        let expander = CacheExpander::new(backend.clone());
+    /// Evict one cached query result.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::BackendError`: Deleting the result fails
+    pub async fn invalidate_query<Q: CacheQuery>(&self, query: &Q) -> Result<()> {
+        let query_key = self.query_key(query, &OperationConfig::default()).await?;
 
-        // Pre-populate cache
-        let entity = TestEntity {
-            id: "1".to_string(),
-            value: "data".to_string(),
-        };
+        let timer = Instant::now();
+        self.backend.delete(&query_key).await?;
+        self.metrics.record_delete(&query_key, timer.elapsed());
+        Ok(())
+    }
+
+    /// Evict the cache entry of the entity with key `id`, and its dependents.
+    ///
+    /// Dependents are only evicted with `with_dependency_cascade()`. Returns
+    /// the number of dependent entries evicted.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Also evicts the invoices depending on customer 42
+    /// expander.invalidate::<Customer>(&42).await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `Error::BackendError`: Deleting the entry or its dependents fails
+    pub async fn invalidate<T: CacheEntity>(&self, id: &T::Key) -> Result<usize> {
+        let cache_key = self.cache_key::<T>(id, &OperationConfig::default()).await?;
+
+        let timer = Instant::now();
+        self.backend.delete(&cache_key).await?;
+        self.metrics.record_delete(&cache_key, timer.elapsed());
+        self.evict_related(&cache_key).await
+    }
+
+    /// Evict every cache entry tagged with `tag`.
+    ///
+    /// Entries are tagged when they are written, from `CacheEntity::cache_tags()`.
+    /// Only the evicted keys are removed from the tag's set, so entries tagged
+    /// while the invalidation runs survive it rather than losing their tag.
+    /// As with `invalidate`, the entries' secondary-key pointers, the query
+    /// results containing them and, with `with_dependency_cascade()`, their
+    /// dependents are evicted too. Returns the number of tagged keys evicted;
+    /// keys whose entries already expired are included.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Invoice::cache_tags() returns ["customer:42"] for customer 42's invoices
+    /// expander.invalidate_tag("customer:42").await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `Error::BackendError`: Reading the tag set or deleting entries fails
+    pub async fn invalidate_tag(&self, tag: &str) -> Result<usize> {
+        let timer = Instant::now();
+        let tag_key = CacheKeyBuilder::build_tag(tag);
+        let cache_keys = self.backend.smembers(&tag_key).await?;
+        if cache_keys.is_empty() {
+            debug!("No cache entries tagged {}", tag);
+            return Ok(0);
+        }
+
+        let key_refs: Vec<&str> = cache_keys.iter().map(String::as_str).collect();
+        self.backend.mdelete(&key_refs).await?;
+        self.backend.srem(&tag_key, &key_refs).await?;
+
+        // Evict what depends on the tagged entries, as `invalidate` does
+        let mut evicted = cache_keys.clone();
+        for cache_key in &cache_keys {
+            evicted.extend(self.cascade_dependents(cache_key).await?);
+        }
+        self.evict_pointers(&evicted).await?;
+        self.evict_queries(&evicted).await?;
+
+        for cache_key in &key_refs {
+            self.metrics.record_delete(cache_key, timer.elapsed());
+        }
+        self.metrics.record_tag_invalidation(tag, key_refs.len());
+        Ok(key_refs.len())
+    }
+
+    /// Invalidate every cached entity of type `T` by bumping its generation.
+    ///
+    /// Requires `with_generations()`. Takes one backend `incr` regardless of
+    /// how many entries are cached. Returns the new generation.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // After a bulk price import
+    /// expander.invalidate_all::<Product>().await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `Error::ConfigError`: Generation counters are not enabled
+    /// - `Error::BackendError`: Incrementing the generation fails
+    pub async fn invalidate_all<T: CacheEntity>(&self) -> Result<u64> {
+        self.bump_generation(T::cache_prefix()).await
+    }
+
+    /// Invalidate every cached entity in `namespace` by bumping its generation.
+    ///
+    /// Covers entities of all types whose `CacheEntity::cache_namespace`
+    /// returns `namespace`. Requires `with_generations()`. Returns the new
+    /// generation.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::ConfigError`: Generation counters are not enabled
+    /// - `Error::BackendError`: Incrementing the generation fails
+    pub async fn invalidate_namespace(&self, namespace: &str) -> Result<u64> {
+        self.bump_generation(namespace).await
+    }
+
+    /// Increment a namespace's generation counter.
+    async fn bump_generation(&self, namespace: &str) -> Result<u64> {
+        let Some(generations) = &self.generations else {
+            return Err(Error::ConfigError(
+                "generation counters are not enabled, see CacheExpander::with_generations"
+                    .to_string(),
+            ));
+        };
+
+        let generation = self
+            .backend
+            .incr(&CacheKeyBuilder::build_generation(namespace))
+            .await?;
+        generations.remember(namespace, generation);
+
+        self.metrics.record_generation_bump(namespace, generation);
+        Ok(generation)
+    }
+
+    /// Retry loop shared by `with_config` and `with_background_refresh`.
+    async fn with_config_inner<T, F, R>(
+        &self,
+        feeder: &mut F,
+        repository: &R,
+        strategy: CacheStrategy,
+        config: OperationConfig,
+        refresh: Option<&BackgroundRefresh<'_, T::Key>>,
+    ) -> Result<()>
+    where
+        T: CacheEntity,
+        F: CacheFeed<T>,
+        R: DataRepository<T>,
+    {
+        // Validate feeder and resolve the entity ID once, not on every attempt
+        feeder.validate()?;
+        let entity_id = feeder.entity_id();
+        self.record_access::<T>(std::slice::from_ref(&entity_id));
+
+        let operation = async {
+            let mut backoff = config.retry.backoff();
+            loop {
+                let result = self
+                    .execute_operation::<T, F, R>(
+                        feeder,
+                        &entity_id,
+                        repository,
+                        strategy.clone(),
+                        &config,
+                        refresh,
+                    )
+                    .await;
+
+                let Err(e) = result else {
+                    return Ok(());
+                };
+                if config.retry.scope != RetryScope::Operation || !backoff.retry(&e).await {
+                    return Err(e);
+                }
+            }
+        };
+
+        with_deadline(TimeoutPhase::Operation, config.timeout, operation).await
+    }
+
+    /// Internal method to execute a single cache operation (without retry).
+    async fn execute_operation<T, F, R>(
+        &self,
+        feeder: &mut F,
+        id: &T::Key,
+        repository: &R,
+        strategy: CacheStrategy,
+        config: &OperationConfig,
+        refresh: Option<&BackgroundRefresh<'_, T::Key>>,
+    ) -> Result<()>
+    where
+        T: CacheEntity,
+        F: CacheFeed<T>,
+        R: DataRepository<T>,
+    {
+        let timer = Instant::now();
+        let cache_key = self.cache_key::<T>(id, config).await?;
+        let cache_key = cache_key.as_str();
+
+        debug!(
+            "» Cache operation for key: {} (strategy: {})",
+            cache_key, strategy
+        );
+
+        // Step 1: Execute strategy
+        let result = match strategy {
+            CacheStrategy::Fresh => {
+                self.strategy_fresh::<T, R>(cache_key, repository, config)
+                    .await
+            }
+            CacheStrategy::Refresh => {
+                self.strategy_refresh::<T, R>(cache_key, id, repository, config)
+                    .await
+            }
+            CacheStrategy::Invalidate => {
+                self.strategy_invalidate::<T, R>(cache_key, id, repository, config)
+                    .await
+            }
+            CacheStrategy::Bypass => {
+                self.strategy_bypass::<T, R>(cache_key, id, repository, config)
+                    .await
+            }
+            CacheStrategy::StaleWhileRevalidate { stale_after } => {
+                self.strategy_stale_while_revalidate::<T, R>(
+                    cache_key,
+                    id,
+                    repository,
+                    config,
+                    stale_after,
+                    refresh,
+                )
+                .await
+            }
+        };
+
+        // Step 2: Handle result
+        match result {
+            Ok(Some(entity)) => {
+                entity.validate()?;
+                feeder.on_hit(cache_key)?;
+                feeder.on_loaded(&entity)?;
+                feeder.feed(Some(entity));
+                self.metrics.record_hit(cache_key, timer.elapsed());
+                info!("✓ Cache operation succeeded in {:?}", timer.elapsed());
+            }
+            Ok(None) => {
+                feeder.on_miss(cache_key)?;
+                feeder.feed(None);
+                self.metrics.record_miss(cache_key, timer.elapsed());
+                debug!("Entity not found after cache operation for {}", cache_key);
+            }
+            Err(e) => {
+                self.metrics.record_error(cache_key, &e.to_string());
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fresh strategy: Cache only, no database fallback.
+    async fn strategy_fresh<T: CacheEntity, R: DataRepository<T>>(
+        &self,
+        cache_key: &str,
+        _repository: &R,
+        config: &OperationConfig,
+    ) -> Result<Option<T>> {
+        debug!("Executing Fresh strategy for {}", cache_key);
+
+        match self.read_cache(cache_key, config).await? {
+            Some(bytes) if self.is_negative_hit(cache_key, &bytes) => Ok(None),
+            Some(bytes) => match Self::decode_hit::<T>(&bytes) {
+                Ok((entity, _)) => {
+                    debug!("✓ Cache hit (Fresh strategy)");
+                    Ok(Some(entity))
+                }
+                Err(e) => {
+                    self.heal(cache_key, config, e).await?;
+                    Ok(None)
+                }
+            },
+            None => {
+                debug!("✗ Cache miss (Fresh strategy) - no fallback");
+                Ok(None)
+            }
+        }
+    }
+
+    /// Refresh strategy: Try cache, fallback to database on miss.
+    async fn strategy_refresh<T: CacheEntity, R: DataRepository<T>>(
+        &self,
+        cache_key: &str,
+        id: &T::Key,
+        repository: &R,
+        config: &OperationConfig,
+    ) -> Result<Option<T>> {
+        debug!("Executing Refresh strategy for {}", cache_key);
+
+        // Try cache first
+        if let Some(bytes) = self.read_cache(cache_key, config).await? {
+            if self.is_negative_hit(cache_key, &bytes) {
+                return Ok(None);
+            }
+
+            match Self::decode_hit::<T>(&bytes) {
+                Ok((entity, metadata)) => {
+                    let refresh_early = self.early_expiration.is_some_and(|early_expiration| {
+                        metadata.is_some_and(|m| early_expiration.should_refresh(&m))
+                    });
+                    if !refresh_early {
+                        debug!("✓ Cache hit (Refresh strategy)");
+                        return Ok(Some(entity));
+                    }
+
+                    debug!(
+                        "Refreshing {} ahead of expiry (early expiration)",
+                        cache_key
+                    );
+                }
+                Err(e) => self.heal(cache_key, config, e).await?,
+            }
+        } else {
+            debug!("Cache miss, falling back to database");
+        }
+
+        // Cache miss - fetch from database
+        self.load_from_repository::<T, R>(cache_key, id, repository, config, false)
+            .await
+    }
+
+    /// Invalidate strategy: Clear cache and refresh from database.
+    async fn strategy_invalidate<T: CacheEntity, R: DataRepository<T>>(
+        &self,
+        cache_key: &str,
+        id: &T::Key,
+        repository: &R,
+        config: &OperationConfig,
+    ) -> Result<Option<T>> {
+        debug!("Executing Invalidate strategy for {}", cache_key);
+
+        // Delete from cache
+        match self
+            .backend_call(config, || self.backend.delete(cache_key))
+            .await
+        {
+            Ok(()) => {
+                debug!("✓ Cache invalidated for {}", cache_key);
+                if let Err(e) = self.evict_related(cache_key).await {
+                    self.tolerate(cache_key, self.failure_mode_for(config), e)?;
+                }
+            }
+            Err(e) => self.tolerate(cache_key, self.failure_mode_for(config), e)?,
+        }
+
+        // Fetch fresh from database and re-populate cache, without joining a
+        // load that started before the delete
+        self.load_from_repository::<T, R>(cache_key, id, repository, config, true)
+            .await
+    }
+
+    /// Bypass strategy: Skip cache, always hit database.
+    async fn strategy_bypass<T: CacheEntity, R: DataRepository<T>>(
+        &self,
+        cache_key: &str,
+        id: &T::Key,
+        repository: &R,
+        config: &OperationConfig,
+    ) -> Result<Option<T>> {
+        debug!("Executing Bypass strategy for {}", cache_key);
+        debug!("Bypassing cache entirely for {}", cache_key);
+
+        // Fetch from database without checking cache
+        match self.fetch_timed::<T, R>(id, repository, config).await? {
+            Some((entity, compute)) => {
+                // Still populate cache for others
+                self.populate_entity(cache_key, &entity, compute, config)
+                    .await?;
+                Ok(Some(entity))
+            }
+            None => {
+                self.store_tombstone::<T>(cache_key, config).await?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Stale-while-revalidate strategy: Serve cached data, refresh it once stale.
+    async fn strategy_stale_while_revalidate<T: CacheEntity, R: DataRepository<T>>(
+        &self,
+        cache_key: &str,
+        id: &T::Key,
+        repository: &R,
+        config: &OperationConfig,
+        stale_after: Duration,
+        refresh: Option<&BackgroundRefresh<'_, T::Key>>,
+    ) -> Result<Option<T>> {
+        debug!("Executing StaleWhileRevalidate strategy for {}", cache_key);
+
+        let Some(spawn_refresh) = refresh else {
+            return Err(Error::ConfigError(
+                "StaleWhileRevalidate needs a background refresher, see CacheExpander::with_background_refresh"
+                    .to_string(),
+            ));
+        };
+
+        let Some(bytes) = self.read_cache(cache_key, config).await? else {
+            debug!("Cache miss, falling back to database");
+            return self
+                .load_from_repository::<T, R>(cache_key, id, repository, config, false)
+                .await;
+        };
+
+        if self.is_negative_hit(cache_key, &bytes) {
+            return Ok(None);
+        }
+
+        let (entity, metadata) = match Self::decode_hit::<T>(&bytes) {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                self.heal(cache_key, config, e).await?;
+                return self
+                    .load_from_repository::<T, R>(cache_key, id, repository, config, false)
+                    .await;
+            }
+        };
+        if metadata.is_some_and(|m| m.age() < stale_after) {
+            debug!("✓ Cache hit (StaleWhileRevalidate strategy, fresh)");
+            return Ok(Some(entity));
+        }
+
+        debug!(
+            "✓ Stale cache hit for {}, refreshing in background",
+            cache_key
+        );
+        spawn_refresh(cache_key.to_string(), id.clone());
+        Ok(Some(entity))
+    }
+
+    /// Spawn a background refresh of one entry, unless one is already running.
+    ///
+    /// The entry is reloaded with `load_from_repository()` under `config`, as
+    /// a foreground miss would be.
+    fn spawn_refresh<T, R>(
+        self: &Arc<Self>,
+        cache_key: String,
+        id: T::Key,
+        repository: R,
+        config: OperationConfig,
+    ) where
+        B: 'static,
+        T: CacheEntity + 'static,
+        R: DataRepository<T> + 'static,
+    {
+        if !self
+            .refreshing
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(cache_key.clone())
+        {
+            debug!("Background refresh already running for {}", cache_key);
+            return;
+        }
+
+        let expander = Arc::clone(self);
+        let guard = RefreshGuard {
+            key: cache_key,
+            refreshing: Arc::clone(&self.refreshing),
+        };
+
+        tokio::spawn(async move {
+            let cache_key = &guard.key;
+            match expander
+                .load_from_repository::<T, R>(cache_key, &id, &repository, &config, false)
+                .await
+            {
+                Ok(Some(_)) => debug!("✓ Background refresh stored {}", cache_key),
+                Ok(None) => {
+                    // load_from_repository has already replaced the stale entry
+                    // if negative caching is on
+                    debug!("Entity for {} no longer exists", cache_key);
+                    if expander.negative_ttl_policy.is_none() {
+                        let _ = expander.backend.delete(cache_key).await;
+                    }
+                }
+                Err(e) => warn!("Background refresh of {} failed: {}", cache_key, e),
+            }
+        });
+    }
+
+    /// Serialize an entity for storage, with write metadata.
+    fn encode<T: CacheEntity>(
+        entity: &T,
+        ttl: Option<Duration>,
+        compute: Duration,
+    ) -> Result<Vec<u8>> {
+        let metadata = EntryMetadata::now()
+            .with_ttl(ttl)
+            .with_compute_time(compute);
+        serialization::serialize_with_metadata(entity, &metadata)
+    }
+
+    /// Fetch an entity from the repository, measuring how long the load took.
+    async fn fetch_timed<T: CacheEntity, R: DataRepository<T>>(
+        &self,
+        id: &T::Key,
+        repository: &R,
+        config: &OperationConfig,
+    ) -> Result<Option<(T, Duration)>> {
+        let started = Instant::now();
+        let entity = config
+            .retry
+            .run_in(RetryScope::Repository, || {
+                let fetch = repository.fetch_by_id(id);
+                with_deadline(TimeoutPhase::Repository, config.repository_timeout, fetch)
+            })
+            .await?;
+        Ok(entity.map(|entity| (entity, started.elapsed())))
+    }
+
+    /// Load an entity from the repository on a cache miss and store it.
+    ///
+    /// Goes through the single-flight group when request coalescing is enabled,
+    /// so concurrent misses on the same key share one fetch and one write. With
+    /// `fresh`, a load already in flight is not joined, since it may have read
+    /// the entity before it was invalidated; the new load replaces it.
+    async fn load_from_repository<T: CacheEntity, R: DataRepository<T>>(
+        &self,
+        cache_key: &str,
+        id: &T::Key,
+        repository: &R,
+        config: &OperationConfig,
+        fresh: bool,
+    ) -> Result<Option<T>> {
+        let Some(single_flight) = &self.single_flight else {
+            return match self.fetch_timed::<T, R>(id, repository, config).await? {
+                Some((entity, compute)) => {
+                    // Store in cache for future use
+                    self.populate_entity(cache_key, &entity, compute, config)
+                        .await?;
+                    Ok(Some(entity))
+                }
+                None => {
+                    self.store_tombstone::<T>(cache_key, config).await?;
+                    Ok(None)
+                }
+            };
+        };
+
+        let mut loaded = None;
+        let slot = &mut loaded;
+        let load = move || async move {
+            match self.fetch_timed::<T, R>(id, repository, config).await? {
+                Some((entity, compute)) => {
+                    let bytes = self
+                        .populate_entity(cache_key, &entity, compute, config)
+                        .await?;
+                    *slot = Some(entity);
+                    Ok(Some(bytes))
+                }
+                None => {
+                    self.store_tombstone::<T>(cache_key, config).await?;
+                    Ok(None)
+                }
+            }
+        };
+        let flight = if fresh {
+            single_flight.run_fresh(cache_key, load).await
+        } else {
+            single_flight.run(cache_key, load).await
+        };
+
+        if flight.coalesced {
+            debug!("Coalesced cache miss for {} onto in-flight load", cache_key);
+            self.metrics.record_coalesced(cache_key);
+        }
+
+        match (loaded, flight.value?) {
+            (Some(entity), _) => Ok(Some(entity)),
+            (None, Some(bytes)) => T::deserialize_from_cache(&bytes).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Run a backend call under the operation's backend deadline and retry policy.
+    async fn backend_call<V, Fut>(
+        &self,
+        config: &OperationConfig,
+        call: impl Fn() -> Fut,
+    ) -> Result<V>
+    where
+        V: Send,
+        Fut: Future<Output = Result<V>> + Send,
+    {
+        config
+            .retry
+            .run_in(RetryScope::Backend, || {
+                with_deadline(TimeoutPhase::Backend, config.backend_timeout, call())
+            })
+            .await
+    }
+
+    /// Write loaded bytes to the cache; failures never fail the operation.
+    async fn populate(
+        &self,
+        cache_key: &str,
+        bytes: Vec<u8>,
+        ttl: Option<Duration>,
+        config: &OperationConfig,
+    ) {
+        let write = self.backend_call(config, || self.backend.set(cache_key, bytes.clone(), ttl));
+        if let Err(e) = write.await {
+            debug!("Failed to populate cache for {}: {}", cache_key, e);
+        }
+    }
+
+    /// Serialize a loaded entity and populate the cache with it and its tags.
+    ///
+    /// Returns the serialized entry. Only serialization errors are returned;
+    /// cache write failures are ignored as in `populate()`.
+    async fn populate_entity<T: CacheEntity>(
+        &self,
+        cache_key: &str,
+        entity: &T,
+        compute: Duration,
+        config: &OperationConfig,
+    ) -> Result<Vec<u8>> {
+        let ttl = self.entity_ttl(entity, config);
+        let bytes = Self::encode(entity, ttl, compute)?;
+        self.populate(cache_key, bytes.clone(), ttl, config).await;
+        self.index_entries(&[(cache_key, entity)], ttl).await;
+        Ok(bytes)
+    }
+
+    /// Entities of a cached query result, or `None` if a member has left the entity cache.
+    async fn resolve_query_entry<T: CacheEntity>(
+        &self,
+        entry: QueryEntry<Vec<T>>,
+        config: &OperationConfig,
+    ) -> Result<Option<Vec<T>>> {
+        let member_keys = match entry {
+            QueryEntry::Page(entities) => {
+                for entity in &entities {
+                    entity.validate()?;
+                }
+                return Ok(Some(entities));
+            }
+            QueryEntry::Ids(member_keys) => member_keys,
+        };
+        if member_keys.is_empty() {
+            return Ok(Some(Vec::new()));
+        }
+
+        let key_refs: Vec<&str> = member_keys.iter().map(String::as_str).collect();
+        let values = match self
+            .backend_call(config, || self.backend.mget(&key_refs))
+            .await
+        {
+            Ok(values) => values,
+            Err(e) => {
+                self.tolerate(key_refs[0], self.failure_mode_for(config), e)?;
+                return Ok(None);
+            }
+        };
+
+        let mut entities = Vec::with_capacity(values.len());
+        for value in values {
+            match value {
+                Some(bytes) if !serialization::is_tombstone(&bytes) => {
+                    entities.push(Self::decode_hit::<T>(&bytes)?.0)
+                }
+                _ => return Ok(None),
+            }
+        }
+        Ok(Some(entities))
+    }
+
+    /// Cache a loaded query result and record the entries it contains.
+    ///
+    /// Failures are logged, not returned: the result is already loaded, and
+    /// the next read loads it again.
+    async fn store_query<Q: CacheQuery>(
+        &self,
+        query: &Q,
+        query_key: &str,
+        entities: &[Q::Entity],
+        compute: Duration,
+        config: &OperationConfig,
+    ) {
+        let ids: Vec<_> = entities.iter().map(CacheEntity::cache_key).collect();
+        let member_keys = match self.cache_keys::<Q::Entity>(&ids, config).await {
+            Ok(member_keys) => member_keys,
+            Err(e) => {
+                debug!("Failed to build member keys of {}: {}", query_key, e);
+                return;
+            }
+        };
+        let key_refs: Vec<&str> = member_keys.iter().map(String::as_str).collect();
+
+        let encoded = match Q::storage() {
+            QueryStorage::Ids => {
+                if let Err(e) = self
+                    .store_members(&key_refs, entities, compute, config)
+                    .await
+                {
+                    debug!("Failed to cache members of {}: {}", query_key, e);
+                    return;
+                }
+                serialization::serialize_for_cache(&QueryEntry::<&[Q::Entity]>::Ids(
+                    member_keys.clone(),
+                ))
+            }
+            QueryStorage::Pages => serialization::serialize_for_cache(&QueryEntry::Page(entities)),
+        };
+        let bytes = match encoded {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                debug!("Failed to serialize {}: {}", query_key, e);
+                return;
+            }
+        };
+        let ttl = config
+            .ttl_override
+            .or_else(|| self.prefix_ttl(Q::query_prefix(), &self.ttl_policy));
+        self.populate(query_key, bytes, ttl, config).await;
+
+        let mut sets: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for member_key in &key_refs {
+            sets.entry(CacheKeyBuilder::build_queries(member_key))
+                .or_default()
+                .push(query_key.to_string());
+        }
+        for tag in query.query_tags() {
+            sets.entry(CacheKeyBuilder::build_tag(&tag))
+                .or_default()
+                .push(query_key.to_string());
+        }
+        self.add_members(&sets, ttl).await;
+    }
+
+    /// Write the members of a query result to the entity cache with one `mset`.
+    async fn store_members<T: CacheEntity>(
+        &self,
+        member_keys: &[&str],
+        entities: &[T],
+        compute: Duration,
+        config: &OperationConfig,
+    ) -> Result<()> {
+        if entities.is_empty() {
+            return Ok(());
+        }
+
+        let mut by_ttl: BTreeMap<Option<Duration>, Vec<(&str, Vec<u8>)>> = BTreeMap::new();
+        for (member_key, entity) in member_keys.iter().zip(entities) {
+            let ttl = self.entity_ttl(entity, config);
+            by_ttl
+                .entry(ttl)
+                .or_default()
+                .push((*member_key, Self::encode(entity, ttl, compute)?));
+        }
+        let ttl = longest_ttl(by_ttl.keys().copied());
+        for (ttl, items) in by_ttl {
+            self.backend_call(config, || self.backend.mset(&items, ttl))
+                .await?;
+        }
+
+        let indexed: Vec<(&str, &T)> = member_keys.iter().copied().zip(entities).collect();
+        self.index_entries(&indexed, ttl).await;
+        Ok(())
+    }
+
+    /// Record the tags, pointers and dependencies of entities just written to the cache.
+    ///
+    /// `ttl` is the longest TTL of the entries: secondary-key pointers are
+    /// written with it and every membership set lives at least as long, so
+    /// neither outlives the entries. Memberships are added with one `msadd`
+    /// and pointers written with one `mset`. Failures are logged, not
+    /// returned: the entries are already cached, and a missing membership only
+    /// means a later invalidation won't evict them before they expire.
+    async fn index_entries<T: CacheEntity>(&self, entries: &[(&str, &T)], ttl: Option<Duration>) {
+        let mut sets: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (cache_key, entity) in entries {
+            for tag in entity.cache_tags() {
+                sets.entry(CacheKeyBuilder::build_tag(&tag))
+                    .or_default()
+                    .push(cache_key.to_string());
+            }
+        }
+        let pointers = Self::pointer_members(entries, &mut sets);
+        if self.cascade_depth.is_some() {
+            self.dependency_members(entries, &mut sets).await;
+        }
+        self.add_members(&sets, ttl).await;
+
+        if pointers.is_empty() {
+            return;
+        }
+        let items: Vec<(&str, Vec<u8>)> = pointers
+            .iter()
+            .map(|(pointer_key, target)| (pointer_key.as_str(), target.as_bytes().to_vec()))
+            .collect();
+        if let Err(e) = self.backend.mset(&items, ttl).await {
+            warn!(
+                "Failed to write {} secondary-key pointers: {}",
+                items.len(),
+                e
+            );
+        }
+    }
+
+    /// Add members to tag, dependency, query and pointer sets with one `msadd`.
+    ///
+    /// Each set is kept for at least `ttl`, the longest TTL of the entries its
+    /// new members name, so sets lapse with their entries instead of growing
+    /// without bound. Failures are logged, as in `index_entries()`.
+    async fn add_members(&self, sets: &BTreeMap<String, Vec<String>>, ttl: Option<Duration>) {
+        if sets.is_empty() {
+            return;
+        }
+
+        let items: Vec<(&str, Vec<&str>)> = sets
+            .iter()
+            .map(|(key, members)| (key.as_str(), members.iter().map(String::as_str).collect()))
+            .collect();
+        if let Err(e) = self.backend.msadd(&items, ttl).await {
+            warn!("Failed to record members of {} sets: {}", items.len(), e);
+        }
+    }
+
+    /// Collect the secondary-key pointers of entities just written to the cache.
+    ///
+    /// Adds each entry's pointer keys to its pointers set in `sets`, so they
+    /// can be deleted when the entry is evicted, and returns the
+    /// `(pointer key, target key)` pairs to write.
+    fn pointer_members<T: CacheEntity>(
+        entries: &[(&str, &T)],
+        sets: &mut BTreeMap<String, Vec<String>>,
+    ) -> Vec<(String, String)> {
+        let mut pointers = Vec::new();
+        for (cache_key, entity) in entries {
+            let pointer_keys: Vec<String> = entity
+                .cache_secondary_keys()
+                .iter()
+                .map(SecondaryKey::pointer_key)
+                .collect();
+            if pointer_keys.is_empty() {
+                continue;
+            }
+
+            sets.entry(CacheKeyBuilder::build_pointers(cache_key))
+                .or_default()
+                .extend(pointer_keys.iter().cloned());
+            pointers.extend(
+                pointer_keys
+                    .into_iter()
+                    .map(|pointer_key| (pointer_key, cache_key.to_string())),
+            );
+        }
+        pointers
+    }
+
+    /// Collect the dependency edges of entities just written to the cache.
+    ///
+    /// Adds each entry's key to the dependents set, in `sets`, of every entry
+    /// it depends on.
+    async fn dependency_members<T: CacheEntity>(
+        &self,
+        entries: &[(&str, &T)],
+        sets: &mut BTreeMap<String, Vec<String>>,
+    ) {
+        let mut dependencies = Vec::new();
+        let mut dependents = Vec::new();
+        for (cache_key, entity) in entries {
+            for dependency in entity.cache_dependencies() {
+                dependencies.push(dependency);
+                dependents.push(*cache_key);
+            }
+        }
+        if dependencies.is_empty() {
+            return;
+        }
+
+        let dependency_keys = self.resolve_keys_detached(&dependencies).await;
+        for (dependency_key, dependent) in dependency_keys.into_iter().zip(dependents) {
+            sets.entry(CacheKeyBuilder::build_dependents(&dependency_key))
+                .or_default()
+                .push(dependent.to_string());
+        }
+    }
+
+    /// Evict the entries and query results that depend on `cache_key`.
+    ///
+    /// Returns the number of dependent entries evicted, not counting query
+    /// results.
+    pub(crate) async fn evict_dependents(&self, cache_key: &str) -> Result<usize> {
+        let mut evicted = self.cascade_dependents(cache_key).await?;
+        let dependents = evicted.len();
+        self.evict_pointers(&evicted).await?;
+        evicted.push(cache_key.to_string());
+        self.evict_queries(&evicted).await?;
+        Ok(dependents)
+    }
+
+    /// Evict the secondary-key pointers to an evicted entry, and what depends on it.
+    ///
+    /// Returns the number of dependent entries evicted, as `evict_dependents()`.
+    async fn evict_related(&self, cache_key: &str) -> Result<usize> {
+        self.evict_pointers(&[cache_key.to_string()]).await?;
+        self.evict_dependents(cache_key).await
+    }
+
+    /// Delete the secondary-key pointers to each of `cache_keys`.
+    ///
+    /// Reads every pointers set with one `msmembers` and deletes the pointers
+    /// and their sets with one `mdelete`.
+    async fn evict_pointers(&self, cache_keys: &[String]) -> Result<()> {
+        if cache_keys.is_empty() {
+            return Ok(());
+        }
+
+        let pointers_keys: Vec<String> = cache_keys
+            .iter()
+            .map(|cache_key| CacheKeyBuilder::build_pointers(cache_key))
+            .collect();
+        let key_refs: Vec<&str> = pointers_keys.iter().map(String::as_str).collect();
+        let members = self.backend.msmembers(&key_refs).await?;
+
+        let mut keys = Vec::new();
+        for (pointers_key, pointer_keys) in key_refs.iter().zip(members) {
+            if !pointer_keys.is_empty() {
+                keys.extend(pointer_keys);
+                keys.push(pointers_key.to_string());
+            }
+        }
+        if keys.is_empty() {
+            return Ok(());
+        }
+
+        let key_refs: Vec<&str> = keys.iter().map(String::as_str).collect();
+        self.backend.mdelete(&key_refs).await?;
+        debug!(
+            "✓ Evicted pointers to {} entries ({} keys)",
+            cache_keys.len(),
+            key_refs.len()
+        );
+        Ok(())
+    }
+
+    /// Evict the query results containing any of `cache_keys`.
+    ///
+    /// Reads every query-membership set with one `msmembers`, deletes the
+    /// query results with one `mdelete`, and removes them from the sets with
+    /// one `msrem`.
+    async fn evict_queries(&self, cache_keys: &[String]) -> Result<()> {
+        if cache_keys.is_empty() {
+            return Ok(());
+        }
+
+        let timer = Instant::now();
+        let queries_keys: Vec<String> = cache_keys
+            .iter()
+            .map(|cache_key| CacheKeyBuilder::build_queries(cache_key))
+            .collect();
+        let key_refs: Vec<&str> = queries_keys.iter().map(String::as_str).collect();
+        let members = self.backend.msmembers(&key_refs).await?;
+
+        let mut query_keys: BTreeSet<&str> = BTreeSet::new();
+        let mut edges: Vec<(&str, Vec<&str>)> = Vec::new();
+        for (queries_key, members) in key_refs.iter().zip(&members) {
+            if members.is_empty() {
+                continue;
+            }
+            let members: Vec<&str> = members.iter().map(String::as_str).collect();
+            query_keys.extend(&members);
+            edges.push((queries_key, members));
+        }
+        if query_keys.is_empty() {
+            return Ok(());
+        }
+
+        let query_keys: Vec<&str> = query_keys.into_iter().collect();
+        self.backend.mdelete(&query_keys).await?;
+        self.backend.msrem(&edges).await?;
+        for query_key in &query_keys {
+            self.metrics.record_delete(query_key, timer.elapsed());
+        }
+        debug!(
+            "✓ Evicted {} query results containing {} entries",
+            query_keys.len(),
+            edges.len()
+        );
+        Ok(())
+    }
+
+    /// Evict the entries that depend on `cache_key`, transitively.
+    ///
+    /// Follows dependency edges breadth-first, with one `msmembers`, `mdelete`
+    /// and `msrem` per level, up to the cascade depth. Keys already evicted
+    /// are not followed again, so cycles end. The edges followed are removed; dependents record them
+    /// again when they are rewritten. Returns the keys evicted.
+    async fn cascade_dependents(&self, cache_key: &str) -> Result<Vec<String>> {
+        let Some(max_depth) = self.cascade_depth else {
+            return Ok(Vec::new());
+        };
+
+        let timer = Instant::now();
+        let mut visited = HashSet::from([cache_key.to_string()]);
+        let mut frontier = vec![cache_key.to_string()];
+        let mut evicted = Vec::new();
+        for depth in 1.. {
+            let dependents_keys: Vec<String> = frontier
+                .iter()
+                .map(|key| CacheKeyBuilder::build_dependents(key))
+                .collect();
+            let key_refs: Vec<&str> = dependents_keys.iter().map(String::as_str).collect();
+            let members = self.backend.msmembers(&key_refs).await?;
+
+            let mut edges = Vec::new();
+            let mut next = Vec::new();
+            for (dependents_key, dependents) in dependents_keys.into_iter().zip(members) {
+                if dependents.is_empty() {
+                    continue;
+                }
+                next.extend(
+                    dependents
+                        .iter()
+                        .filter(|dependent| visited.insert((*dependent).clone()))
+                        .cloned(),
+                );
+                edges.push((dependents_key, dependents));
+            }
+            if next.is_empty() {
+                break;
+            }
+            if depth > max_depth {
+                warn!(
+                    "Cascade from {} stopped at depth {}, {} dependents left cached",
+                    cache_key,
+                    max_depth,
+                    next.len()
+                );
+                break;
+            }
+
+            let key_refs: Vec<&str> = next.iter().map(String::as_str).collect();
+            self.backend.mdelete(&key_refs).await?;
+            let edges: Vec<(&str, Vec<&str>)> = edges
+                .iter()
+                .map(|(dependents_key, dependents)| {
+                    (
+                        dependents_key.as_str(),
+                        dependents.iter().map(String::as_str).collect(),
+                    )
+                })
+                .collect();
+            self.backend.msrem(&edges).await?;
+            for key in &key_refs {
+                self.metrics.record_delete(key, timer.elapsed());
+            }
+
+            evicted.extend_from_slice(&next);
+            frontier = next;
+        }
+
+        if !evicted.is_empty() {
+            self.metrics.record_cascade(cache_key, evicted.len());
+        }
+        Ok(evicted)
+    }
+
+    /// Evict the dependents of an entry after a repository write.
+    ///
+    /// If the entry itself was `evicted`, its secondary-key pointers are too.
+    async fn cascade(&self, cache_key: &str, evicted: bool) -> Result<()> {
+        let result = match evicted {
+            true => self.evict_related(cache_key).await,
+            false => self.evict_dependents(cache_key).await,
+        };
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                warn!(
+                    "Failed to evict dependents of {} after repository write: {}",
+                    cache_key, e
+                );
+                self.metrics.record_error(cache_key, &e.to_string());
+                self.tolerate(
+                    cache_key,
+                    self.failure_mode,
+                    Self::cache_write_error(cache_key, e),
+                )
+            }
+        }
+    }
+
+    /// TTL for a negative-cache tombstone, or `None` if negative caching is off.
+    fn tombstone_ttl<T: CacheEntity>(&self) -> Option<Option<Duration>> {
+        self.negative_ttl_policy
+            .as_ref()
+            .map(|policy| self.policy_ttl::<T>(policy))
+    }
+
+    /// Store a tombstone for an entity the repository doesn't have.
+    ///
+    /// Does nothing unless negative caching is enabled.
+    async fn store_tombstone<T: CacheEntity>(
+        &self,
+        cache_key: &str,
+        config: &OperationConfig,
+    ) -> Result<()> {
+        let Some(ttl) = self.tombstone_ttl::<T>() else {
+            return Ok(());
+        };
+
+        let bytes = serialization::serialize_tombstone(T::cache_prefix())?;
+        self.populate(cache_key, bytes, ttl, config).await;
+        debug!("✓ Stored negative cache entry for {}", cache_key);
+        Ok(())
+    }
+
+    /// Decode a cache hit and validate the entity.
+    fn decode_hit<T: CacheEntity>(bytes: &[u8]) -> Result<(T, Option<EntryMetadata>)> {
+        let (entity, metadata) = serialization::deserialize_with_metadata::<T>(bytes)?;
+        entity.validate()?;
+        Ok((entity, metadata))
+    }
+
+    /// Evict an unusable cache hit so the caller can treat it as a miss.
+    ///
+    /// Returns `error` unchanged unless self-healing is enabled and the error
+    /// means the entry itself is bad.
+    async fn heal(&self, cache_key: &str, config: &OperationConfig, error: Error) -> Result<()> {
+        let healable = matches!(
+            error,
+            Error::InvalidCacheEntry(_)
+                | Error::VersionMismatch { .. }
+                | Error::DeserializationError(_)
+                | Error::ValidationError(_)
+        );
+        if !self.self_healing || !healable {
+            return Err(error);
+        }
+
+        warn!("Healing cache entry {}: {}", cache_key, error);
+        self.metrics.record_heal(cache_key, &error.to_string());
+        if let Err(e) = self
+            .backend_call(config, || self.backend.delete(cache_key))
+            .await
+        {
+            debug!("Failed to evict unreadable entry {}: {}", cache_key, e);
+        }
+        Ok(())
+    }
+
+    /// Check cached bytes for a tombstone, recording a negative hit if found.
+    fn is_negative_hit(&self, cache_key: &str, bytes: &[u8]) -> bool {
+        if !serialization::is_tombstone(bytes) {
+            return false;
+        }
+
+        debug!("✓ Negative cache hit for {}", cache_key);
+        self.metrics.record_negative_hit(cache_key);
+        true
+    }
+
+    /// Write an entity to the cache with its TTL, outside a read path.
+    ///
+    /// Records the write through `CacheMetrics::record_set`, or the failure
+    /// through `record_error`.
+    pub(crate) async fn store_entity<T: CacheEntity>(
+        &self,
+        cache_key: &str,
+        entity: &T,
+        compute: Duration,
+    ) -> Result<()> {
+        let timer = Instant::now();
+        let ttl = self.entity_ttl(entity, &OperationConfig::default());
+        let stored = match Self::encode(entity, ttl, compute) {
+            Ok(bytes) => self.backend.set(cache_key, bytes, ttl).await,
+            Err(e) => Err(e),
+        };
+
+        match &stored {
+            Ok(()) => {
+                self.metrics.record_set(cache_key, timer.elapsed());
+                self.index_entries(&[(cache_key, entity)], ttl).await;
+            }
+            Err(e) => self.metrics.record_error(cache_key, &e.to_string()),
+        }
+        stored
+    }
+
+    /// Read a cache entry, treating backend errors as misses in fail-open mode.
+    async fn read_cache(
+        &self,
+        cache_key: &str,
+        config: &OperationConfig,
+    ) -> Result<Option<Vec<u8>>> {
+        match self
+            .backend_call(config, || self.backend.get(cache_key))
+            .await
+        {
+            Ok(bytes) => Ok(bytes),
+            Err(e) => {
+                self.tolerate(cache_key, self.failure_mode_for(config), e)?;
+                debug!("Cache unavailable, treating {} as a miss", cache_key);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Failure mode for an operation: the config override, else the expander's.
+    fn failure_mode_for(&self, config: &OperationConfig) -> FailureMode {
+        config.failure_mode.unwrap_or(self.failure_mode)
+    }
+
+    /// Apply a failure mode to a backend error.
+    ///
+    /// Returns the error in `Closed` mode; logs and counts it in `Open` mode.
+    pub(crate) fn tolerate(&self, key: &str, mode: FailureMode, error: Error) -> Result<()> {
+        match mode {
+            FailureMode::Closed => Err(error),
+            FailureMode::Open => {
+                warn!("Cache backend failed for {}, failing open: {}", key, error);
+                self.metrics.record_fail_open(key, &error.to_string());
+                Ok(())
+            }
+        }
+    }
+
+    /// The expander's failure mode.
+    pub(crate) fn failure_mode(&self) -> FailureMode {
+        self.failure_mode
+    }
+
+    /// Metrics handler shared with components built on this expander.
+    pub(crate) fn metrics(&self) -> &dyn CacheMetrics {
+        self.metrics.as_ref()
+    }
+
+    /// Report every later read of a `T` entity to `recorder`, until it is dropped.
+    pub(crate) fn add_access_recorder<T: CacheEntity>(&self, recorder: Weak<dyn AccessRecorder>) {
+        self.access_recorders
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(T::cache_prefix())
+            .or_default()
+            .push(recorder);
+    }
+
+    /// Report reads of the `T` entities `ids` to the registered recorders.
+    fn record_access<T: CacheEntity>(&self, ids: &[T::Key]) {
+        let mut recorders = self
+            .access_recorders
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let Some(registered) = recorders.get_mut(T::cache_prefix()) else {
+            return;
+        };
+        registered.retain(|recorder| {
+            let Some(recorder) = recorder.upgrade() else {
+                return false;
+            };
+            for id in ids {
+                recorder.record_access(id);
+            }
+            true
+        });
+    }
+
+    /// Delete a cache entry after a repository write.
+    async fn evict(&self, cache_key: &str) -> Result<()> {
+        let timer = Instant::now();
+        match self.backend.delete(cache_key).await {
+            Ok(()) => {
+                self.metrics.record_delete(cache_key, timer.elapsed());
+                debug!("✓ Evicted {} after repository write", cache_key);
+                self.cascade(cache_key, true).await
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to evict {} after repository write: {}",
+                    cache_key, e
+                );
+                self.metrics.record_error(cache_key, &e.to_string());
+                self.tolerate(
+                    cache_key,
+                    self.failure_mode,
+                    Self::cache_write_error(cache_key, e),
+                )
+            }
+        }
+    }
+
+    /// Report a cache failure that followed a successful repository write.
+    fn cache_write_error(cache_key: &str, error: Error) -> Error {
+        Error::BackendError(format!(
+            "repository updated but cache update for {} failed: {}",
+            cache_key, error
+        ))
+    }
+
+    /// Cache key for `id`, including generations when they are enabled.
+    pub(crate) async fn cache_key<T: CacheEntity>(
+        &self,
+        id: &T::Key,
+        config: &OperationConfig,
+    ) -> Result<String> {
+        let mut keys = self
+            .cache_keys::<T>(std::slice::from_ref(id), config)
+            .await?;
+        Ok(keys.remove(0))
+    }
+
+    /// Cache keys for `ids`, reading all the generations they need at once.
+    async fn cache_keys<T: CacheEntity>(
+        &self,
+        ids: &[T::Key],
+        config: &OperationConfig,
+    ) -> Result<Vec<String>> {
+        if self.generations.is_none() {
+            return Ok(ids.iter().map(CacheKeyBuilder::build::<T>).collect());
+        }
+
+        let entries: Vec<EntryRef> = ids.iter().map(EntryRef::of::<T>).collect();
+        self.resolve_keys(&entries, config).await
+    }
+
+    /// Cache keys for entries of any type, including generations when they are enabled.
+    ///
+    /// Falls back to the last generations seen when reading them fails in
+    /// `FailureMode::Open`.
+    async fn resolve_keys(
+        &self,
+        entries: &[EntryRef],
+        config: &OperationConfig,
+    ) -> Result<Vec<String>> {
+        let Some(generations) = self.generations.as_deref().filter(|_| !entries.is_empty()) else {
+            return Ok(entries.iter().map(Generations::plain_key).collect());
+        };
+
+        let lookup = Generations::lookup(entries);
+        let values = self.read_generations(generations, &lookup, config).await?;
+        Ok(Generations::keys(entries, &lookup, values))
+    }
+
+    /// Read the current generation of each namespace with one `mget`.
+    ///
+    /// Falls back to the last generations seen when the read fails in
+    /// `FailureMode::Open`.
+    async fn read_generations(
+        &self,
+        generations: &Generations,
+        namespaces: &[String],
+        config: &OperationConfig,
+    ) -> Result<Vec<u64>> {
+        match self
+            .backend_call(config, || generations.read(&self.backend, namespaces))
+            .await
+        {
+            Ok(values) => Ok(values),
+            Err(e) => {
+                let key = CacheKeyBuilder::build_generation(&namespaces[0]);
+                self.tolerate(&key, self.failure_mode_for(config), e)?;
+                Ok(generations.last_known(namespaces))
+            }
+        }
+    }
+
+    /// Cache key of a query result, including its entity type's generation
+    /// when generations are enabled.
+    async fn query_key<Q: CacheQuery>(
+        &self,
+        query: &Q,
+        config: &OperationConfig,
+    ) -> Result<String> {
+        let generation = match &self.generations {
+            Some(generations) => {
+                let lookup = [Q::Entity::cache_prefix().to_string()];
+                Some(self.read_generations(generations, &lookup, config).await?[0])
+            }
+            None => None,
+        };
+        Ok(CacheKeyBuilder::build_query(
+            Q::query_prefix(),
+            &query.query_params(),
+            generation,
+        ))
+    }
+
+    /// Cache keys for entries outside an operation, e.g. while indexing a write.
+    ///
+    /// Generations are read without retries or timeouts; if that fails, the
+    /// last generations seen are used.
+    async fn resolve_keys_detached(&self, entries: &[EntryRef]) -> Vec<String> {
+        let Some(generations) = self.generations.as_deref().filter(|_| !entries.is_empty()) else {
+            return entries.iter().map(Generations::plain_key).collect();
+        };
+
+        let lookup = Generations::lookup(entries);
+        let values = match generations.read(&self.backend, &lookup).await {
+            Ok(values) => values,
+            Err(e) => {
+                warn!("Failed to read generations, using last known: {}", e);
+                generations.last_known(&lookup)
+            }
+        };
+        Generations::keys(entries, &lookup, values)
+    }
+
+    /// Resolve the TTL for writes of entity type `T`.
+    ///
+    /// Uses the config override if provided, then `T::cache_ttl()`, otherwise
+    /// the expander's TTL policy.
+    fn resolve_ttl<T: CacheEntity>(&self, config: &OperationConfig) -> Option<Duration> {
+        config
+            .ttl_override
+            .or_else(T::cache_ttl)
+            .or_else(|| self.policy_ttl::<T>(&self.ttl_policy))
+    }
+
+    /// Resolve the TTL for writing `entity`.
+    ///
+    /// Precedence: the config override, `entity.cache_instance_ttl()`,
+    /// `T::cache_ttl()`, then the expander's TTL policy.
+    fn entity_ttl<T: CacheEntity>(&self, entity: &T, config: &OperationConfig) -> Option<Duration> {
+        Self::instance_ttl(entity, config.ttl_override, || {
+            self.resolve_ttl::<T>(config)
+        })
+    }
+
+    /// TTL for `entity`, given `type_ttl` from `resolve_ttl`: the instance TTL
+    /// applies unless the TTL is overridden.
+    fn instance_ttl<T: CacheEntity>(
+        entity: &T,
+        ttl_override: Option<Duration>,
+        type_ttl: impl FnOnce() -> Option<Duration>,
+    ) -> Option<Duration> {
+        ttl_override
+            .or_else(|| entity.cache_instance_ttl())
+            .or_else(type_ttl)
+    }
+
+    /// TTL from `policy`, with any jitter drawn from the expander's RNG.
+    fn policy_ttl<T: CacheEntity>(&self, policy: &TtlPolicy) -> Option<Duration> {
+        self.prefix_ttl(T::cache_prefix(), policy)
+    }
+
+    /// TTL from `policy` for entries with `prefix`.
+    fn prefix_ttl(&self, prefix: &str, policy: &TtlPolicy) -> Option<Duration> {
+        let mut rng = self.ttl_rng.lock().unwrap_or_else(PoisonError::into_inner);
+        policy.get_ttl_with(prefix, &mut *rng)
+    }
+
+    /// Get backend reference (for advanced use).
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Get mutable backend reference (for advanced use).
+    pub fn backend_mut(&mut self) -> &mut B {
+        &mut self.backend
+    }
+}
+
+/// Generation counters an expander has seen, shared with background refreshes.
+#[derive(Default)]
+struct Generations {
+    known: Mutex<HashMap<String, u64>>,
+}
+
+impl Generations {
+    /// Read the current generation of each namespace with one `mget`.
+    async fn read<B: CacheBackend>(&self, backend: &B, namespaces: &[String]) -> Result<Vec<u64>> {
+        let keys: Vec<String> = namespaces
+            .iter()
+            .map(|namespace| CacheKeyBuilder::build_generation(namespace))
+            .collect();
+        let key_refs: Vec<&str> = keys.iter().map(String::as_str).collect();
+
+        let generations = backend
+            .mget(&key_refs)
+            .await?
+            .iter()
+            .map(|value| parse_counter(value.as_deref()))
+            .collect::<Result<Vec<u64>>>()?;
+
+        let mut known = self.known.lock().unwrap_or_else(PoisonError::into_inner);
+        for (namespace, generation) in namespaces.iter().zip(&generations) {
+            known.insert(namespace.clone(), *generation);
+        }
+        Ok(generations)
+    }
+
+    /// Remember a namespace's generation after bumping it.
+    fn remember(&self, namespace: &str, generation: u64) {
+        self.known
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(namespace.to_string(), generation);
+    }
+
+    /// Last generation seen for each namespace, or 0 for ones never seen.
+    fn last_known(&self, namespaces: &[String]) -> Vec<u64> {
+        let known = self.known.lock().unwrap_or_else(PoisonError::into_inner);
+        namespaces
+            .iter()
+            .map(|namespace| known.get(namespace).copied().unwrap_or(0))
+            .collect()
+    }
+
+    /// Namespaces whose generations the keys of `entries` need: type prefixes
+    /// and entity namespaces.
+    fn lookup(entries: &[EntryRef]) -> Vec<String> {
+        entries
+            .iter()
+            .flat_map(|entry| std::iter::once(entry.prefix()).chain(entry.namespace()))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Cache keys of `entries`, given the generations of the `lookup` namespaces.
+    fn keys(entries: &[EntryRef], lookup: &[String], values: Vec<u64>) -> Vec<String> {
+        let by_namespace: HashMap<&str, u64> =
+            lookup.iter().map(String::as_str).zip(values).collect();
+
+        entries
+            .iter()
+            .map(|entry| {
+                let namespace = entry
+                    .namespace()
+                    .map(|namespace| (namespace, by_namespace[namespace]));
+                CacheKeyBuilder::build_generational_with_prefix(
+                    entry.prefix(),
+                    &entry.id(),
+                    by_namespace[entry.prefix()],
+                    namespace,
+                )
+            })
+            .collect()
+    }
+
+    /// Cache key of an entry when generations are disabled.
+    fn plain_key(entry: &EntryRef) -> String {
+        CacheKeyBuilder::build_with_prefix(entry.prefix(), &entry.id())
+    }
+}
+
+/// The longest of `ttls`, where `None` (no expiry) is longest.
+fn longest_ttl(ttls: impl IntoIterator<Item = Option<Duration>>) -> Option<Duration> {
+    ttls.into_iter()
+        .try_fold(Duration::ZERO, |longest, ttl| Some(longest.max(ttl?)))
+}
+
+/// Marks a key as no longer being refreshed when the refresh task ends.
+struct RefreshGuard {
+    key: String,
+    refreshing: Arc<Mutex<HashSet<String>>>,
+}
+
+impl Drop for RefreshGuard {
+    fn drop(&mut self) {
+        self.refreshing
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::InMemoryBackend;
+    use crate::feed::GenericFeeder;
+    use crate::repository::InMemoryRepository;
+    use crate::retry::Jitter;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Clone, Serialize, Deserialize)]
+    struct TestEntity {
+        id: String,
+        value: String,
+    }
+
+    impl CacheEntity for TestEntity {
+        type Key = String;
+
+        fn cache_key(&self) -> Self::Key {
+            self.id.clone()
+        }
+
+        fn cache_prefix() -> &'static str {
+            "test"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_expander_with_fresh_strategy_hit() {
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone());
+
+        // Pre-populate cache
+        let entity = TestEntity {
+            id: "1".to_string(),
+            value: "data".to_string(),
+        };
+        let bytes = entity.serialize_for_cache().expect("Failed to serialize");
+        backend
+            .clone()
+            .set("test:1", bytes, None)
+            .await
+            .expect("Failed to set");
+
+        // Create feeder
+        let mut feeder = GenericFeeder::new("1".to_string());
+        let repo = InMemoryRepository::new();
+
+        // Execute
+        expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Fresh)
+            .await
+            .expect("Failed to execute");
+
+        assert!(feeder.data.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_expander_with_fresh_strategy_miss() {
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend);
+
+        let mut feeder = GenericFeeder::new("1".to_string());
+        let repo = InMemoryRepository::new();
+
+        expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Fresh)
+            .await
+            .expect("Failed to execute");
+
+        assert!(feeder.data.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expander_refresh_strategy_cache_hit() {
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone());
+
+        // Pre-populate cache
+        let entity = TestEntity {
+            id: "1".to_string(),
+            value: "cached_data".to_string(),
+        };
         let bytes = entity.serialize_for_cache().expect("Failed to serialize");
         backend
-            .clone()
-            .set("test:1", bytes, None)
+            .clone()
+            .set("test:1", bytes, None)
+            .await
+            .expect("Failed to set");
+
+        let mut feeder = GenericFeeder::new("1".to_string());
+        let repo = InMemoryRepository::new();
+
+        expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute");
+
+        assert!(feeder.data.is_some());
+        assert_eq!(feeder.data.expect("Data not found").value, "cached_data");
+    }
+
+    #[tokio::test]
+    async fn test_expander_refresh_strategy_cache_miss_db_hit() {
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone());
+
+        // Populate repository
+        let mut repo = InMemoryRepository::new();
+        repo.insert(
+            "1".to_string(),
+            TestEntity {
+                id: "1".to_string(),
+                value: "db_data".to_string(),
+            },
+        );
+
+        let mut feeder = GenericFeeder::new("1".to_string());
+
+        expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute");
+
+        assert!(feeder.data.is_some());
+        assert_eq!(feeder.data.expect("Data not found").value, "db_data");
+
+        // Verify it was cached
+        let cached = backend
+            .clone()
+            .get("test:1")
+            .await
+            .expect("Failed to get from cache");
+        assert!(cached.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_expander_refresh_strategy_complete_miss() {
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend);
+
+        let mut feeder = GenericFeeder::new("nonexistent".to_string());
+        let repo = InMemoryRepository::new();
+
+        expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute");
+
+        assert!(feeder.data.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expander_invalidate_strategy() {
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone());
+
+        // Pre-populate cache with stale data
+        let stale_entity = TestEntity {
+            id: "1".to_string(),
+            value: "stale_data".to_string(),
+        };
+        let bytes = stale_entity
+            .serialize_for_cache()
+            .expect("Failed to serialize");
+        backend
+            .clone()
+            .set("test:1", bytes, None)
+            .await
+            .expect("Failed to set");
+
+        // Populate repository with fresh data
+        let mut repo = InMemoryRepository::new();
+        repo.insert(
+            "1".to_string(),
+            TestEntity {
+                id: "1".to_string(),
+                value: "fresh_data".to_string(),
+            },
+        );
+
+        let mut feeder = GenericFeeder::new("1".to_string());
+
+        expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Invalidate)
+            .await
+            .expect("Failed to execute");
+
+        assert!(feeder.data.is_some());
+        assert_eq!(feeder.data.expect("Data not found").value, "fresh_data");
+
+        // Verify cache was updated
+        let cached_bytes = backend
+            .clone()
+            .get("test:1")
+            .await
+            .expect("Failed to get")
+            .expect("Cache is empty");
+        let cached_entity =
+            TestEntity::deserialize_from_cache(&cached_bytes).expect("Failed to deserialize");
+        assert_eq!(cached_entity.value, "fresh_data");
+    }
+
+    #[tokio::test]
+    async fn test_expander_bypass_strategy() {
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone());
+
+        // Pre-populate cache
+        let cached_entity = TestEntity {
+            id: "1".to_string(),
+            value: "cached_data".to_string(),
+        };
+        let bytes = cached_entity
+            .serialize_for_cache()
+            .expect("Failed to serialize");
+        backend
+            .clone()
+            .set("test:1", bytes, None)
+            .await
+            .expect("Failed to set");
+
+        // Populate repository with different data
+        let mut repo = InMemoryRepository::new();
+        repo.insert(
+            "1".to_string(),
+            TestEntity {
+                id: "1".to_string(),
+                value: "db_data".to_string(),
+            },
+        );
+
+        let mut feeder = GenericFeeder::new("1".to_string());
+
+        expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Bypass)
+            .await
+            .expect("Failed to execute");
+
+        // Should get database data, not cached data
+        assert!(feeder.data.is_some());
+        assert_eq!(feeder.data.expect("Data not found").value, "db_data");
+    }
+
+    #[tokio::test]
+    async fn test_expander_with_ttl_policy() {
+        use crate::observability::TtlPolicy;
+        use std::time::Duration;
+
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone())
+            .with_ttl_policy(TtlPolicy::Fixed(Duration::from_secs(300)));
+
+        let mut repo = InMemoryRepository::new();
+        repo.insert(
+            "1".to_string(),
+            TestEntity {
+                id: "1".to_string(),
+                value: "data".to_string(),
+            },
+        );
+
+        let mut feeder = GenericFeeder::new("1".to_string());
+
+        expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute");
+
+        assert!(feeder.data.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_expander_with_custom_metrics() {
+        use crate::observability::CacheMetrics;
+        use std::sync::{Arc, Mutex};
+        use std::time::Duration;
+
+        #[derive(Clone)]
+        struct TestMetrics {
+            hits: Arc<Mutex<usize>>,
+            misses: Arc<Mutex<usize>>,
+        }
+
+        impl CacheMetrics for TestMetrics {
+            fn record_hit(&self, _key: &str, _duration: Duration) {
+                *self.hits.lock().expect("Failed to lock hits") += 1;
+            }
+
+            fn record_miss(&self, _key: &str, _duration: Duration) {
+                *self.misses.lock().expect("Failed to lock misses") += 1;
+            }
+        }
+
+        let metrics = TestMetrics {
+            hits: Arc::new(Mutex::new(0)),
+            misses: Arc::new(Mutex::new(0)),
+        };
+
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone()).with_metrics(Box::new(metrics.clone()));
+
+        // Populate repository
+        let mut repo = InMemoryRepository::new();
+        repo.insert(
+            "1".to_string(),
+            TestEntity {
+                id: "1".to_string(),
+                value: "data".to_string(),
+            },
+        );
+
+        let mut feeder = GenericFeeder::new("1".to_string());
+
+        // First call: cache miss, database hit
+        expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute");
+
+        assert_eq!(*metrics.hits.lock().expect("Failed to lock hits"), 1); // Counted as hit after DB fetch
+
+        // Second call: cache hit
+        let mut feeder2 = GenericFeeder::new("1".to_string());
+        expander
+            .with::<TestEntity, _, _>(&mut feeder2, &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute");
+
+        assert_eq!(*metrics.hits.lock().expect("Failed to lock hits"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_expander_error_on_missing_data() {
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend);
+
+        let mut feeder = GenericFeeder::new("nonexistent".to_string());
+        let repo = InMemoryRepository::new();
+
+        // Fresh strategy with miss should return None (not error)
+        let result = expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Fresh)
+            .await;
+        assert!(result.is_ok());
+        assert!(feeder.data.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expander_backend_reference() {
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone());
+
+        // Test backend() method
+        let _backend_ref = expander.backend();
+
+        // Verify we can access the backend
+        assert_eq!(backend.len().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_expander_request_coalescing() {
+        use crate::observability::CacheMetrics;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        struct SlowRepository {
+            fetches: Arc<AtomicUsize>,
+        }
+
+        impl DataRepository<TestEntity> for SlowRepository {
+            async fn fetch_by_id(&self, id: &String) -> Result<Option<TestEntity>> {
+                self.fetches.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok(Some(TestEntity {
+                    id: id.clone(),
+                    value: "db_data".to_string(),
+                }))
+            }
+        }
+
+        struct CoalescedMetrics {
+            coalesced: Arc<AtomicUsize>,
+        }
+
+        impl CacheMetrics for CoalescedMetrics {
+            fn record_coalesced(&self, _key: &str) {
+                self.coalesced.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let coalesced = Arc::new(AtomicUsize::new(0));
+        let expander = Arc::new(
+            CacheExpander::new(InMemoryBackend::new())
+                .with_metrics(Box::new(CoalescedMetrics {
+                    coalesced: coalesced.clone(),
+                }))
+                .with_request_coalescing(),
+        );
+
+        let mut handles = vec![];
+        for _ in 0..8 {
+            let expander = expander.clone();
+            let repo = SlowRepository {
+                fetches: fetches.clone(),
+            };
+            handles.push(tokio::spawn(async move {
+                let mut feeder = GenericFeeder::new("1".to_string());
+                expander
+                    .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+                    .await
+                    .expect("Failed to execute");
+                feeder.data.expect("Data not found").value
+            }));
+        }
+
+        for handle in handles {
+            assert_eq!(handle.await.expect("Task failed"), "db_data");
+        }
+
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        assert_eq!(coalesced.load(Ordering::SeqCst), 7);
+    }
+
+    #[tokio::test]
+    async fn test_expander_invalidate_skips_load_in_flight() {
+        use std::sync::Mutex;
+
+        /// Repository that reads its value when a fetch starts, then stalls.
+        struct SlowRepository {
+            value: Arc<Mutex<String>>,
+        }
+
+        impl DataRepository<TestEntity> for SlowRepository {
+            async fn fetch_by_id(&self, id: &String) -> Result<Option<TestEntity>> {
+                let value = self.value.lock().expect("Failed to lock value").clone();
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                Ok(Some(TestEntity {
+                    id: id.clone(),
+                    value,
+                }))
+            }
+        }
+
+        let value = Arc::new(Mutex::new("old".to_string()));
+        let expander =
+            Arc::new(CacheExpander::new(InMemoryBackend::new()).with_request_coalescing());
+
+        let stale = {
+            let expander = expander.clone();
+            let repo = SlowRepository {
+                value: value.clone(),
+            };
+            tokio::spawn(async move {
+                let mut feeder = GenericFeeder::new("1".to_string());
+                expander
+                    .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+                    .await
+                    .expect("Failed to execute");
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // The entity changes while the first load is still in flight
+        *value.lock().expect("Failed to lock value") = "new".to_string();
+        let repo = SlowRepository { value };
+        let mut feeder = GenericFeeder::new("1".to_string());
+        expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Invalidate)
+            .await
+            .expect("Failed to execute");
+        assert_eq!(feeder.data.expect("Data not found").value, "new");
+
+        stale.await.expect("Task failed");
+        let mut feeder = GenericFeeder::new("1".to_string());
+        expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Fresh)
+            .await
+            .expect("Failed to execute");
+        assert_eq!(feeder.data.expect("Data not found").value, "new");
+    }
+
+    #[tokio::test]
+    async fn test_expander_stale_while_revalidate_fresh_hit() {
+        let backend = InMemoryBackend::new();
+        let expander = Arc::new(CacheExpander::new(backend.clone()));
+
+        let entity = TestEntity {
+            id: "1".to_string(),
+            value: "cached_data".to_string(),
+        };
+        let bytes = serialization::serialize_with_metadata(&entity, &EntryMetadata::now())
+            .expect("Failed to serialize");
+        backend
+            .set("test:1", bytes, None)
+            .await
+            .expect("Failed to set");
+
+        let mut repo = InMemoryRepository::new();
+        repo.insert(
+            "1".to_string(),
+            TestEntity {
+                id: "1".to_string(),
+                value: "db_data".to_string(),
+            },
+        );
+        let repo = Arc::new(repo);
+
+        let mut feeder = GenericFeeder::new("1".to_string());
+        let strategy = CacheStrategy::StaleWhileRevalidate {
+            stale_after: Duration::from_secs(60),
+        };
+        expander
+            .with_background_refresh::<TestEntity, _, _>(
+                &mut feeder,
+                &repo,
+                strategy,
+                OperationConfig::default(),
+            )
+            .await
+            .expect("Failed to execute");
+
+        assert_eq!(feeder.data.expect("Data not found").value, "cached_data");
+    }
+
+    #[tokio::test]
+    async fn test_expander_stale_while_revalidate_background_refresh() {
+        let backend = InMemoryBackend::new();
+        let expander = Arc::new(CacheExpander::new(backend.clone()));
+
+        // Entry written long ago is past its soft expiry
+        let stale = TestEntity {
+            id: "1".to_string(),
+            value: "stale_data".to_string(),
+        };
+        let bytes = serialization::serialize_with_metadata(
+            &stale,
+            &EntryMetadata {
+                created_at_ms: 0,
+                ..Default::default()
+            },
+        )
+        .expect("Failed to serialize");
+        backend
+            .set("test:1", bytes, None)
+            .await
+            .expect("Failed to set");
+
+        let mut repo = InMemoryRepository::new();
+        repo.insert(
+            "1".to_string(),
+            TestEntity {
+                id: "1".to_string(),
+                value: "fresh_data".to_string(),
+            },
+        );
+        let repo = Arc::new(repo);
+
+        let mut feeder = GenericFeeder::new("1".to_string());
+        let strategy = CacheStrategy::StaleWhileRevalidate {
+            stale_after: Duration::from_secs(60),
+        };
+        expander
+            .with_background_refresh::<TestEntity, _, _>(
+                &mut feeder,
+                &repo,
+                strategy,
+                OperationConfig::default(),
+            )
+            .await
+            .expect("Failed to execute");
+
+        // Stale value is served immediately
+        assert_eq!(feeder.data.expect("Data not found").value, "stale_data");
+
+        // Background task rewrites the entry
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let cached_bytes = backend
+            .get("test:1")
+            .await
+            .expect("Failed to get")
+            .expect("Cache is empty");
+        let (cached, metadata) =
+            serialization::deserialize_with_metadata::<TestEntity>(&cached_bytes)
+                .expect("Failed to deserialize");
+        assert_eq!(cached.value, "fresh_data");
+        assert!(metadata.is_some_and(|m| m.age() < Duration::from_secs(60)));
+    }
+
+    #[tokio::test]
+    async fn test_expander_stale_while_revalidate_requires_refresher() {
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone());
+        let repo = InMemoryRepository::<TestEntity>::new();
+
+        let mut feeder = GenericFeeder::new("1".to_string());
+        let strategy = CacheStrategy::StaleWhileRevalidate {
+            stale_after: Duration::from_secs(60),
+        };
+        let result = expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, strategy)
+            .await;
+
+        assert!(matches!(result, Err(Error::ConfigError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_expander_stale_while_revalidate_background_refresh_uses_config() {
+        #[derive(Clone)]
+        struct SlowRepository;
+
+        impl DataRepository<TestEntity> for SlowRepository {
+            async fn fetch_by_id(&self, id: &String) -> Result<Option<TestEntity>> {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                Ok(Some(TestEntity {
+                    id: id.clone(),
+                    value: "fresh_data".to_string(),
+                }))
+            }
+        }
+
+        let backend = InMemoryBackend::new();
+        let expander = Arc::new(CacheExpander::new(backend.clone()));
+
+        let stale = TestEntity {
+            id: "1".to_string(),
+            value: "stale_data".to_string(),
+        };
+        backend
+            .set(
+                "test:1",
+                stale.serialize_for_cache().expect("Failed to serialize"),
+                None,
+            )
+            .await
+            .expect("Failed to set");
+
+        let mut feeder = GenericFeeder::new("1".to_string());
+        let strategy = CacheStrategy::StaleWhileRevalidate {
+            stale_after: Duration::from_secs(60),
+        };
+        let config = OperationConfig::default().with_repository_timeout(Duration::from_millis(10));
+        expander
+            .with_background_refresh::<TestEntity, _, _>(
+                &mut feeder,
+                &SlowRepository,
+                strategy,
+                config,
+            )
+            .await
+            .expect("Failed to execute");
+        assert_eq!(feeder.data.expect("Data not found").value, "stale_data");
+
+        // The refresh times out under the repository deadline and leaves the entry stale
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let cached_bytes = backend
+            .get("test:1")
+            .await
+            .expect("Failed to get")
+            .expect("Cache is empty");
+        let cached =
+            TestEntity::deserialize_from_cache(&cached_bytes).expect("Failed to deserialize");
+        assert_eq!(cached.value, "stale_data");
+    }
+
+    #[tokio::test]
+    async fn test_expander_early_expiration_refreshes_before_ttl() {
+        use crate::observability::EarlyExpiration;
+
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone())
+            .with_early_expiration(EarlyExpiration::new(1_000_000_000.0));
+
+        // Expensive entry close to its TTL
+        let cached = TestEntity {
+            id: "1".to_string(),
+            value: "cached_data".to_string(),
+        };
+        let metadata = EntryMetadata::now()
+            .with_ttl(Some(Duration::from_secs(60)))
+            .with_compute_time(Duration::from_secs(1));
+        let bytes = serialization::serialize_with_metadata(&cached, &metadata)
+            .expect("Failed to serialize");
+        backend
+            .set("test:1", bytes, Some(Duration::from_secs(60)))
+            .await
+            .expect("Failed to set");
+
+        let mut repo = InMemoryRepository::new();
+        repo.insert(
+            "1".to_string(),
+            TestEntity {
+                id: "1".to_string(),
+                value: "db_data".to_string(),
+            },
+        );
+
+        let mut feeder = GenericFeeder::new("1".to_string());
+        expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute");
+
+        assert_eq!(feeder.data.expect("Data not found").value, "db_data");
+    }
+
+    #[tokio::test]
+    async fn test_expander_negative_caching() {
+        use crate::observability::CacheMetrics;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        struct CountingRepository {
+            fetches: Arc<AtomicUsize>,
+        }
+
+        impl DataRepository<TestEntity> for CountingRepository {
+            async fn fetch_by_id(&self, _id: &String) -> Result<Option<TestEntity>> {
+                self.fetches.fetch_add(1, Ordering::SeqCst);
+                Ok(None)
+            }
+        }
+
+        struct NegativeMetrics {
+            negative_hits: Arc<AtomicUsize>,
+            misses: Arc<AtomicUsize>,
+        }
+
+        impl CacheMetrics for NegativeMetrics {
+            fn record_miss(&self, _key: &str, _duration: Duration) {
+                self.misses.fetch_add(1, Ordering::SeqCst);
+            }
+
+            fn record_negative_hit(&self, _key: &str) {
+                self.negative_hits.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let negative_hits = Arc::new(AtomicUsize::new(0));
+        let misses = Arc::new(AtomicUsize::new(0));
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone())
+            .with_metrics(Box::new(NegativeMetrics {
+                negative_hits: negative_hits.clone(),
+                misses: misses.clone(),
+            }))
+            .with_negative_caching(TtlPolicy::Fixed(Duration::from_secs(30)));
+        let repo = CountingRepository {
+            fetches: fetches.clone(),
+        };
+
+        for strategy in [
+            CacheStrategy::Refresh,
+            CacheStrategy::Refresh,
+            CacheStrategy::Fresh,
+        ] {
+            let mut feeder = GenericFeeder::new("missing".to_string());
+            expander
+                .with::<TestEntity, _, _>(&mut feeder, &repo, strategy)
+                .await
+                .expect("Failed to execute");
+            assert!(feeder.data.is_none());
+        }
+
+        let cached = backend
+            .get("test:missing")
+            .await
+            .expect("Failed to get")
+            .expect("Tombstone not stored");
+        assert!(serialization::is_tombstone(&cached));
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        assert_eq!(negative_hits.load(Ordering::SeqCst), 2);
+        assert_eq!(misses.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_expander_negative_caching_disabled_stores_nothing() {
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone());
+        let repo = InMemoryRepository::<TestEntity>::new();
+
+        let mut feeder = GenericFeeder::new("missing".to_string());
+        expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute");
+
+        assert!(feeder.data.is_none());
+        assert!(backend.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn test_expander_write_through() {
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone());
+        let repo = InMemoryRepository::new();
+
+        let entity = TestEntity {
+            id: "1".to_string(),
+            value: "written".to_string(),
+        };
+        expander
+            .write_through(&entity, &repo)
+            .await
+            .expect("Failed to write through");
+
+        assert_eq!(repo.len(), 1);
+        let cached = backend
+            .get("test:1")
+            .await
+            .expect("Failed to get")
+            .expect("Entity not cached");
+        let cached = TestEntity::deserialize_from_cache(&cached).expect("Failed to deserialize");
+        assert_eq!(cached.value, "written");
+    }
+
+    #[tokio::test]
+    async fn test_expander_write_around_and_delete_through() {
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone());
+        let repo = InMemoryRepository::new();
+
+        let entity = TestEntity {
+            id: "1".to_string(),
+            value: "v1".to_string(),
+        };
+        expander
+            .write_through(&entity, &repo)
+            .await
+            .expect("Failed to write through");
+
+        let updated = TestEntity {
+            id: "1".to_string(),
+            value: "v2".to_string(),
+        };
+        expander
+            .write_around(&updated, &repo)
+            .await
+            .expect("Failed to write around");
+        assert!(backend
+            .get("test:1")
+            .await
+            .expect("Failed to get")
+            .is_none());
+
+        let mut feeder = GenericFeeder::new("1".to_string());
+        expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute");
+        assert_eq!(feeder.data.expect("Data not found").value, "v2");
+
+        expander
+            .delete_through::<TestEntity, _>(&"1".to_string(), &repo)
+            .await
+            .expect("Failed to delete through");
+        assert!(repo.is_empty());
+        assert!(backend
+            .get("test:1")
+            .await
+            .expect("Failed to get")
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expander_write_through_repository_failure_leaves_cache() {
+        use crate::repository::WritableRepository;
+
+        struct ReadOnlyRepository;
+
+        impl DataRepository<TestEntity> for ReadOnlyRepository {
+            async fn fetch_by_id(&self, _id: &String) -> Result<Option<TestEntity>> {
+                Ok(None)
+            }
+        }
+
+        impl WritableRepository<TestEntity> for ReadOnlyRepository {
+            async fn save(&self, _entity: &TestEntity) -> Result<()> {
+                Err(Error::RepositoryError("read-only".to_string()))
+            }
+
+            async fn delete(&self, _id: &String) -> Result<()> {
+                Err(Error::RepositoryError("read-only".to_string()))
+            }
+        }
+
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone());
+        backend
+            .set("test:1", b"existing".to_vec(), None)
+            .await
+            .expect("Failed to set");
+
+        let entity = TestEntity {
+            id: "1".to_string(),
+            value: "new".to_string(),
+        };
+        let result = expander.write_through(&entity, &ReadOnlyRepository).await;
+        assert!(matches!(result, Err(Error::RepositoryError(_))));
+
+        let result = expander
+            .delete_through::<TestEntity, _>(&"1".to_string(), &ReadOnlyRepository)
+            .await;
+        assert!(matches!(result, Err(Error::RepositoryError(_))));
+
+        assert_eq!(
+            backend.get("test:1").await.expect("Failed to get"),
+            Some(b"existing".to_vec())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expander_write_through_cache_failure() {
+        #[derive(Clone)]
+        struct ReadOnlyBackend;
+
+        impl CacheBackend for ReadOnlyBackend {
+            async fn get(&self, _key: &str) -> Result<Option<Vec<u8>>> {
+                Ok(None)
+            }
+
+            async fn set(&self, _key: &str, _value: Vec<u8>, _ttl: Option<Duration>) -> Result<()> {
+                Err(Error::BackendError("read-only".to_string()))
+            }
+
+            async fn delete(&self, _key: &str) -> Result<()> {
+                Ok(())
+            }
+        }
+
+        let expander = CacheExpander::new(ReadOnlyBackend);
+        let repo = InMemoryRepository::new();
+
+        let entity = TestEntity {
+            id: "1".to_string(),
+            value: "saved".to_string(),
+        };
+        let result = expander.write_through(&entity, &repo).await;
+
+        assert!(matches!(result, Err(Error::BackendError(_))));
+        assert_eq!(repo.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_expander_with_many() {
+        use crate::observability::CacheMetrics;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::{Arc, Mutex};
+
+        struct RecordingRepository {
+            inner: InMemoryRepository<TestEntity>,
+            requested: Arc<Mutex<Vec<Vec<String>>>>,
+        }
+
+        impl DataRepository<TestEntity> for RecordingRepository {
+            async fn fetch_by_id(&self, id: &String) -> Result<Option<TestEntity>> {
+                self.inner.fetch_by_id(id).await
+            }
+
+            async fn fetch_by_ids(&self, ids: &[String]) -> Result<Vec<Option<TestEntity>>> {
+                self.requested
+                    .lock()
+                    .expect("Failed to lock requested")
+                    .push(ids.to_vec());
+                self.inner.fetch_by_ids(ids).await
+            }
+        }
+
+        struct CountingMetrics {
+            hits: Arc<AtomicUsize>,
+            misses: Arc<AtomicUsize>,
+        }
+
+        impl CacheMetrics for CountingMetrics {
+            fn record_hit(&self, _key: &str, _duration: Duration) {
+                self.hits.fetch_add(1, Ordering::SeqCst);
+            }
+
+            fn record_miss(&self, _key: &str, _duration: Duration) {
+                self.misses.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let entity = |id: &str, value: &str| TestEntity {
+            id: id.to_string(),
+            value: value.to_string(),
+        };
+
+        let backend = InMemoryBackend::new();
+        backend
+            .set(
+                "test:2",
+                entity("2", "cached")
+                    .serialize_for_cache()
+                    .expect("Failed to serialize"),
+                None,
+            )
+            .await
+            .expect("Failed to set");
+
+        let mut inner = InMemoryRepository::new();
+        inner.insert("1".to_string(), entity("1", "db"));
+        inner.insert("2".to_string(), entity("2", "db"));
+        let requested = Arc::new(Mutex::new(Vec::new()));
+        let repo = RecordingRepository {
+            inner,
+            requested: requested.clone(),
+        };
+
+        let hits = Arc::new(AtomicUsize::new(0));
+        let misses = Arc::new(AtomicUsize::new(0));
+        let expander =
+            CacheExpander::new(backend.clone()).with_metrics(Box::new(CountingMetrics {
+                hits: hits.clone(),
+                misses: misses.clone(),
+            }));
+
+        let ids = vec!["3".to_string(), "2".to_string(), "1".to_string()];
+        let results = expander
+            .with_many::<TestEntity, _>(&ids, &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute batch");
+
+        let values: Vec<Option<String>> = results
+            .into_iter()
+            .map(|result| result.map(|e| e.value))
+            .collect();
+        assert_eq!(
+            values,
+            vec![None, Some("cached".to_string()), Some("db".to_string())]
+        );
+        assert_eq!(
+            *requested.lock().expect("Failed to lock requested"),
+            vec![vec!["3".to_string(), "1".to_string()]]
+        );
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        assert_eq!(misses.load(Ordering::SeqCst), 2);
+
+        // Back-filled entries are served from cache next time
+        assert!(backend
+            .get("test:1")
+            .await
+            .expect("Failed to get")
+            .is_some());
+        expander
+            .with_many::<TestEntity, _>(&ids[1..], &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute batch");
+        assert_eq!(requested.lock().expect("Failed to lock requested").len(), 1);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_expander_warm() {
+        use std::sync::{Arc, Mutex};
+
+        let mut repo = InMemoryRepository::new();
+        for id in 1..=5 {
+            repo.insert(
+                id.to_string(),
+                TestEntity {
+                    id: id.to_string(),
+                    value: "db".to_string(),
+                },
+            );
+        }
+
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone());
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let options = WarmOptions::default()
+            .with_page_size(2)
+            .with_batch_size(1)
+            .with_concurrency(2)
+            .with_filter(|id: &String| id != "3")
+            .with_progress({
+                let reports = reports.clone();
+                move |progress: &WarmProgress| {
+                    reports
+                        .lock()
+                        .expect("Failed to lock reports")
+                        .push(progress.fetched)
+                }
+            });
+
+        let progress = expander
+            .warm::<TestEntity, _>(&repo, options)
+            .await
+            .expect("Failed to warm");
+
+        assert_eq!(progress.total, Some(5));
+        assert_eq!(progress.fetched, 5);
+        assert_eq!(progress.written, 4);
+        assert_eq!(progress.skipped, 1);
+        assert_eq!(progress.failed, 0);
+        assert_eq!(
+            *reports.lock().expect("Failed to lock reports"),
+            vec![2, 4, 5]
+        );
+
+        assert!(backend
+            .get("test:1")
+            .await
+            .expect("Failed to get")
+            .is_some());
+        assert!(backend
+            .get("test:3")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        assert!(backend
+            .get("test:5")
+            .await
+            .expect("Failed to get")
+            .is_some());
+
+        // Warmed entries are cache hits
+        let cached = expander
+            .get::<TestEntity>("4".to_string())
+            .await
+            .expect("Failed to get");
+        assert_eq!(cached.map(|e| e.value), Some("db".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_expander_warm_falls_back_to_fetch_all() {
+        struct UnpagedRepository(Vec<TestEntity>);
+
+        impl DataRepository<TestEntity> for UnpagedRepository {
+            async fn fetch_by_id(&self, id: &String) -> Result<Option<TestEntity>> {
+                Ok(self.0.iter().find(|e| &e.id == id).cloned())
+            }
+
+            async fn fetch_all(&self) -> Result<Vec<TestEntity>> {
+                Ok(self.0.clone())
+            }
+        }
+
+        let repo = UnpagedRepository(
+            (1..=3)
+                .map(|id| TestEntity {
+                    id: id.to_string(),
+                    value: "db".to_string(),
+                })
+                .collect(),
+        );
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone());
+
+        let progress = expander
+            .warm::<TestEntity, _>(&repo, WarmOptions::default().with_page_size(2))
+            .await
+            .expect("Failed to warm");
+
+        assert_eq!(progress.total, Some(3));
+        assert_eq!(progress.written, 3);
+        assert!(backend
+            .get("test:2")
+            .await
+            .expect("Failed to get")
+            .is_some());
+
+        // Neither paging nor fetch_all
+        let result = expander
+            .warm::<TestEntity, _>(
+                &LoaderRepository(|| async { Ok(None) }),
+                WarmOptions::default(),
+            )
+            .await;
+        assert!(matches!(result, Err(Error::NotImplemented(_))));
+    }
+
+    #[tokio::test]
+    async fn test_expander_invalidate_tag() {
+        #[derive(Clone, Serialize, Deserialize)]
+        struct Invoice {
+            id: u32,
+            customer: u32,
+        }
+
+        impl CacheEntity for Invoice {
+            type Key = u32;
+
+            fn cache_key(&self) -> Self::Key {
+                self.id
+            }
+
+            fn cache_prefix() -> &'static str {
+                "invoice"
+            }
+
+            fn cache_tags(&self) -> Vec<String> {
+                vec![format!("customer:{}", self.customer)]
+            }
+        }
+
+        let mut repo = InMemoryRepository::new();
+        for (id, customer) in [(1, 7), (2, 7), (3, 8)] {
+            repo.insert(id, Invoice { id, customer });
+        }
+
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone());
+
+        // Single reads, batch reads and write-through all record tags
+        let mut feeder = GenericFeeder::new(1);
+        expander
+            .with::<Invoice, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute");
+        expander
+            .with_many::<Invoice, _>(&[3], &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute batch");
+        expander
+            .write_through(&Invoice { id: 2, customer: 7 }, &repo)
+            .await
+            .expect("Failed to write through");
+
+        let evicted = expander
+            .invalidate_tag("customer:7")
+            .await
+            .expect("Failed to invalidate tag");
+        assert_eq!(evicted, 2);
+
+        assert!(backend
+            .get("invoice:1")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        assert!(backend
+            .get("invoice:2")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        assert!(backend
+            .get("invoice:3")
+            .await
+            .expect("Failed to get")
+            .is_some());
+
+        // The tag set is emptied, so a second invalidation is a no-op
+        assert_eq!(
+            expander
+                .invalidate_tag("customer:7")
+                .await
+                .expect("Failed to invalidate tag"),
+            0
+        );
+
+        // Query results containing a tagged entry are evicted with it, even
+        // when the query itself carries no tags
+        struct Overdue;
+
+        impl CacheQuery for Overdue {
+            type Entity = Invoice;
+
+            fn query_prefix() -> &'static str {
+                "overdue"
+            }
+
+            fn query_params(&self) -> String {
+                "all".to_string()
+            }
+        }
+
+        expander
+            .query(&Overdue, || async {
+                Ok(vec![Invoice { id: 3, customer: 8 }])
+            })
+            .await
+            .expect("Query failed");
+        assert!(backend
+            .get("query:overdue:all")
+            .await
+            .expect("Failed to get")
+            .is_some());
+        assert_eq!(
+            expander
+                .invalidate_tag("customer:8")
+                .await
+                .expect("Failed to invalidate tag"),
+            1
+        );
+        assert!(backend
+            .get("query:overdue:all")
+            .await
+            .expect("Failed to get")
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expander_membership_sets_expire_with_entries() {
+        #[derive(Clone, Serialize, Deserialize)]
+        struct Invoice {
+            id: u32,
+            customer: u32,
+        }
+
+        impl CacheEntity for Invoice {
+            type Key = u32;
+
+            fn cache_key(&self) -> Self::Key {
+                self.id
+            }
+
+            fn cache_prefix() -> &'static str {
+                "invoice"
+            }
+
+            fn cache_tags(&self) -> Vec<String> {
+                vec![format!("customer:{}", self.customer)]
+            }
+        }
+
+        let mut repo = InMemoryRepository::new();
+        repo.insert(1, Invoice { id: 1, customer: 7 });
+
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone())
+            .with_ttl_policy(TtlPolicy::Fixed(Duration::from_millis(50)));
+
+        let mut feeder = GenericFeeder::new(1);
+        expander
+            .with::<Invoice, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute");
+        assert_eq!(
+            backend
+                .smembers("cache_kit:tag:customer:7")
+                .await
+                .expect("Failed to smembers"),
+            vec!["invoice:1"]
+        );
+
+        // The tag set lapses with the entry instead of keeping its key forever
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(backend
+            .smembers("cache_kit:tag:customer:7")
+            .await
+            .expect("Failed to smembers")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_expander_dependency_cascade() {
+        #[derive(Clone, Serialize, Deserialize)]
+        struct Customer {
+            id: u32,
+        }
+
+        impl CacheEntity for Customer {
+            type Key = u32;
+
+            fn cache_key(&self) -> Self::Key {
+                self.id
+            }
+
+            fn cache_prefix() -> &'static str {
+                "customer"
+            }
+
+            fn cache_tags(&self) -> Vec<String> {
+                vec!["customers".to_string()]
+            }
+        }
+
+        #[derive(Clone, Serialize, Deserialize)]
+        struct Invoice {
+            id: u32,
+            customer: u32,
+        }
+
+        impl CacheEntity for Invoice {
+            type Key = u32;
+
+            fn cache_key(&self) -> Self::Key {
+                self.id
+            }
+
+            fn cache_prefix() -> &'static str {
+                "invoice"
+            }
+
+            fn cache_dependencies(&self) -> Vec<EntryRef> {
+                vec![EntryRef::of::<Customer>(&self.customer)]
+            }
+        }
+
+        let mut customers = InMemoryRepository::new();
+        customers.insert(42, Customer { id: 42 });
+        let mut invoices = InMemoryRepository::new();
+        for (id, customer) in [(1, 42), (2, 42), (3, 43)] {
+            invoices.insert(id, Invoice { id, customer });
+        }
+
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone()).with_dependency_cascade(3);
+        expander
+            .with_many::<Invoice, _>(&[1, 2, 3], &invoices, CacheStrategy::Refresh)
             .await
-            .expect("Failed to set");
+            .expect("Failed to execute batch");
+
+        // Rewriting the customer evicts its invoices
+        expander
+            .write_through(&Customer { id: 42 }, &customers)
+            .await
+            .expect("Failed to write through");
+        assert!(backend
+            .get("customer:42")
+            .await
+            .expect("Failed to get")
+            .is_some());
+        assert!(backend
+            .get("invoice:1")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        assert!(backend
+            .get("invoice:2")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        assert!(backend
+            .get("invoice:3")
+            .await
+            .expect("Failed to get")
+            .is_some());
+
+        // Reloading an invoice records its dependency again
+        let mut feeder = GenericFeeder::new(1);
+        expander
+            .with::<Invoice, _, _>(&mut feeder, &invoices, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute");
+        let evicted = expander
+            .invalidate::<Customer>(&42)
+            .await
+            .expect("Failed to invalidate");
+        assert_eq!(evicted, 1);
+        assert!(backend
+            .get("customer:42")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        assert!(backend
+            .get("invoice:1")
+            .await
+            .expect("Failed to get")
+            .is_none());
+
+        // Tag invalidation cascades too
+        expander
+            .with::<Invoice, _, _>(&mut feeder, &invoices, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute");
+        expander
+            .write_through(&Customer { id: 42 }, &customers)
+            .await
+            .expect("Failed to write through");
+        expander
+            .with::<Invoice, _, _>(&mut feeder, &invoices, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute");
+        expander
+            .invalidate_tag("customers")
+            .await
+            .expect("Failed to invalidate tag");
+        assert!(backend
+            .get("customer:42")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        assert!(backend
+            .get("invoice:1")
+            .await
+            .expect("Failed to get")
+            .is_none());
+
+        // Without cascading, dependencies are neither recorded nor followed
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone());
+        expander
+            .with_many::<Invoice, _>(&[1], &invoices, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute batch");
+        assert_eq!(
+            expander
+                .invalidate::<Customer>(&42)
+                .await
+                .expect("Failed to invalidate"),
+            0
+        );
+        assert!(backend
+            .get("invoice:1")
+            .await
+            .expect("Failed to get")
+            .is_some());
+        assert!(backend
+            .smembers("cache_kit:deps:customer:42")
+            .await
+            .expect("Failed to smembers")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_expander_dependency_cascade_cycles_and_depth() {
+        #[derive(Clone, Serialize, Deserialize)]
+        struct Node {
+            id: u32,
+            depends_on: u32,
+        }
+
+        impl CacheEntity for Node {
+            type Key = u32;
+
+            fn cache_key(&self) -> Self::Key {
+                self.id
+            }
+
+            fn cache_prefix() -> &'static str {
+                "node"
+            }
+
+            fn cache_dependencies(&self) -> Vec<EntryRef> {
+                vec![EntryRef::of::<Node>(&self.depends_on)]
+            }
+        }
+
+        // A cycle 1 <- 2 <- 3 <- 1, and a chain 10 <- 11 <- 12 <- 13 <- 14
+        let mut repo = InMemoryRepository::new();
+        let nodes = [
+            (1, 3),
+            (2, 1),
+            (3, 2),
+            (10, 0),
+            (11, 10),
+            (12, 11),
+            (13, 12),
+            (14, 13),
+        ];
+        for (id, depends_on) in nodes {
+            repo.insert(id, Node { id, depends_on });
+        }
+
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone()).with_dependency_cascade(2);
+        expander
+            .with_many::<Node, _>(
+                &[1, 2, 3, 10, 11, 12, 13, 14],
+                &repo,
+                CacheStrategy::Refresh,
+            )
+            .await
+            .expect("Failed to execute batch");
+
+        // The cycle is followed once around and stops at the invalidated entry
+        let evicted = expander
+            .invalidate::<Node>(&1)
+            .await
+            .expect("Failed to invalidate");
+        assert_eq!(evicted, 2);
+        for key in ["node:1", "node:2", "node:3"] {
+            assert!(backend.get(key).await.expect("Failed to get").is_none());
+        }
+
+        // The chain is only followed two levels deep
+        let evicted = expander
+            .invalidate::<Node>(&10)
+            .await
+            .expect("Failed to invalidate");
+        assert_eq!(evicted, 2);
+        assert!(backend
+            .get("node:11")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        assert!(backend
+            .get("node:12")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        assert!(backend
+            .get("node:13")
+            .await
+            .expect("Failed to get")
+            .is_some());
+        assert!(backend
+            .get("node:14")
+            .await
+            .expect("Failed to get")
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_expander_with_index() {
+        use crate::index::{SecondaryIndex, SecondaryKey};
+
+        #[derive(Clone, Serialize, Deserialize)]
+        struct User {
+            id: u32,
+            email: String,
+        }
+
+        struct ByEmail;
+
+        impl SecondaryIndex<User> for ByEmail {
+            type Key = String;
+
+            fn index_prefix() -> &'static str {
+                "user_email"
+            }
+        }
+
+        impl CacheEntity for User {
+            type Key = u32;
+
+            fn cache_key(&self) -> Self::Key {
+                self.id
+            }
+
+            fn cache_prefix() -> &'static str {
+                "user"
+            }
+
+            fn cache_secondary_keys(&self) -> Vec<SecondaryKey> {
+                vec![SecondaryKey::of::<User, ByEmail>(&self.email)]
+            }
+        }
+
+        let user = |id: u32, email: &str| User {
+            id,
+            email: email.to_string(),
+        };
+        let mut repo = InMemoryRepository::new();
+        repo.insert(1, user(1, "ada@example.com"));
+        repo.insert(2, user(2, "bob@example.com"));
+
+        /// Collects the user IDs read through the expander.
+        #[derive(Default)]
+        struct Reads(Mutex<Vec<u32>>);
+
+        impl AccessRecorder for Reads {
+            fn record_access(&self, id: &dyn Any) {
+                if let Some(id) = id.downcast_ref::<u32>() {
+                    self.0.lock().expect("Failed to lock reads").push(*id);
+                }
+            }
+        }
+
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone());
+        let reads = Arc::new(Reads::default());
+        expander.add_access_recorder::<User>(Arc::downgrade(&reads) as Weak<dyn AccessRecorder>);
+        let lookup = |email: &str| {
+            let (email, expander, repo) = (email.to_string(), &expander, &repo);
+            async move { expander.with_index::<User, ByEmail, _>(&email, repo).await }
+        };
+
+        // A miss loads by email and writes a pointer to the primary entry
+        let found = lookup("ada@example.com").await.expect("Lookup failed");
+        assert_eq!(found.map(|user| user.id), Some(1));
+        assert_eq!(
+            backend
+                .get("user_email:ada@example.com")
+                .await
+                .expect("Failed to get"),
+            Some(b"user:1".to_vec())
+        );
+        assert!(backend
+            .get("user:1")
+            .await
+            .expect("Failed to get")
+            .is_some());
+
+        // A hit follows the pointer without the repository
+        let empty = InMemoryRepository::<User>::new();
+        let found = expander
+            .with_index::<User, ByEmail, _>(&"ada@example.com".to_string(), &empty)
+            .await
+            .expect("Lookup failed");
+        assert_eq!(found.map(|user| user.id), Some(1));
+
+        // Both reads count against the primary key, e.g. for refresh-ahead
+        assert_eq!(*reads.0.lock().expect("Failed to lock reads"), vec![1, 1]);
+
+        // A pointer left behind by a changed email is detected and deleted
+        expander
+            .write_through(&user(1, "ada@example.org"), &repo)
+            .await
+            .expect("Failed to write through");
+        assert!(lookup("ada@example.com")
+            .await
+            .expect("Lookup failed")
+            .is_none());
+        assert!(backend
+            .get("user_email:ada@example.com")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        assert!(backend
+            .get("user_email:ada@example.org")
+            .await
+            .expect("Failed to get")
+            .is_some());
+
+        // Invalidation and deletes remove pointers with their entries
+        expander
+            .invalidate::<User>(&1)
+            .await
+            .expect("Failed to invalidate");
+        assert!(backend
+            .get("user_email:ada@example.org")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        lookup("bob@example.com").await.expect("Lookup failed");
+        expander
+            .delete_through::<User, _>(&2, &repo)
+            .await
+            .expect("Failed to delete through");
+        assert!(backend
+            .get("user_email:bob@example.com")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        assert!(backend
+            .smembers("cache_kit:ptrs:user:2")
+            .await
+            .expect("Failed to smembers")
+            .is_empty());
+        assert!(lookup("bob@example.com")
+            .await
+            .expect("Lookup failed")
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expander_query() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Clone, Serialize, Deserialize)]
+        struct Invoice {
+            id: u32,
+            customer: u32,
+        }
+
+        impl CacheEntity for Invoice {
+            type Key = u32;
+
+            fn cache_key(&self) -> Self::Key {
+                self.id
+            }
+
+            fn cache_prefix() -> &'static str {
+                "invoice"
+            }
+        }
+
+        struct InvoiceList {
+            customer: u32,
+        }
+
+        impl CacheQuery for InvoiceList {
+            type Entity = Invoice;
+
+            fn query_prefix() -> &'static str {
+                "invoice_list"
+            }
+
+            fn query_params(&self) -> String {
+                self.customer.to_string()
+            }
+
+            fn query_tags(&self) -> Vec<String> {
+                vec![format!("customer:{}", self.customer)]
+            }
+        }
+
+        let mut repo = InMemoryRepository::new();
+        for (id, customer) in [(1, 7), (2, 7), (3, 8)] {
+            repo.insert(id, Invoice { id, customer });
+        }
+
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone());
+        let loads = AtomicUsize::new(0);
+        let list = InvoiceList { customer: 7 };
+        let load = || async {
+            loads.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![
+                Invoice { id: 1, customer: 7 },
+                Invoice { id: 2, customer: 7 },
+            ])
+        };
+
+        // A miss loads the result and caches its members; a hit reads them back
+        let invoices = expander.query(&list, load).await.expect("Query failed");
+        assert_eq!(invoices.len(), 2);
+        assert!(backend
+            .get("query:invoice_list:7")
+            .await
+            .expect("Failed to get")
+            .is_some());
+        assert!(backend
+            .get("invoice:1")
+            .await
+            .expect("Failed to get")
+            .is_some());
+        let invoices = expander.query(&list, load).await.expect("Query failed");
+        assert_eq!(invoices[1].id, 2);
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+
+        // Writing or invalidating a member evicts the result
+        expander
+            .write_through(&Invoice { id: 1, customer: 7 }, &repo)
+            .await
+            .expect("Failed to write through");
+        assert!(backend
+            .get("query:invoice_list:7")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        expander.query(&list, load).await.expect("Query failed");
+        expander
+            .invalidate::<Invoice>(&2)
+            .await
+            .expect("Failed to invalidate");
+        assert!(backend
+            .get("query:invoice_list:7")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        expander.query(&list, load).await.expect("Query failed");
+        assert_eq!(loads.load(Ordering::SeqCst), 3);
+
+        // A member that left the entity cache reloads the result
+        backend.delete("invoice:1").await.expect("Failed to delete");
+        expander.query(&list, load).await.expect("Query failed");
+        assert_eq!(loads.load(Ordering::SeqCst), 4);
+
+        // Query tags and explicit invalidation
+        expander
+            .invalidate_tag("customer:7")
+            .await
+            .expect("Failed to invalidate tag");
+        assert!(backend
+            .get("query:invoice_list:7")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        expander.query(&list, load).await.expect("Query failed");
+        expander
+            .invalidate_query(&list)
+            .await
+            .expect("Failed to invalidate query");
+        assert!(backend
+            .get("query:invoice_list:7")
+            .await
+            .expect("Failed to get")
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expander_query_pages() {
+        #[derive(Clone, Serialize, Deserialize)]
+        struct Invoice {
+            id: u32,
+        }
+
+        impl CacheEntity for Invoice {
+            type Key = u32;
+
+            fn cache_key(&self) -> Self::Key {
+                self.id
+            }
+
+            fn cache_prefix() -> &'static str {
+                "invoice"
+            }
+        }
+
+        struct RecentInvoices;
+
+        impl CacheQuery for RecentInvoices {
+            type Entity = Invoice;
+
+            fn query_prefix() -> &'static str {
+                "recent_invoices"
+            }
+
+            fn query_params(&self) -> String {
+                "all".to_string()
+            }
+
+            fn storage() -> QueryStorage {
+                QueryStorage::Pages
+            }
+        }
+
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone()).with_generations();
+        let load = || async { Ok(vec![Invoice { id: 1 }, Invoice { id: 2 }]) };
+
+        // Pages are stored whole, keyed by the entity type's generation
+        expander
+            .query(&RecentInvoices, load)
+            .await
+            .expect("Query failed");
+        assert!(backend
+            .get("query:recent_invoices:g0:all")
+            .await
+            .expect("Failed to get")
+            .is_some());
+        assert!(backend
+            .get("invoice:g0:1")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        let invoices = expander
+            .query(&RecentInvoices, || async { Ok(Vec::new()) })
+            .await
+            .expect("Query failed");
+        assert_eq!(invoices.len(), 2);
+
+        // Members still evict the page, and a generation bump drops it
+        expander
+            .invalidate::<Invoice>(&2)
+            .await
+            .expect("Failed to invalidate");
+        assert!(backend
+            .get("query:recent_invoices:g0:all")
+            .await
+            .expect("Failed to get")
+            .is_none());
+        expander
+            .query(&RecentInvoices, load)
+            .await
+            .expect("Query failed");
+        expander
+            .invalidate_all::<Invoice>()
+            .await
+            .expect("Failed to bump generation");
+        let invoices = expander
+            .query(&RecentInvoices, || async { Ok(Vec::new()) })
+            .await
+            .expect("Query failed");
+        assert!(invoices.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_expander_invalidate_all_bumps_generation() {
+        let mut repo = InMemoryRepository::new();
+        for id in ["1", "2"] {
+            repo.insert(
+                id.to_string(),
+                TestEntity {
+                    id: id.to_string(),
+                    value: "v1".to_string(),
+                },
+            );
+        }
+
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone()).with_generations();
+        let ids = vec!["1".to_string(), "2".to_string()];
+
+        expander
+            .with_many::<TestEntity, _>(&ids, &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute batch");
+        assert!(backend
+            .get("test:g0:1")
+            .await
+            .expect("Failed to get")
+            .is_some());
+
+        repo.insert(
+            "1".to_string(),
+            TestEntity {
+                id: "1".to_string(),
+                value: "v2".to_string(),
+            },
+        );
+        assert_eq!(
+            expander
+                .invalidate_all::<TestEntity>()
+                .await
+                .expect("Failed to invalidate"),
+            1
+        );
 
-        // Create feeder
+        // Old entries are unreachable; reads reload under the new generation
         let mut feeder = GenericFeeder::new("1".to_string());
-        let repo = InMemoryRepository::new();
-
-        // Execute
         expander
-            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Fresh)
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
             .await
             .expect("Failed to execute");
+        assert_eq!(feeder.data.map(|e| e.value), Some("v2".to_string()));
+        assert!(backend
+            .get("test:g1:1")
+            .await
+            .expect("Failed to get")
+            .is_some());
 
-        assert!(feeder.data.is_some());
+        let cached = expander
+            .get::<TestEntity>("2".to_string())
+            .await
+            .expect("Failed to get");
+        assert!(cached.is_none());
+
+        // Without generations there is nothing to bump
+        let plain = CacheExpander::new(InMemoryBackend::new());
+        assert!(matches!(
+            plain.invalidate_all::<TestEntity>().await,
+            Err(Error::ConfigError(_))
+        ));
     }
 
     #[tokio::test]
-    async fn test_expander_with_fresh_strategy_miss() {
+    async fn test_expander_invalidate_namespace() {
+        #[derive(Clone, Serialize, Deserialize)]
+        struct Document {
+            id: String,
+        }
+
+        impl CacheEntity for Document {
+            type Key = String;
+
+            fn cache_key(&self) -> Self::Key {
+                self.id.clone()
+            }
+
+            fn cache_prefix() -> &'static str {
+                "doc"
+            }
+
+            fn cache_namespace(id: &String) -> Option<String> {
+                id.split_once('/')
+                    .map(|(tenant, _)| format!("tenant:{}", tenant))
+            }
+        }
+
         let backend = InMemoryBackend::new();
-        let expander = CacheExpander::new(backend);
+        let expander = CacheExpander::new(backend.clone()).with_generations();
+        for id in ["a/1", "b/1"] {
+            let document = Document { id: id.to_string() };
+            expander
+                .get_or_load::<Document, _>(id.to_string(), CacheStrategy::Refresh, || {
+                    let document = document.clone();
+                    async move { Ok(Some(document)) }
+                })
+                .await
+                .expect("Failed to load");
+        }
+        assert!(backend
+            .get("doc:g0:tenant:a:g0:a/1")
+            .await
+            .expect("Failed to get")
+            .is_some());
+
+        expander
+            .invalidate_namespace("tenant:a")
+            .await
+            .expect("Failed to invalidate");
+
+        let tenant_a = expander
+            .get::<Document>("a/1".to_string())
+            .await
+            .expect("Failed to get");
+        let tenant_b = expander
+            .get::<Document>("b/1".to_string())
+            .await
+            .expect("Failed to get");
+        assert!(tenant_a.is_none());
+        assert!(tenant_b.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_expander_generations_fail_open() {
+        #[derive(Clone)]
+        struct NoGetBackend(InMemoryBackend);
+
+        impl CacheBackend for NoGetBackend {
+            async fn get(&self, _key: &str) -> Result<Option<Vec<u8>>> {
+                Err(Error::BackendError("down".to_string()))
+            }
+
+            async fn set(&self, key: &str, value: Vec<u8>, ttl: Option<Duration>) -> Result<()> {
+                self.0.set(key, value, ttl).await
+            }
+
+            async fn delete(&self, key: &str) -> Result<()> {
+                self.0.delete(key).await
+            }
+        }
+
+        let mut repo = InMemoryRepository::new();
+        repo.insert(
+            "1".to_string(),
+            TestEntity {
+                id: "1".to_string(),
+                value: "db".to_string(),
+            },
+        );
 
+        let expander = CacheExpander::new(NoGetBackend(InMemoryBackend::new())).with_generations();
         let mut feeder = GenericFeeder::new("1".to_string());
-        let repo = InMemoryRepository::new();
+        let result = expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .await;
+        assert!(matches!(result, Err(Error::BackendError(_))));
 
+        let expander = expander.with_failure_mode(FailureMode::Open);
         expander
-            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Fresh)
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
             .await
-            .expect("Failed to execute");
-
-        assert!(feeder.data.is_none());
+            .expect("Fail-open read failed");
+        assert_eq!(feeder.data.map(|e| e.value), Some("db".to_string()));
     }
 
     #[tokio::test]
-    async fn test_expander_refresh_strategy_cache_hit() {
+    async fn test_expander_with_many_fresh_skips_repository() {
         let backend = InMemoryBackend::new();
-        let expander = CacheExpander::new(backend.clone());
+        let expander = CacheExpander::new(backend);
+
+        let mut repo = InMemoryRepository::new();
+        repo.insert(
+            "1".to_string(),
+            TestEntity {
+                id: "1".to_string(),
+                value: "db".to_string(),
+            },
+        );
+
+        let results = expander
+            .with_many::<TestEntity, _>(&["1".to_string()], &repo, CacheStrategy::Fresh)
+            .await
+            .expect("Failed to execute batch");
+        assert!(results[0].is_none());
+    }
+
+    /// Backend that fails every operation, simulating an outage.
+    #[derive(Clone)]
+    struct DownBackend;
+
+    impl CacheBackend for DownBackend {
+        async fn get(&self, _key: &str) -> Result<Option<Vec<u8>>> {
+            Err(Error::BackendError("connection refused".to_string()))
+        }
+
+        async fn set(&self, _key: &str, _value: Vec<u8>, _ttl: Option<Duration>) -> Result<()> {
+            Err(Error::BackendError("connection refused".to_string()))
+        }
+
+        async fn delete(&self, _key: &str) -> Result<()> {
+            Err(Error::BackendError("connection refused".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_expander_fail_closed_by_default() {
+        let expander = CacheExpander::new(DownBackend);
+        let mut repo = InMemoryRepository::new();
+        repo.insert(
+            "1".to_string(),
+            TestEntity {
+                id: "1".to_string(),
+                value: "db_data".to_string(),
+            },
+        );
+
+        let mut feeder = GenericFeeder::new("1".to_string());
+        let result = expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .await;
+
+        assert!(matches!(result, Err(Error::BackendError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_expander_fail_open_reads_from_repository() {
+        use crate::observability::CacheMetrics;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        struct FailOpenMetrics {
+            tolerated: Arc<AtomicUsize>,
+        }
+
+        impl CacheMetrics for FailOpenMetrics {
+            fn record_fail_open(&self, _key: &str, _error: &str) {
+                self.tolerated.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let tolerated = Arc::new(AtomicUsize::new(0));
+        let expander = CacheExpander::new(DownBackend)
+            .with_metrics(Box::new(FailOpenMetrics {
+                tolerated: tolerated.clone(),
+            }))
+            .with_failure_mode(FailureMode::Open);
+        let mut repo = InMemoryRepository::new();
+        repo.insert(
+            "1".to_string(),
+            TestEntity {
+                id: "1".to_string(),
+                value: "db_data".to_string(),
+            },
+        );
+
+        for strategy in [CacheStrategy::Refresh, CacheStrategy::Invalidate] {
+            let mut feeder = GenericFeeder::new("1".to_string());
+            expander
+                .with::<TestEntity, _, _>(&mut feeder, &repo, strategy)
+                .await
+                .expect("Failed to execute");
+            assert_eq!(feeder.data.expect("Data not found").value, "db_data");
+        }
+        assert_eq!(tolerated.load(Ordering::SeqCst), 2);
+
+        let results = expander
+            .with_many::<TestEntity, _>(&["1".to_string()], &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute batch");
+        assert!(results[0].is_some());
+
+        // Per-operation override restores fail-closed behavior
+        let mut feeder = GenericFeeder::new("1".to_string());
+        let config = OperationConfig::default().with_failure_mode(FailureMode::Closed);
+        let result = expander
+            .with_config::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh, config)
+            .await;
+        assert!(matches!(result, Err(Error::BackendError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_expander_fail_open_ignores_write_errors() {
+        let expander = CacheExpander::new(DownBackend).with_failure_mode(FailureMode::Open);
+        let repo = InMemoryRepository::new();
 
-        // Pre-populate cache
         let entity = TestEntity {
             id: "1".to_string(),
-            value: "cached_data".to_string(),
+            value: "saved".to_string(),
         };
-        let bytes = entity.serialize_for_cache().expect("Failed to serialize");
-        backend
-            .clone()
-            .set("test:1", bytes, None)
+        expander
+            .write_through(&entity, &repo)
             .await
-            .expect("Failed to set");
+            .expect("Failed to write through");
+        expander
+            .delete_through::<TestEntity, _>(&"1".to_string(), &repo)
+            .await
+            .expect("Failed to delete through");
+
+        assert!(repo.is_empty());
+    }
+
+    /// Backend whose reads hang long enough to trip any test deadline.
+    #[derive(Clone)]
+    struct HungBackend;
+
+    impl CacheBackend for HungBackend {
+        async fn get(&self, _key: &str) -> Result<Option<Vec<u8>>> {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(None)
+        }
+
+        async fn set(&self, _key: &str, _value: Vec<u8>, _ttl: Option<Duration>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn delete(&self, _key: &str) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Repository whose fetches hang long enough to trip any test deadline.
+    struct HungRepository;
+
+    impl DataRepository<TestEntity> for HungRepository {
+        async fn fetch_by_id(&self, _id: &String) -> Result<Option<TestEntity>> {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(None)
+        }
+    }
 
+    #[tokio::test]
+    async fn test_expander_backend_timeout() {
+        let config = OperationConfig::default().with_backend_timeout(Duration::from_millis(20));
+        let mut repo = InMemoryRepository::new();
+        repo.insert(
+            "1".to_string(),
+            TestEntity {
+                id: "1".to_string(),
+                value: "db_data".to_string(),
+            },
+        );
+
+        let expander = CacheExpander::new(HungBackend);
         let mut feeder = GenericFeeder::new("1".to_string());
-        let repo = InMemoryRepository::new();
+        let result = expander
+            .with_config::<TestEntity, _, _>(
+                &mut feeder,
+                &repo,
+                CacheStrategy::Fresh,
+                config.clone(),
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::Timeout(ref msg)) if msg.starts_with("backend call")
+        ));
 
+        // A timed-out read is an outage like any other under fail-open
+        let expander = CacheExpander::new(HungBackend).with_failure_mode(FailureMode::Open);
+        let mut feeder = GenericFeeder::new("1".to_string());
         expander
-            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .with_config::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh, config)
             .await
             .expect("Failed to execute");
+        assert_eq!(feeder.data.expect("Data not found").value, "db_data");
+    }
+
+    #[tokio::test]
+    async fn test_expander_repository_timeout() {
+        let expander = CacheExpander::new(InMemoryBackend::new());
+        let config = OperationConfig::default().with_repository_timeout(Duration::from_millis(20));
+
+        let mut feeder = GenericFeeder::new("1".to_string());
+        let result = expander
+            .with_config::<TestEntity, _, _>(
+                &mut feeder,
+                &HungRepository,
+                CacheStrategy::Refresh,
+                config,
+            )
+            .await;
+
+        match result {
+            Err(Error::Timeout(msg)) => assert_eq!(msg, "repository call exceeded 20ms"),
+            other => panic!("Expected repository timeout, got {:?}", other.err()),
+        }
+        assert!(feeder.data.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expander_operation_timeout_covers_retries() {
+        let expander = CacheExpander::new(InMemoryBackend::new());
+        let config = OperationConfig::default()
+            .with_repository_timeout(Duration::from_millis(20))
+            .with_retry_policy(RetryPolicy::new(4).with_jitter(Jitter::None))
+            .with_timeout(Duration::from_millis(150));
 
+        let started = Instant::now();
+        let mut feeder = GenericFeeder::new("1".to_string());
+        let result = expander
+            .with_config::<TestEntity, _, _>(
+                &mut feeder,
+                &HungRepository,
+                CacheStrategy::Refresh,
+                config,
+            )
+            .await;
+
+        // Without the operation deadline the backoff alone would take 700ms
+        assert!(matches!(
+            result,
+            Err(Error::Timeout(ref msg)) if msg.starts_with("operation")
+        ));
+        assert!(started.elapsed() < Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_expander_with_many_config() {
+        let ids = vec!["1".to_string(), "2".to_string()];
+
+        let expander = CacheExpander::new(InMemoryBackend::new());
+        let config = OperationConfig::default().with_repository_timeout(Duration::from_millis(20));
+        let result = expander
+            .with_many_config::<TestEntity, _>(
+                &ids,
+                &HungRepository,
+                CacheStrategy::Refresh,
+                config,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::Timeout(ref msg)) if msg.starts_with("repository call")
+        ));
+
+        // A timed-out mget fails open under a per-operation override
+        let mut repo = InMemoryRepository::new();
+        repo.insert(
+            "1".to_string(),
+            TestEntity {
+                id: "1".to_string(),
+                value: "db_data".to_string(),
+            },
+        );
+        let expander = CacheExpander::new(HungBackend);
+        let config = OperationConfig::default()
+            .with_backend_timeout(Duration::from_millis(20))
+            .with_failure_mode(FailureMode::Open);
+        let results = expander
+            .with_many_config::<TestEntity, _>(&ids, &repo, CacheStrategy::Refresh, config)
+            .await
+            .expect("Failed to execute batch");
+        assert_eq!(
+            results[0].as_ref().expect("Data not found").value,
+            "db_data"
+        );
+        assert!(results[1].is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expander_with_many_coalesces_misses() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Clone)]
+        struct SlowRepository {
+            fetches: Arc<AtomicUsize>,
+        }
+
+        impl DataRepository<TestEntity> for SlowRepository {
+            async fn fetch_by_id(&self, id: &String) -> Result<Option<TestEntity>> {
+                self.fetches.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok(Some(TestEntity {
+                    id: id.clone(),
+                    value: "db_data".to_string(),
+                }))
+            }
+        }
+
+        let repo = SlowRepository {
+            fetches: Arc::new(AtomicUsize::new(0)),
+        };
+        let expander =
+            Arc::new(CacheExpander::new(InMemoryBackend::new()).with_request_coalescing());
+
+        let single = {
+            let expander = expander.clone();
+            let repo = repo.clone();
+            tokio::spawn(async move {
+                let mut feeder = GenericFeeder::new("1".to_string());
+                expander
+                    .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+                    .await
+                    .expect("Failed to execute");
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let mut batches = vec![];
+        for _ in 0..4 {
+            let expander = expander.clone();
+            let repo = repo.clone();
+            batches.push(tokio::spawn(async move {
+                expander
+                    .with_many::<TestEntity, _>(
+                        &["1".to_string(), "2".to_string()],
+                        &repo,
+                        CacheStrategy::Refresh,
+                    )
+                    .await
+                    .expect("Failed to execute batch")
+            }));
+        }
+
+        for batch in batches {
+            let results = batch.await.expect("Task failed");
+            assert!(results.iter().all(Option::is_some));
+        }
+        single.await.expect("Task failed");
+
+        // "1" joined the single load; "2" was fetched by one batch for all four
+        assert_eq!(repo.fetches.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_expander_retries_only_retryable_errors() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// Fails its first `failures` fetches with `error`.
+        struct FlakyRepository {
+            fetches: AtomicUsize,
+            failures: usize,
+            error: Error,
+        }
+
+        impl DataRepository<TestEntity> for FlakyRepository {
+            async fn fetch_by_id(&self, id: &String) -> Result<Option<TestEntity>> {
+                if self.fetches.fetch_add(1, Ordering::SeqCst) < self.failures {
+                    return Err(self.error.clone());
+                }
+                Ok(Some(TestEntity {
+                    id: id.clone(),
+                    value: "db_data".to_string(),
+                }))
+            }
+        }
+
+        let expander = CacheExpander::new(InMemoryBackend::new());
+        let policy = RetryPolicy::new(3).with_base_delay(Duration::from_millis(1));
+
+        let repo = FlakyRepository {
+            fetches: AtomicUsize::new(0),
+            failures: 2,
+            error: Error::RepositoryError("connection reset".to_string()),
+        };
+        let mut feeder = GenericFeeder::new("1".to_string());
+        let config = OperationConfig::default().with_retry_policy(policy);
+        expander
+            .with_config::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Bypass, config)
+            .await
+            .expect("Failed to execute");
+        assert_eq!(repo.fetches.load(Ordering::SeqCst), 3);
         assert!(feeder.data.is_some());
-        assert_eq!(feeder.data.expect("Data not found").value, "cached_data");
+
+        let repo = FlakyRepository {
+            fetches: AtomicUsize::new(0),
+            failures: 2,
+            error: Error::ValidationError("malformed id".to_string()),
+        };
+        let mut feeder = GenericFeeder::new("1".to_string());
+        let config = OperationConfig::default().with_retry_policy(policy);
+        let result = expander
+            .with_config::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Bypass, config)
+            .await;
+        assert!(matches!(result, Err(Error::ValidationError(_))));
+        assert_eq!(repo.fetches.load(Ordering::SeqCst), 1);
     }
 
     #[tokio::test]
-    async fn test_expander_refresh_strategy_cache_miss_db_hit() {
-        let backend = InMemoryBackend::new();
-        let expander = CacheExpander::new(backend.clone());
+    async fn test_expander_retry_scope() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
 
-        // Populate repository
+        /// Fails reads while `failures` is positive, decrementing it each time.
+        #[derive(Clone)]
+        struct FlakyBackend {
+            inner: InMemoryBackend,
+            failures: Arc<AtomicUsize>,
+        }
+
+        impl CacheBackend for FlakyBackend {
+            async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+                let remaining = self.failures.load(Ordering::SeqCst);
+                if remaining > 0 {
+                    self.failures.store(remaining - 1, Ordering::SeqCst);
+                    return Err(Error::BackendError("connection reset".to_string()));
+                }
+                self.inner.get(key).await
+            }
+
+            async fn set(&self, key: &str, value: Vec<u8>, ttl: Option<Duration>) -> Result<()> {
+                self.inner.set(key, value, ttl).await
+            }
+
+            async fn delete(&self, key: &str) -> Result<()> {
+                self.inner.delete(key).await
+            }
+        }
+
+        let failures = Arc::new(AtomicUsize::new(0));
+        let expander = CacheExpander::new(FlakyBackend {
+            inner: InMemoryBackend::new(),
+            failures: failures.clone(),
+        });
         let mut repo = InMemoryRepository::new();
         repo.insert(
             "1".to_string(),
@@ -625,248 +5644,366 @@ mod tests {
                 value: "db_data".to_string(),
             },
         );
+        let policy = RetryPolicy::new(3).with_base_delay(Duration::from_millis(1));
 
+        // Retrying only the repository does not retry a failed cache read
+        failures.store(1, Ordering::SeqCst);
         let mut feeder = GenericFeeder::new("1".to_string());
+        let config =
+            OperationConfig::default().with_retry_policy(policy.with_scope(RetryScope::Repository));
+        let result = expander
+            .with_config::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh, config)
+            .await;
+        assert!(matches!(result, Err(Error::BackendError(_))));
 
+        // Retrying the backend recovers from it
+        failures.store(2, Ordering::SeqCst);
+        let mut feeder = GenericFeeder::new("1".to_string());
+        let config =
+            OperationConfig::default().with_retry_policy(policy.with_scope(RetryScope::Backend));
         expander
-            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .with_config::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh, config)
             .await
             .expect("Failed to execute");
-
-        assert!(feeder.data.is_some());
         assert_eq!(feeder.data.expect("Data not found").value, "db_data");
-
-        // Verify it was cached
-        let cached = backend
-            .clone()
-            .get("test:1")
-            .await
-            .expect("Failed to get from cache");
-        assert!(cached.is_some());
+        assert_eq!(failures.load(Ordering::SeqCst), 0);
     }
 
     #[tokio::test]
-    async fn test_expander_refresh_strategy_complete_miss() {
-        let backend = InMemoryBackend::new();
-        let expander = CacheExpander::new(backend);
-
-        let mut feeder = GenericFeeder::new("nonexistent".to_string());
-        let repo = InMemoryRepository::new();
+    async fn test_expander_self_healing_reloads_corrupt_entry() {
+        use crate::observability::CacheMetrics;
+        use std::sync::atomic::{AtomicUsize, Ordering};
 
-        expander
-            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
-            .await
-            .expect("Failed to execute");
+        struct HealMetrics {
+            heals: Arc<AtomicUsize>,
+        }
 
-        assert!(feeder.data.is_none());
-    }
+        impl CacheMetrics for HealMetrics {
+            fn record_heal(&self, _key: &str, _error: &str) {
+                self.heals.fetch_add(1, Ordering::SeqCst);
+            }
+        }
 
-    #[tokio::test]
-    async fn test_expander_invalidate_strategy() {
         let backend = InMemoryBackend::new();
-        let expander = CacheExpander::new(backend.clone());
-
-        // Pre-populate cache with stale data
-        let stale_entity = TestEntity {
-            id: "1".to_string(),
-            value: "stale_data".to_string(),
-        };
-        let bytes = stale_entity
-            .serialize_for_cache()
-            .expect("Failed to serialize");
-        backend
-            .clone()
-            .set("test:1", bytes, None)
-            .await
-            .expect("Failed to set");
-
-        // Populate repository with fresh data
         let mut repo = InMemoryRepository::new();
         repo.insert(
             "1".to_string(),
             TestEntity {
                 id: "1".to_string(),
-                value: "fresh_data".to_string(),
+                value: "db_data".to_string(),
             },
         );
+        backend
+            .set("test:1", b"left over from an old deploy".to_vec(), None)
+            .await
+            .expect("Failed to set");
 
+        let expander = CacheExpander::new(backend.clone());
         let mut feeder = GenericFeeder::new("1".to_string());
+        let result = expander
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .await;
+        assert!(result.is_err());
 
+        let heals = Arc::new(AtomicUsize::new(0));
+        let expander = CacheExpander::new(backend.clone())
+            .with_metrics(Box::new(HealMetrics {
+                heals: heals.clone(),
+            }))
+            .with_self_healing();
+        let mut feeder = GenericFeeder::new("1".to_string());
         expander
-            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Invalidate)
+            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
             .await
-            .expect("Failed to execute");
-
-        assert!(feeder.data.is_some());
-        assert_eq!(feeder.data.expect("Data not found").value, "fresh_data");
+            .expect("Failed to heal");
+        assert_eq!(feeder.data.expect("Data not found").value, "db_data");
+        assert_eq!(heals.load(Ordering::SeqCst), 1);
 
-        // Verify cache was updated
-        let cached_bytes = backend
-            .clone()
+        // The entry was rewritten in the current format
+        let bytes = backend
             .get("test:1")
             .await
             .expect("Failed to get")
-            .expect("Cache is empty");
-        let cached_entity =
-            TestEntity::deserialize_from_cache(&cached_bytes).expect("Failed to deserialize");
-        assert_eq!(cached_entity.value, "fresh_data");
+            .expect("Entry not rewritten");
+        let cached = TestEntity::deserialize_from_cache(&bytes).expect("Entry still corrupt");
+        assert_eq!(cached.value, "db_data");
     }
 
     #[tokio::test]
-    async fn test_expander_bypass_strategy() {
-        let backend = InMemoryBackend::new();
-        let expander = CacheExpander::new(backend.clone());
+    async fn test_expander_self_healing_evicts_invalid_entity() {
+        #[derive(Clone, Serialize, Deserialize)]
+        struct CheckedEntity {
+            id: String,
+            value: String,
+        }
 
-        // Pre-populate cache
-        let cached_entity = TestEntity {
+        impl CacheEntity for CheckedEntity {
+            type Key = String;
+
+            fn cache_key(&self) -> Self::Key {
+                self.id.clone()
+            }
+
+            fn cache_prefix() -> &'static str {
+                "checked"
+            }
+
+            fn validate(&self) -> Result<()> {
+                if self.value.is_empty() {
+                    return Err(Error::ValidationError("empty value".to_string()));
+                }
+                Ok(())
+            }
+        }
+
+        let backend = InMemoryBackend::new();
+        let invalid = CheckedEntity {
             id: "1".to_string(),
-            value: "cached_data".to_string(),
+            value: String::new(),
         };
-        let bytes = cached_entity
-            .serialize_for_cache()
-            .expect("Failed to serialize");
         backend
-            .clone()
-            .set("test:1", bytes, None)
+            .set(
+                "checked:1",
+                invalid.serialize_for_cache().expect("Failed to serialize"),
+                None,
+            )
             .await
             .expect("Failed to set");
 
-        // Populate repository with different data
-        let mut repo = InMemoryRepository::new();
-        repo.insert(
-            "1".to_string(),
-            TestEntity {
-                id: "1".to_string(),
-                value: "db_data".to_string(),
-            },
-        );
-
+        let expander = CacheExpander::new(backend.clone()).with_self_healing();
+        let repo = InMemoryRepository::new();
         let mut feeder = GenericFeeder::new("1".to_string());
-
         expander
-            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Bypass)
+            .with::<CheckedEntity, _, _>(&mut feeder, &repo, CacheStrategy::Fresh)
             .await
-            .expect("Failed to execute");
+            .expect("Failed to heal");
 
-        // Should get database data, not cached data
-        assert!(feeder.data.is_some());
-        assert_eq!(feeder.data.expect("Data not found").value, "db_data");
+        assert!(feeder.data.is_none());
+        assert!(backend
+            .get("checked:1")
+            .await
+            .expect("Failed to get")
+            .is_none());
     }
 
     #[tokio::test]
-    async fn test_expander_with_ttl_policy() {
-        use crate::observability::TtlPolicy;
-        use std::time::Duration;
-
-        let backend = InMemoryBackend::new();
-        let expander = CacheExpander::new(backend.clone())
-            .with_ttl_policy(TtlPolicy::Fixed(Duration::from_secs(300)));
-
-        let mut repo = InMemoryRepository::new();
-        repo.insert(
-            "1".to_string(),
-            TestEntity {
-                id: "1".to_string(),
-                value: "data".to_string(),
-            },
-        );
-
-        let mut feeder = GenericFeeder::new("1".to_string());
-
-        expander
-            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
-            .await
-            .expect("Failed to execute");
+    async fn test_expander_entity_ttl_precedence() {
+        #[derive(Clone, Serialize, Deserialize)]
+        struct Post {
+            id: String,
+            draft: bool,
+        }
 
-        assert!(feeder.data.is_some());
-    }
+        impl CacheEntity for Post {
+            type Key = String;
 
-    #[tokio::test]
-    async fn test_expander_with_custom_metrics() {
-        use crate::observability::CacheMetrics;
-        use std::sync::{Arc, Mutex};
-        use std::time::Duration;
+            fn cache_key(&self) -> Self::Key {
+                self.id.clone()
+            }
 
-        #[derive(Clone)]
-        struct TestMetrics {
-            hits: Arc<Mutex<usize>>,
-            misses: Arc<Mutex<usize>>,
-        }
+            fn cache_prefix() -> &'static str {
+                "post"
+            }
 
-        impl CacheMetrics for TestMetrics {
-            fn record_hit(&self, _key: &str, _duration: Duration) {
-                *self.hits.lock().expect("Failed to lock hits") += 1;
+            fn cache_ttl() -> Option<Duration> {
+                Some(Duration::from_secs(3600))
             }
 
-            fn record_miss(&self, _key: &str, _duration: Duration) {
-                *self.misses.lock().expect("Failed to lock misses") += 1;
+            fn cache_instance_ttl(&self) -> Option<Duration> {
+                self.draft.then(|| Duration::from_secs(60))
             }
         }
 
-        let metrics = TestMetrics {
-            hits: Arc::new(Mutex::new(0)),
-            misses: Arc::new(Mutex::new(0)),
-        };
-
-        let backend = InMemoryBackend::new();
-        let expander = CacheExpander::new(backend.clone()).with_metrics(Box::new(metrics.clone()));
+        // Expiry of an entry, relative to when it was written
+        async fn ttl(backend: &InMemoryBackend, id: &str) -> u64 {
+            let bytes = backend
+                .get(&format!("post:{}", id))
+                .await
+                .expect("Failed to get")
+                .expect("Entry not cached");
+            let (_, metadata) = serialization::deserialize_with_metadata::<Post>(&bytes)
+                .expect("Failed to deserialize");
+            let metadata = metadata.expect("Metadata missing");
+            metadata.expires_at_ms.expect("TTL missing") - metadata.created_at_ms
+        }
 
-        // Populate repository
         let mut repo = InMemoryRepository::new();
-        repo.insert(
-            "1".to_string(),
-            TestEntity {
-                id: "1".to_string(),
-                value: "data".to_string(),
-            },
-        );
+        for (id, draft) in [("1", false), ("2", true), ("3", false), ("4", true)] {
+            repo.insert(
+                id.to_string(),
+                Post {
+                    id: id.to_string(),
+                    draft,
+                },
+            );
+        }
+        let backend = InMemoryBackend::new();
+        let expander = CacheExpander::new(backend.clone())
+            .with_ttl_policy(TtlPolicy::Fixed(Duration::from_secs(10)));
 
-        let mut feeder = GenericFeeder::new("1".to_string());
+        for id in ["1", "2"] {
+            let mut feeder = GenericFeeder::new(id.to_string());
+            expander
+                .with::<Post, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+                .await
+                .expect("Failed to execute");
+        }
+        assert_eq!(ttl(&backend, "1").await, 3_600_000);
+        assert_eq!(ttl(&backend, "2").await, 60_000);
 
-        // First call: cache miss, database hit
+        // Batch loads write each entity with its own TTL
         expander
-            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .with_many::<Post, _>(
+                &["3".to_string(), "4".to_string()],
+                &repo,
+                CacheStrategy::Refresh,
+            )
             .await
             .expect("Failed to execute");
+        assert_eq!(ttl(&backend, "3").await, 3_600_000);
+        assert_eq!(ttl(&backend, "4").await, 60_000);
 
-        assert_eq!(*metrics.hits.lock().expect("Failed to lock hits"), 1); // Counted as hit after DB fetch
-
-        // Second call: cache hit
-        let mut feeder2 = GenericFeeder::new("1".to_string());
+        // The config override wins over both entity TTLs
+        let mut feeder = GenericFeeder::new("2".to_string());
         expander
-            .with::<TestEntity, _, _>(&mut feeder2, &repo, CacheStrategy::Refresh)
+            .with_config::<Post, _, _>(
+                &mut feeder,
+                &repo,
+                CacheStrategy::Invalidate,
+                OperationConfig::default().with_ttl(Duration::from_secs(5)),
+            )
             .await
             .expect("Failed to execute");
-
-        assert_eq!(*metrics.hits.lock().expect("Failed to lock hits"), 2);
+        assert_eq!(ttl(&backend, "2").await, 5_000);
     }
 
     #[tokio::test]
-    async fn test_expander_error_on_missing_data() {
-        let backend = InMemoryBackend::new();
-        let expander = CacheExpander::new(backend);
+    async fn test_expander_jittered_ttl() {
+        use crate::observability::TtlJitter;
 
-        let mut feeder = GenericFeeder::new("nonexistent".to_string());
-        let repo = InMemoryRepository::new();
+        let base = Duration::from_secs(3600);
+        let policy = TtlPolicy::Fixed(base).with_jitter(TtlJitter::Range(Duration::from_secs(600)));
+        let mut repo = InMemoryRepository::new();
+        for id in ["1", "2", "3"] {
+            repo.insert(
+                id.to_string(),
+                TestEntity {
+                    id: id.to_string(),
+                    value: "db_data".to_string(),
+                },
+            );
+        }
 
-        // Fresh strategy with miss should return None (not error)
-        let result = expander
-            .with::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Fresh)
-            .await;
-        assert!(result.is_ok());
-        assert!(feeder.data.is_none());
+        // Expiry of each entry, relative to when it was written
+        async fn ttls(
+            seed: u64,
+            policy: TtlPolicy,
+            repo: &InMemoryRepository<TestEntity>,
+        ) -> Vec<u64> {
+            let backend = InMemoryBackend::new();
+            let expander = CacheExpander::new(backend.clone())
+                .with_ttl_policy(policy)
+                .with_ttl_seed(seed);
+            let mut ttls = vec![];
+            for (id, strategy) in [
+                ("1", CacheStrategy::Refresh),
+                ("2", CacheStrategy::Invalidate),
+                ("3", CacheStrategy::Bypass),
+            ] {
+                let mut feeder = GenericFeeder::new(id.to_string());
+                expander
+                    .with::<TestEntity, _, _>(&mut feeder, repo, strategy)
+                    .await
+                    .expect("Failed to execute");
+                let bytes = backend
+                    .get(&format!("test:{}", id))
+                    .await
+                    .expect("Failed to get")
+                    .expect("Entry not cached");
+                let (_, metadata) = serialization::deserialize_with_metadata::<TestEntity>(&bytes)
+                    .expect("Failed to deserialize");
+                let metadata = metadata.expect("Metadata missing");
+                ttls.push(metadata.expires_at_ms.expect("TTL missing") - metadata.created_at_ms);
+            }
+            ttls
+        }
+
+        let first = ttls(42, policy.clone(), &repo).await;
+        let range = 3_600_000..=4_200_000;
+        assert!(first.iter().all(|ttl| range.contains(ttl)));
+        assert!(first.iter().any(|ttl| *ttl != first[0]));
+
+        // A fixed seed makes the draws reproducible
+        assert_eq!(ttls(42, policy, &repo).await, first);
     }
 
     #[tokio::test]
-    async fn test_expander_backend_reference() {
+    async fn test_expander_composite_key_reaches_repository() {
+        use std::fmt;
+
+        /// Key whose `Display` form can't be parsed back.
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        struct MembershipKey {
+            org: String,
+            user: u64,
+        }
+
+        impl fmt::Display for MembershipKey {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}/{}", self.org, self.user)
+            }
+        }
+
+        #[derive(Clone, Serialize, Deserialize)]
+        struct Membership {
+            org: String,
+            user: u64,
+            role: String,
+        }
+
+        impl CacheEntity for Membership {
+            type Key = MembershipKey;
+
+            fn cache_key(&self) -> Self::Key {
+                MembershipKey {
+                    org: self.org.clone(),
+                    user: self.user,
+                }
+            }
+
+            fn cache_prefix() -> &'static str {
+                "membership"
+            }
+        }
+
         let backend = InMemoryBackend::new();
         let expander = CacheExpander::new(backend.clone());
+        let mut repo = InMemoryRepository::new();
+        let key = MembershipKey {
+            org: "acme:eu".to_string(),
+            user: 7,
+        };
+        repo.insert(
+            key.clone(),
+            Membership {
+                org: key.org.clone(),
+                user: key.user,
+                role: "admin".to_string(),
+            },
+        );
 
-        // Test backend() method
-        let _backend_ref = expander.backend();
+        let mut feeder = GenericFeeder::new(key);
+        expander
+            .with::<Membership, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute");
 
-        // Verify we can access the backend
-        assert_eq!(backend.len().await, 0);
+        assert_eq!(feeder.data.expect("Data not found").role, "admin");
+        assert!(backend
+            .get("membership:acme:eu/7")
+            .await
+            .expect("Failed to get")
+            .is_some());
     }
 
     #[tokio::test]
@@ -0,0 +1,97 @@
+//! Secondary-key lookups.
+//!
+//! A [`SecondaryIndex`] names an alternate unique field of an entity, such as
+//! a user's email or a product's slug. Entities list their alternate keys with
+//! `CacheEntity::cache_secondary_keys()`, and whenever the expander writes an
+//! entity it also writes a pointer `"{index_prefix}:{value}"` holding the
+//! entity's primary cache key.
+//!
+//! `CacheExpander::with_index` (or `CacheService::execute_by`) follows the
+//! pointer to the primary entry. If the pointer or the entry is missing, or the
+//! entry no longer has the requested value, the entity is loaded with
+//! [`IndexedRepository::fetch_by_index`](crate::repository::IndexedRepository)
+//! and cached with fresh pointers.
+//!
+//! Pointers are deleted when their entry is invalidated or evicted through the
+//! expander (`invalidate`, `write_around`, `delete_through`, `invalidate_tag`,
+//! dependency cascades). Pointers left behind by a changed field are detected
+//! on lookup and deleted then.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use cache_kit::index::{SecondaryIndex, SecondaryKey};
+//!
+//! struct ByEmail;
+//!
+//! impl SecondaryIndex<User> for ByEmail {
+//!     type Key = String;
+//!
+//!     fn index_prefix() -> &'static str {
+//!         "user_email"
+//!     }
+//! }
+//!
+//! impl CacheEntity for User {
+//!     // ...
+//!     fn cache_secondary_keys(&self) -> Vec<SecondaryKey> {
+//!         vec![SecondaryKey::of::<User, ByEmail>(&self.email)]
+//!     }
+//! }
+//!
+//! let user = cache
+//!     .execute_by::<User, ByEmail, _>(&"ada@example.com".to_string(), &user_repo)
+//!     .await?;
+//! ```
+
+use crate::entity::CacheEntity;
+use crate::key::CacheKeyBuilder;
+use std::fmt::Display;
+
+/// An alternate unique key of entity type `T`.
+pub trait SecondaryIndex<T: CacheEntity>: Send + Sync + 'static {
+    /// Type of the alternate key (e.g. `String` for an email address).
+    type Key: Display + Send + Sync;
+
+    /// Return the prefix of this index's pointer keys.
+    ///
+    /// Example: "user_email". Pointer key format: `"{prefix}:{value}"`
+    fn index_prefix() -> &'static str;
+}
+
+/// One alternate key of an entity, returned by `CacheEntity::cache_secondary_keys`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SecondaryKey {
+    prefix: &'static str,
+    value: String,
+}
+
+impl SecondaryKey {
+    /// Alternate key `value` in the index with `prefix`.
+    pub fn new(prefix: &'static str, value: impl Display) -> Self {
+        SecondaryKey {
+            prefix,
+            value: value.to_string(),
+        }
+    }
+
+    /// Alternate key `value` in index `I`.
+    pub fn of<T: CacheEntity, I: SecondaryIndex<T>>(value: &I::Key) -> Self {
+        Self::new(I::index_prefix(), value)
+    }
+
+    /// The index's `index_prefix()`.
+    pub fn prefix(&self) -> &'static str {
+        self.prefix
+    }
+
+    /// The alternate key, formatted.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Key of the pointer to the entry with this alternate key.
+    pub fn pointer_key(&self) -> String {
+        CacheKeyBuilder::build_with_prefix(self.prefix, &self.value)
+    }
+}
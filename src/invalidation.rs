@@ -0,0 +1,356 @@
+//! Cross-instance invalidation of in-process cache tiers.
+//!
+//! When several replicas each keep an in-process L1 tier in a
+//! [`TieredBackend`](crate::backend::TieredBackend), overwriting or deleting
+//! an entry on one replica leaves the other replicas' L1 copies in place until
+//! they expire. An [`InvalidationBus`] carries each [`Invalidation`] to every
+//! replica, and each replica evicts the named keys from its own L1. The L1 tier
+//! can be any backend implementing `CacheBackend::delete_prefix` and
+//! `clear_all`, such as `InMemoryBackend`.
+//!
+//! [`RedisBus`] uses Redis pub/sub; [`InProcessBus`] connects tiers in the same
+//! process and is meant for tests.
+//!
+//! Messages are delivered at most once. A subscriber that may have missed
+//! messages (a lagging receiver, a dropped Redis connection) treats the gap as
+//! [`Invalidation::All`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use cache_kit::backend::{InMemoryBackend, TieredBackend, TieredConfig};
+//! use cache_kit::invalidation::RedisBus;
+//!
+//! let bus = RedisBus::new(redis_config.clone()).await?;
+//! let backend = TieredBackend::new(InMemoryBackend::new(), redis, TieredConfig::default())
+//!     .with_invalidation_bus(bus)
+//!     .await?;
+//! let cache = CacheService::new(backend);
+//! ```
+
+use crate::error::Result;
+use futures::stream::{BoxStream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use tokio::sync::broadcast;
+
+/// Keys to evict from every peer's local tier.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Invalidation {
+    /// These keys.
+    Keys(Vec<String>),
+
+    /// Every key starting with this prefix.
+    Prefix(String),
+
+    /// Every key.
+    All,
+}
+
+/// Transport broadcasting invalidations between instances.
+///
+/// A subscriber also receives the invalidations its own instance publishes.
+pub trait InvalidationBus: Send + Sync + 'static {
+    /// Send `invalidation` to every current subscriber.
+    fn publish(&self, invalidation: &Invalidation) -> impl Future<Output = Result<()>> + Send;
+
+    /// Start receiving invalidations published from now on.
+    fn subscribe(&self) -> impl Future<Output = Result<Subscription>> + Send;
+}
+
+/// Stream of invalidations from an [`InvalidationBus`].
+pub struct Subscription {
+    invalidations: BoxStream<'static, Invalidation>,
+}
+
+impl Subscription {
+    /// Wrap a stream of invalidations. The stream ends when the subscription
+    /// is lost.
+    pub fn new(invalidations: impl Stream<Item = Invalidation> + Send + 'static) -> Self {
+        Subscription {
+            invalidations: invalidations.boxed(),
+        }
+    }
+
+    /// Wait for the next invalidation. Returns `None` once the subscription
+    /// is lost.
+    pub async fn recv(&mut self) -> Option<Invalidation> {
+        self.invalidations.next().await
+    }
+}
+
+/// Default capacity of an [`InProcessBus`].
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// Invalidation bus between tiers in the same process.
+///
+/// Clones share the channel. A subscriber more than `capacity` messages
+/// behind receives `Invalidation::All` in place of the messages it missed.
+#[derive(Clone)]
+pub struct InProcessBus {
+    sender: broadcast::Sender<Invalidation>,
+}
+
+impl InProcessBus {
+    /// Create a bus buffering up to 1024 messages per subscriber.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// Create a bus buffering up to `capacity` messages per subscriber.
+    pub fn with_capacity(capacity: usize) -> Self {
+        InProcessBus {
+            sender: broadcast::channel(capacity.max(1)).0,
+        }
+    }
+}
+
+impl Default for InProcessBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InvalidationBus for InProcessBus {
+    async fn publish(&self, invalidation: &Invalidation) -> Result<()> {
+        // No subscribers is not an error
+        let _ = self.sender.send(invalidation.clone());
+        Ok(())
+    }
+
+    async fn subscribe(&self) -> Result<Subscription> {
+        let receiver = self.sender.subscribe();
+        Ok(Subscription::new(futures::stream::unfold(
+            receiver,
+            |mut receiver| async move {
+                let invalidation = match receiver.recv().await {
+                    Ok(invalidation) => invalidation,
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        warn!(
+                            "Invalidation subscriber missed {} messages, invalidating all",
+                            missed
+                        );
+                        Invalidation::All
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                };
+                Some((invalidation, receiver))
+            },
+        )))
+    }
+}
+
+#[cfg(feature = "redis")]
+pub use self::redis::RedisBus;
+
+#[cfg(feature = "redis")]
+mod redis {
+    use super::{Invalidation, InvalidationBus, Subscription};
+    use crate::backend::{RedisBackend, RedisConfig};
+    use crate::error::{Error, Result};
+    use crate::serialization::{deserialize_from_cache, serialize_for_cache};
+    use deadpool_redis::redis::{self, AsyncCommands};
+    use futures::StreamExt;
+
+    /// Default Redis channel for invalidations.
+    const DEFAULT_CHANNEL: &str = "cache_kit:invalidations";
+
+    /// Invalidation bus over Redis pub/sub.
+    ///
+    /// Publishes through a connection pool; each subscription holds its own
+    /// connection.
+    #[derive(Clone)]
+    pub struct RedisBus {
+        publisher: RedisBackend,
+        client: redis::Client,
+        channel: String,
+    }
+
+    impl RedisBus {
+        /// Create a bus on the server in `config`, using the channel
+        /// `"cache_kit:invalidations"`.
+        ///
+        /// # Errors
+        /// Returns `Err` if the pool or client cannot be created.
+        pub async fn new(config: RedisConfig) -> Result<Self> {
+            let client = redis::Client::open(config.connection_string()).map_err(|e| {
+                Error::BackendError(format!("Failed to create Redis client: {}", e))
+            })?;
+
+            Ok(RedisBus {
+                publisher: RedisBackend::new(config).await?,
+                client,
+                channel: DEFAULT_CHANNEL.to_string(),
+            })
+        }
+
+        /// Use `channel` instead of `"cache_kit:invalidations"`, e.g. to keep
+        /// several applications on one server apart.
+        pub fn with_channel(mut self, channel: impl Into<String>) -> Self {
+            self.channel = channel.into();
+            self
+        }
+    }
+
+    impl InvalidationBus for RedisBus {
+        async fn publish(&self, invalidation: &Invalidation) -> Result<()> {
+            let payload = serialize_for_cache(invalidation)?;
+            let mut conn = self.publisher.connection().await?;
+
+            conn.publish::<_, _, ()>(&self.channel, payload)
+                .await
+                .map_err(|e| {
+                    Error::BackendError(format!(
+                        "Redis PUBLISH failed for channel {}: {}",
+                        self.channel, e
+                    ))
+                })
+        }
+
+        async fn subscribe(&self) -> Result<Subscription> {
+            let mut pubsub = self.client.get_async_pubsub().await.map_err(|e| {
+                Error::BackendError(format!("Failed to open Redis pub/sub connection: {}", e))
+            })?;
+            pubsub.subscribe(&self.channel).await.map_err(|e| {
+                Error::BackendError(format!(
+                    "Redis SUBSCRIBE failed for channel {}: {}",
+                    self.channel, e
+                ))
+            })?;
+
+            let messages = pubsub.into_on_message().map(|message| {
+                deserialize_from_cache(message.get_payload_bytes()).unwrap_or_else(|e| {
+                    warn!("Undecodable invalidation message, invalidating all: {}", e);
+                    Invalidation::All
+                })
+            });
+            Ok(Subscription::new(messages))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{CacheBackend, InMemoryBackend, TieredBackend, TieredConfig};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_in_process_bus_broadcasts() {
+        let bus = InProcessBus::new();
+        let mut first = bus.subscribe().await.expect("Failed to subscribe");
+        let mut second = bus.clone().subscribe().await.expect("Failed to subscribe");
+
+        let invalidation = Invalidation::Keys(vec!["user:1".to_string()]);
+        bus.publish(&invalidation).await.expect("Failed to publish");
+        assert_eq!(first.recv().await, Some(invalidation.clone()));
+        assert_eq!(second.recv().await, Some(invalidation));
+    }
+
+    #[tokio::test]
+    async fn test_in_process_bus_lag_invalidates_all() {
+        let bus = InProcessBus::with_capacity(1);
+        let mut subscription = bus.subscribe().await.expect("Failed to subscribe");
+
+        bus.publish(&Invalidation::Prefix("user".to_string()))
+            .await
+            .expect("Failed to publish");
+        bus.publish(&Invalidation::Keys(vec!["user:1".to_string()]))
+            .await
+            .expect("Failed to publish");
+        assert_eq!(subscription.recv().await, Some(Invalidation::All));
+        assert_eq!(
+            subscription.recv().await,
+            Some(Invalidation::Keys(vec!["user:1".to_string()]))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_in_process_bus_evicts_peer_tiered_l1() {
+        let bus = InProcessBus::new();
+        let l2 = InMemoryBackend::new();
+        let config = TieredConfig::default().with_l1_ttl(Duration::from_secs(60));
+        let local = TieredBackend::new(InMemoryBackend::new(), l2.clone(), config.clone())
+            .with_invalidation_bus(bus.clone())
+            .await
+            .expect("Failed to subscribe");
+        let peer = TieredBackend::new(InMemoryBackend::new(), l2.clone(), config)
+            .with_invalidation_bus(bus)
+            .await
+            .expect("Failed to subscribe");
+
+        l2.set("user:1", b"value".to_vec(), None)
+            .await
+            .expect("Failed to set");
+        peer.get("user:1").await.expect("Failed to get");
+        assert!(peer
+            .l1()
+            .get("user:1")
+            .await
+            .expect("Failed to get")
+            .is_some());
+
+        local.delete("user:1").await.expect("Failed to delete");
+
+        wait_for_l1_eviction(&peer, "user:1").await;
+        assert!(peer.get("user:1").await.expect("Failed to get").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_in_process_bus_evicts_overwritten_peer_tiered_l1() {
+        let bus = InProcessBus::new();
+        let l2 = InMemoryBackend::new();
+        let config = TieredConfig::default().with_l1_ttl(Duration::from_secs(60));
+        let local = TieredBackend::new(InMemoryBackend::new(), l2.clone(), config.clone())
+            .with_invalidation_bus(bus.clone())
+            .await
+            .expect("Failed to subscribe");
+        let peer = TieredBackend::new(InMemoryBackend::new(), l2.clone(), config)
+            .with_invalidation_bus(bus)
+            .await
+            .expect("Failed to subscribe");
+
+        l2.set("user:1", b"old".to_vec(), None)
+            .await
+            .expect("Failed to set");
+        assert_eq!(
+            peer.get("user:1").await.expect("Failed to get"),
+            Some(b"old".to_vec())
+        );
+
+        local
+            .set("user:1", b"new".to_vec(), None)
+            .await
+            .expect("Failed to set");
+        wait_for_l1_eviction(&peer, "user:1").await;
+        assert_eq!(
+            peer.get("user:1").await.expect("Failed to get"),
+            Some(b"new".to_vec())
+        );
+
+        local
+            .mset(&[("user:1", b"newer".to_vec())], None)
+            .await
+            .expect("Failed to mset");
+        wait_for_l1_eviction(&peer, "user:1").await;
+        assert_eq!(
+            peer.get("user:1").await.expect("Failed to get"),
+            Some(b"newer".to_vec())
+        );
+    }
+
+    /// Wait for an invalidation published in the background to evict `key`
+    /// from `tiered`'s L1.
+    async fn wait_for_l1_eviction(
+        tiered: &TieredBackend<InMemoryBackend, InMemoryBackend>,
+        key: &str,
+    ) {
+        for _ in 0..50 {
+            if tiered.l1().get(key).await.expect("Failed to get").is_none() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(tiered.l1().get(key).await.expect("Failed to get").is_none());
+    }
+}
@@ -8,12 +8,126 @@ type KeyGeneratorFn = dyn Fn(&dyn std::fmt::Display) -> String + Send + Sync;
 /// Builder for cache keys.
 pub struct CacheKeyBuilder;
 
+/// Reference to the cache entry of an entity of any type.
+///
+/// Returned by `CacheEntity::cache_dependencies` to name entries of other
+/// entity types.
+///
+/// # Example
+///
+/// ```ignore
+/// let customer = EntryRef::of::<Customer>(&42);
+/// assert_eq!(customer.prefix(), "customer");
+/// assert_eq!(customer.id(), "42");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EntryRef {
+    prefix: &'static str,
+    id: String,
+    namespace: Option<String>,
+}
+
+impl EntryRef {
+    /// Reference the entry of the `T` with key `id`.
+    pub fn of<T: CacheEntity>(id: &T::Key) -> Self {
+        EntryRef {
+            prefix: T::cache_prefix(),
+            id: id.to_string(),
+            namespace: T::cache_namespace(id),
+        }
+    }
+
+    /// The entity type's `cache_prefix()`.
+    pub fn prefix(&self) -> &'static str {
+        self.prefix
+    }
+
+    /// The entity's key, formatted.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The entity's `cache_namespace()`, if any.
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+}
+
 impl CacheKeyBuilder {
     /// Build full cache key from entity type and ID.
     pub fn build<T: CacheEntity>(id: &T::Key) -> String {
         format!("{}:{}", T::cache_prefix(), id)
     }
 
+    /// Build the key of the set holding every cache key tagged with `tag`.
+    pub fn build_tag(tag: &str) -> String {
+        format!("cache_kit:tag:{}", tag)
+    }
+
+    /// Build a cache key that includes namespace generations.
+    ///
+    /// Format: `"{prefix}:g{generation}:{id}"`, or
+    /// `"{prefix}:g{generation}:{namespace}:g{namespace_generation}:{id}"` for
+    /// entities in a namespace (see `CacheEntity::cache_namespace`).
+    pub fn build_generational<T: CacheEntity>(
+        id: &T::Key,
+        generation: u64,
+        namespace: Option<(&str, u64)>,
+    ) -> String {
+        Self::build_generational_with_prefix(T::cache_prefix(), id, generation, namespace)
+    }
+
+    /// Build a cache key that includes namespace generations, with a custom prefix.
+    pub fn build_generational_with_prefix(
+        prefix: &str,
+        id: &dyn std::fmt::Display,
+        generation: u64,
+        namespace: Option<(&str, u64)>,
+    ) -> String {
+        match namespace {
+            Some((namespace, namespace_generation)) => format!(
+                "{}:g{}:{}:g{}:{}",
+                prefix, generation, namespace, namespace_generation, id
+            ),
+            None => format!("{}:g{}:{}", prefix, generation, id),
+        }
+    }
+
+    /// Build the key of the counter holding a namespace's generation.
+    ///
+    /// Entity types use their `cache_prefix()` as namespace, so type prefixes
+    /// and `CacheEntity::cache_namespace` values share one space.
+    pub fn build_generation(namespace: &str) -> String {
+        format!("cache_kit:gen:{}", namespace)
+    }
+
+    /// Build the key of the set holding the cache keys that depend on `cache_key`.
+    pub fn build_dependents(cache_key: &str) -> String {
+        format!("cache_kit:deps:{}", cache_key)
+    }
+
+    /// Build the key of the set holding the secondary-key pointers to `cache_key`.
+    pub fn build_pointers(cache_key: &str) -> String {
+        format!("cache_kit:ptrs:{}", cache_key)
+    }
+
+    /// Build the key of a query result (see `CacheQuery`).
+    ///
+    /// Format: `"query:{prefix}:{params}"`, or
+    /// `"query:{prefix}:g{generation}:{params}"` with the entity type's
+    /// generation.
+    pub fn build_query(prefix: &str, params: &str, generation: Option<u64>) -> String {
+        match generation {
+            Some(generation) => format!("query:{}:g{}:{}", prefix, generation, params),
+            None => format!("query:{}:{}", prefix, params),
+        }
+    }
+
+    /// Build the key of the set holding the query results that contain `cache_key`.
+    pub fn build_queries(cache_key: &str) -> String {
+        format!("cache_kit:queries:{}", cache_key)
+    }
+
     /// Build cache key with custom prefix.
     pub fn build_with_prefix(prefix: &str, id: &dyn std::fmt::Display) -> String {
         format!("{}:{}", prefix, id)
@@ -95,6 +209,55 @@ mod tests {
         assert_eq!(key, "test:entity_123");
     }
 
+    #[test]
+    fn test_cache_key_builder_generational() {
+        let id = "entity_123".to_string();
+        assert_eq!(
+            CacheKeyBuilder::build_generational::<TestEntity>(&id, 4, None),
+            "test:g4:entity_123"
+        );
+        assert_eq!(
+            CacheKeyBuilder::build_generational::<TestEntity>(&id, 4, Some(("tenant:7", 2))),
+            "test:g4:tenant:7:g2:entity_123"
+        );
+        assert_eq!(
+            CacheKeyBuilder::build_generation("test"),
+            "cache_kit:gen:test"
+        );
+    }
+
+    #[test]
+    fn test_entry_ref() {
+        let entry = EntryRef::of::<TestEntity>(&"entity_123".to_string());
+        assert_eq!(entry.prefix(), "test");
+        assert_eq!(entry.id(), "entity_123");
+        assert_eq!(entry.namespace(), None);
+        assert_eq!(
+            CacheKeyBuilder::build_dependents("test:entity_123"),
+            "cache_kit:deps:test:entity_123"
+        );
+    }
+
+    #[test]
+    fn test_cache_key_builder_query() {
+        assert_eq!(
+            CacheKeyBuilder::build_query("invoice_list", "42:20:0", None),
+            "query:invoice_list:42:20:0"
+        );
+        assert_eq!(
+            CacheKeyBuilder::build_query("invoice_list", "42:20:0", Some(3)),
+            "query:invoice_list:g3:42:20:0"
+        );
+        assert_eq!(
+            CacheKeyBuilder::build_queries("test:entity_123"),
+            "cache_kit:queries:test:entity_123"
+        );
+        assert_eq!(
+            CacheKeyBuilder::build_pointers("test:entity_123"),
+            "cache_kit:ptrs:test:entity_123"
+        );
+    }
+
     #[test]
     fn test_cache_key_builder_custom_prefix() {
         let key = CacheKeyBuilder::build_with_prefix("custom", &"123");
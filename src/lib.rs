@@ -77,24 +77,34 @@
 extern crate log;
 
 pub mod backend;
+pub mod coalesce;
 pub mod entity;
 pub mod error;
 pub mod expander;
 pub mod feed;
+pub mod index;
+pub mod invalidation;
 pub mod key;
 pub mod observability;
+pub mod query;
+pub mod refresh_ahead;
 pub mod repository;
+pub mod retry;
 pub mod serialization;
 pub mod service;
 pub mod strategy;
+pub mod warm;
+pub mod write_behind;
 
 // Re-exports for convenience
 pub use backend::CacheBackend;
 pub use entity::CacheEntity;
 pub use error::{Error, Result};
-pub use expander::{CacheExpander, OperationConfig};
+pub use expander::{CacheExpander, FailureMode, OperationConfig};
 pub use feed::CacheFeed;
-pub use repository::DataRepository;
+pub use query::CacheQuery;
+pub use repository::{DataRepository, WritableRepository};
+pub use retry::RetryPolicy;
 pub use service::CacheService;
 pub use strategy::CacheStrategy;
 
@@ -67,6 +67,22 @@
 //! | `Fixed` | Uniform cache duration | All entries expire in 5 minutes |
 //! | `Infinite` | Never expire | Static reference data (rarely used) |
 //! | `PerType` | Type-specific expiry | Users cache 1h, sessions 30m |
+//! | `Jittered` | Spread out expiry of bulk writes | Warmed entries expire over 60-66m |
+//!
+//! # Early Expiration
+//!
+//! Entries that share a TTL (e.g. bulk-loaded with `TtlPolicy::Fixed`) all expire
+//! together. [`EarlyExpiration`] implements the XFetch algorithm: each read may
+//! refresh an entry shortly before its TTL elapses, with a probability that grows
+//! as expiry approaches and with how long the entry took to load.
+//!
+//! ```ignore
+//! use cache_kit::observability::{EarlyExpiration, TtlPolicy};
+//!
+//! let expander = CacheExpander::new(backend)
+//!     .with_ttl_policy(TtlPolicy::Fixed(Duration::from_secs(3600)))
+//!     .with_early_expiration(EarlyExpiration::default());
+//! ```
 //!
 //! # Metrics Methods
 //!
@@ -76,9 +92,24 @@
 //! - `record_set()` - Cache write with operation duration
 //! - `record_delete()` - Cache delete with operation duration
 //! - `record_error()` - Operation failure with error message
+//! - `record_coalesced()` - Caller joined another caller's in-flight load
+//! - `record_negative_hit()` - Read answered by a negative-cache tombstone
+//! - `record_fail_open()` - Backend error tolerated in fail-open mode
+//! - `record_circuit_transition()` - Circuit breaker changed state
+//! - `record_tier_hit()` - Tier of a `TieredBackend` that served a hit
+//! - `record_flush()` / `record_flush_error()` - Write-behind batch persisted or failed
+//! - `record_queue_depth()` - Writes waiting in a write-behind queue
+//! - `record_heal()` - Unreadable cache entry evicted by self-healing
+//! - `record_refresh_ahead()` / `record_refresh_ahead_skipped()` - Hot entry reloaded, or let expire
+//! - `record_tag_invalidation()` - Entries evicted by tag
+//! - `record_generation_bump()` - Namespace invalidated by bumping its generation
+//! - `record_cascade()` - Dependents evicted along with an invalidated entry
 //!
 //! All methods receive the cache key and relevant timing/error information.
 
+use crate::backend::{CacheTier, CircuitState};
+use crate::serialization::EntryMetadata;
+use rand::Rng;
 use std::time::Duration;
 
 /// Trait for cache metrics collection.
@@ -107,6 +138,87 @@ pub trait CacheMetrics: Send + Sync {
     fn record_error(&self, key: &str, error: &str) {
         warn!("Cache ERROR for {}: {}", key, error);
     }
+
+    /// Record a caller that waited on another caller's in-flight load.
+    fn record_coalesced(&self, key: &str) {
+        debug!("Cache COALESCED: {}", key);
+    }
+
+    /// Record a read answered by a negative-cache tombstone.
+    ///
+    /// Negative hits are also reported as misses, since no entity is returned.
+    fn record_negative_hit(&self, key: &str) {
+        debug!("Cache NEGATIVE HIT: {}", key);
+    }
+
+    /// Record a backend error tolerated because the operation fails open.
+    fn record_fail_open(&self, key: &str, error: &str) {
+        warn!("Cache FAIL-OPEN for {}: {}", key, error);
+    }
+
+    /// Record a circuit breaker state transition.
+    fn record_circuit_transition(&self, from: CircuitState, to: CircuitState) {
+        info!("Circuit breaker TRANSITION: {} -> {}", from, to);
+    }
+
+    /// Record the tier of a `TieredBackend` that served a hit.
+    fn record_tier_hit(&self, key: &str, tier: CacheTier) {
+        debug!("Cache {} HIT: {}", tier, key);
+    }
+
+    /// Record a write-behind batch persisted to the repository.
+    fn record_flush(&self, batch_size: usize, duration: Duration) {
+        debug!(
+            "Write-behind FLUSH: {} entries took {:?}",
+            batch_size, duration
+        );
+    }
+
+    /// Record a failed attempt to persist a write-behind batch.
+    fn record_flush_error(&self, batch_size: usize, error: &str) {
+        warn!(
+            "Write-behind FLUSH ERROR ({} entries): {}",
+            batch_size, error
+        );
+    }
+
+    /// Record the number of writes waiting in a write-behind queue.
+    fn record_queue_depth(&self, depth: usize) {
+        debug!("Write-behind QUEUE DEPTH: {}", depth);
+    }
+
+    /// Record an unreadable cache entry evicted by self-healing.
+    fn record_heal(&self, key: &str, error: &str) {
+        warn!("Cache HEAL for {}: {}", key, error);
+    }
+
+    /// Record a hot entry reloaded ahead of its expiry.
+    fn record_refresh_ahead(&self, key: &str, duration: Duration) {
+        debug!("Cache REFRESH-AHEAD: {} took {:?}", key, duration);
+    }
+
+    /// Record an entry the refresh-ahead scheduler let expire.
+    fn record_refresh_ahead_skipped(&self, key: &str, reason: &str) {
+        debug!("Cache REFRESH-AHEAD SKIPPED: {} ({})", key, reason);
+    }
+
+    /// Record the entries evicted by invalidating a tag.
+    fn record_tag_invalidation(&self, tag: &str, evicted: usize) {
+        info!(
+            "Cache TAG INVALIDATION: {} evicted {} entries",
+            tag, evicted
+        );
+    }
+
+    /// Record a namespace invalidated by bumping its generation.
+    fn record_generation_bump(&self, namespace: &str, generation: u64) {
+        info!("Cache GENERATION BUMP: {} -> {}", namespace, generation);
+    }
+
+    /// Record dependents evicted because the entry at `key` was invalidated.
+    fn record_cascade(&self, key: &str, evicted: usize) {
+        info!("Cache CASCADE: {} ({} dependents)", key, evicted);
+    }
 }
 
 /// Default metrics implementation (no-op).
@@ -119,6 +231,20 @@ impl CacheMetrics for NoOpMetrics {
     fn record_set(&self, _key: &str, _duration: Duration) {}
     fn record_delete(&self, _key: &str, _duration: Duration) {}
     fn record_error(&self, _key: &str, _error: &str) {}
+    fn record_coalesced(&self, _key: &str) {}
+    fn record_negative_hit(&self, _key: &str) {}
+    fn record_fail_open(&self, _key: &str, _error: &str) {}
+    fn record_circuit_transition(&self, _from: CircuitState, _to: CircuitState) {}
+    fn record_tier_hit(&self, _key: &str, _tier: CacheTier) {}
+    fn record_flush(&self, _batch_size: usize, _duration: Duration) {}
+    fn record_flush_error(&self, _batch_size: usize, _error: &str) {}
+    fn record_queue_depth(&self, _depth: usize) {}
+    fn record_heal(&self, _key: &str, _error: &str) {}
+    fn record_refresh_ahead(&self, _key: &str, _duration: Duration) {}
+    fn record_refresh_ahead_skipped(&self, _key: &str, _reason: &str) {}
+    fn record_tag_invalidation(&self, _tag: &str, _evicted: usize) {}
+    fn record_generation_bump(&self, _namespace: &str, _generation: u64) {}
+    fn record_cascade(&self, _key: &str, _evicted: usize) {}
 }
 
 /// TTL (Time-to-Live) policy for cache entries.
@@ -134,22 +260,119 @@ pub enum TtlPolicy {
     /// No TTL (entries live forever)
     Infinite,
 
-    /// Custom per-type policy
+    /// Custom per-type policy, keyed on the cache prefix. Entities can also
+    /// declare their own TTL with `CacheEntity::cache_ttl`.
     PerType(fn(&str) -> Duration),
+
+    /// Another policy with random jitter added to its TTL
+    Jittered(Box<TtlPolicy>, TtlJitter),
 }
 
 impl TtlPolicy {
-    /// Get TTL for an entity type.
+    /// Wrap this policy so each TTL it returns is extended by a random jitter.
+    ///
+    /// ```
+    /// use cache_kit::observability::{TtlJitter, TtlPolicy};
+    /// use std::time::Duration;
+    ///
+    /// // Expire between 60 and 66 minutes after writing
+    /// let _policy = TtlPolicy::Fixed(Duration::from_secs(3600)).with_jitter(TtlJitter::Percent(0.1));
+    /// ```
+    pub fn with_jitter(self, jitter: TtlJitter) -> Self {
+        TtlPolicy::Jittered(Box::new(self), jitter)
+    }
+
+    /// Get TTL for an entity type, using the thread-local RNG for jitter.
     pub fn get_ttl(&self, entity_type: &str) -> Option<Duration> {
+        self.get_ttl_with(entity_type, &mut rand::rng())
+    }
+
+    /// Get TTL for an entity type, drawing any jitter from `rng`.
+    pub fn get_ttl_with<R: Rng + ?Sized>(
+        &self,
+        entity_type: &str,
+        rng: &mut R,
+    ) -> Option<Duration> {
         match self {
             TtlPolicy::Default => None,
             TtlPolicy::Fixed(d) => Some(*d),
             TtlPolicy::Infinite => None,
             TtlPolicy::PerType(f) => Some(f(entity_type)),
+            TtlPolicy::Jittered(policy, jitter) => policy
+                .get_ttl_with(entity_type, rng)
+                .map(|ttl| ttl + jitter.max_for(ttl).mul_f64(rng.random::<f64>())),
         }
     }
 }
 
+/// Random extension applied to a TTL by `TtlPolicy::Jittered`.
+///
+/// Jitter only lengthens a TTL, so entries never expire earlier than the
+/// wrapped policy says. Policies without a TTL (`Default`, `Infinite`) are
+/// left unchanged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TtlJitter {
+    /// Up to this fraction of the TTL, clamped to `0.0..=1.0`.
+    Percent(f64),
+
+    /// Up to this absolute duration.
+    Range(Duration),
+}
+
+impl TtlJitter {
+    /// Largest extension this jitter can add to `ttl`.
+    fn max_for(&self, ttl: Duration) -> Duration {
+        match self {
+            TtlJitter::Percent(fraction) => ttl.mul_f64(fraction.clamp(0.0, 1.0)),
+            TtlJitter::Range(range) => *range,
+        }
+    }
+}
+
+/// Probabilistic early expiration (XFetch).
+///
+/// On a cache hit, an entry is treated as a miss when
+/// `now + compute_time * beta * -ln(random) >= expiry`, where `random` is drawn
+/// uniformly from `(0, 1]`. Expensive entries and entries close to expiry are
+/// more likely to be refreshed early, which spreads out recomputation of entries
+/// that would otherwise expire at the same moment.
+///
+/// Only entries written with a TTL and load-time metadata are considered.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EarlyExpiration {
+    /// Eagerness of early refresh. `1.0` is the recommended default; values above
+    /// `1.0` refresh earlier, values below `1.0` refresh later.
+    pub beta: f64,
+}
+
+impl EarlyExpiration {
+    /// Create an early expiration policy with the given `beta`.
+    pub fn new(beta: f64) -> Self {
+        EarlyExpiration { beta }
+    }
+
+    /// Decide whether to refresh an entry now, using the thread-local RNG.
+    pub fn should_refresh(&self, metadata: &EntryMetadata) -> bool {
+        self.should_refresh_with(metadata, 1.0 - rand::random::<f64>())
+    }
+
+    /// Decide whether to refresh an entry now, given a random draw in `(0, 1]`.
+    pub fn should_refresh_with(&self, metadata: &EntryMetadata, random: f64) -> bool {
+        let Some(remaining) = metadata.remaining() else {
+            return false;
+        };
+
+        let gap_ms = metadata.compute_ms as f64 * self.beta * -random.ln();
+        gap_ms >= remaining.as_millis() as f64
+    }
+}
+
+impl Default for EarlyExpiration {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -186,4 +409,67 @@ mod tests {
         );
         assert_eq!(policy.get_ttl("other"), Some(Duration::from_secs(1800)));
     }
+
+    #[test]
+    fn test_ttl_policy_jittered() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let base = Duration::from_secs(3600);
+        let percent = TtlPolicy::Fixed(base).with_jitter(TtlJitter::Percent(0.1));
+        let range = TtlPolicy::Fixed(base).with_jitter(TtlJitter::Range(Duration::from_secs(30)));
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let ttls: Vec<_> = (0..100)
+            .map(|_| percent.get_ttl_with("any", &mut rng).expect("TTL missing"))
+            .collect();
+        assert!(ttls
+            .iter()
+            .all(|ttl| (base..=base + Duration::from_secs(360)).contains(ttl)));
+        assert!(ttls.iter().any(|ttl| *ttl != ttls[0]));
+
+        for _ in 0..100 {
+            let ttl = range.get_ttl("any").expect("TTL missing");
+            assert!((base..=base + Duration::from_secs(30)).contains(&ttl));
+        }
+
+        // Same seed, same TTLs
+        let mut replay = StdRng::seed_from_u64(7);
+        assert_eq!(percent.get_ttl_with("any", &mut replay), Some(ttls[0]));
+
+        // No TTL stays no TTL
+        let infinite = TtlPolicy::Infinite.with_jitter(TtlJitter::Percent(0.5));
+        assert_eq!(infinite.get_ttl("any"), None);
+    }
+
+    #[test]
+    fn test_early_expiration_near_expiry() {
+        let policy = EarlyExpiration::default();
+        let metadata = EntryMetadata::now()
+            .with_ttl(Some(Duration::from_millis(100)))
+            .with_compute_time(Duration::from_millis(500));
+
+        // A draw of 0.5 gives a ~350ms head start, well past the 100ms left
+        assert!(policy.should_refresh_with(&metadata, 0.5));
+        // A draw of 1.0 never refreshes early
+        assert!(!policy.should_refresh_with(&metadata, 1.0));
+    }
+
+    #[test]
+    fn test_early_expiration_far_from_expiry() {
+        let policy = EarlyExpiration::default();
+        let metadata = EntryMetadata::now()
+            .with_ttl(Some(Duration::from_secs(3600)))
+            .with_compute_time(Duration::from_millis(10));
+
+        assert!(!policy.should_refresh_with(&metadata, 0.5));
+    }
+
+    #[test]
+    fn test_early_expiration_without_ttl() {
+        let policy = EarlyExpiration::new(10.0);
+        let metadata = EntryMetadata::now().with_compute_time(Duration::from_secs(10));
+
+        assert!(!policy.should_refresh_with(&metadata, 0.01));
+    }
 }
@@ -0,0 +1,130 @@
+//! Query-result caching.
+//!
+//! A [`CacheQuery`] describes a query over one entity type: a prefix, and the
+//! parameters that identify one result, e.g. a customer's invoices with a limit
+//! and offset. `CacheExpander::query` (or `CacheService::query`) caches the
+//! result under `"query:{prefix}:{params}"` and loads it with a closure on a
+//! miss.
+//!
+//! By default a result stores the cache keys of its members, which are written
+//! to the entity cache alongside it and read back with one `mget`
+//! (`QueryStorage::Ids`). Results can instead store the whole page of entities
+//! (`QueryStorage::Pages`).
+//!
+//! A result is evicted when one of its members is written or invalidated
+//! through the expander or service (`write_through`, `write_around`,
+//! `delete_through`, `invalidate`, write-behind writes). Results a new entity
+//! should appear in are not known from the entity alone: evict them with
+//! `CacheQuery::query_tags` and `invalidate_tag`, or with `invalidate_query`.
+//! With generation counters, bumping the entity type's generation drops its
+//! query results too.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use cache_kit::CacheQuery;
+//!
+//! struct InvoiceList {
+//!     customer_id: Uuid,
+//!     limit: i64,
+//!     offset: i64,
+//! }
+//!
+//! impl CacheQuery for InvoiceList {
+//!     type Entity = Invoice;
+//!
+//!     fn query_prefix() -> &'static str {
+//!         "invoice_list"
+//!     }
+//!
+//!     fn query_params(&self) -> String {
+//!         format!("{}:{}:{}", self.customer_id, self.limit, self.offset)
+//!     }
+//!
+//!     fn query_tags(&self) -> Vec<String> {
+//!         vec![format!("customer:{}", self.customer_id)]
+//!     }
+//! }
+//!
+//! let query = InvoiceList { customer_id, limit: 20, offset: 0 };
+//! let invoices = cache
+//!     .query(&query, || async { db.list_invoices(&customer_id, 20, 0).await })
+//!     .await?;
+//! ```
+
+use crate::entity::CacheEntity;
+use serde::{Deserialize, Serialize};
+
+/// A cacheable query over one entity type.
+pub trait CacheQuery: Send + Sync {
+    /// Entity type the query returns.
+    type Entity: CacheEntity;
+
+    /// Return the cache prefix for this query.
+    ///
+    /// Example: "invoice_list". Final cache key format:
+    /// `"query:{prefix}:{params}"`
+    fn query_prefix() -> &'static str;
+
+    /// Return the parameters identifying this result.
+    ///
+    /// Example: `format!("{}:{}:{}", self.customer_id, self.limit, self.offset)`
+    fn query_params(&self) -> String;
+
+    /// Optional: How results are stored. Default: `QueryStorage::Ids`.
+    fn storage() -> QueryStorage {
+        QueryStorage::Ids
+    }
+
+    /// Optional: Tags for group invalidation, as in `CacheEntity::cache_tags`.
+    ///
+    /// Example: `vec![format!("customer:{}", self.customer_id)]`, so that
+    /// `invalidate_tag` evicts the list when the customer gets a new invoice.
+    fn query_tags(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// How a [`CacheQuery`] result is stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum QueryStorage {
+    /// Store the members' cache keys and read the members from the entity
+    /// cache (default). Members stay current; if one has left the entity
+    /// cache, the whole result is reloaded.
+    #[default]
+    Ids,
+
+    /// Store the members with the result. Reads take one `get`, but members
+    /// are only as current as the result.
+    Pages,
+}
+
+/// Cached form of a query result.
+///
+/// Serialized with `P = &[T]` and deserialized with `P = Vec<T>`, which share
+/// a wire format.
+#[derive(Serialize, Deserialize)]
+pub(crate) enum QueryEntry<P> {
+    Ids(Vec<String>),
+    Page(P),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serialization::{deserialize_from_cache, serialize_for_cache};
+
+    #[test]
+    fn test_query_entry_page_round_trip() {
+        let page = vec!["a".to_string(), "b".to_string()];
+        let bytes =
+            serialize_for_cache(&QueryEntry::Page(page.as_slice())).expect("Failed to serialize");
+
+        match deserialize_from_cache::<QueryEntry<Vec<String>>>(&bytes)
+            .expect("Failed to deserialize")
+        {
+            QueryEntry::Page(decoded) => assert_eq!(decoded, page),
+            QueryEntry::Ids(_) => panic!("Expected a page"),
+        }
+    }
+}
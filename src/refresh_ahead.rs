@@ -0,0 +1,716 @@
+//! Refresh-ahead scheduler that keeps hot keys warm.
+//!
+//! A [`RefreshAhead`] counts reads per key, whether made through the scheduler
+//! or through the `CacheExpander` / `CacheService` it was created from. Keys
+//! read at least `min_hits` times in the current (or previous) `window` are
+//! *hot*: shortly before their cache entry's TTL elapses, the scheduler reloads
+//! them from the registered [`DataRepository`] and rewrites the entry, so
+//! readers never see the miss.
+//!
+//! Reloads run on spawned tasks, at most `max_concurrent` at a time. A hot key
+//! that comes due while every slot is busy is skipped until the next check, and
+//! a key that has gone cold is dropped instead of being refreshed; both are
+//! reported through `CacheMetrics::record_refresh_ahead_skipped`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use cache_kit::refresh_ahead::RefreshAheadConfig;
+//!
+//! let products = cache.refresh_ahead(product_repo, RefreshAheadConfig::default());
+//!
+//! // Reads through the service or the scheduler count towards hotness
+//! let product = cache.execute(&mut feeder, &product_repo, CacheStrategy::Refresh).await?;
+//! let product = products.get(&product_id).await?;
+//!
+//! // On shutdown
+//! products.shutdown().await;
+//! ```
+
+use crate::backend::CacheBackend;
+use crate::entity::CacheEntity;
+use crate::error::Result;
+use crate::expander::{AccessRecorder, CacheExpander, OperationConfig};
+use crate::key::CacheKeyBuilder;
+use crate::repository::DataRepository;
+use crate::serialization;
+use crate::strategy::CacheStrategy;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::{Notify, Semaphore};
+
+/// Configuration for a [`RefreshAhead`] scheduler.
+///
+/// # Example
+///
+/// ```ignore
+/// let config = RefreshAheadConfig::default()
+///     .with_min_hits(10)
+///     .with_lead_time(Duration::from_secs(30));
+/// ```
+#[derive(Clone, Debug)]
+pub struct RefreshAheadConfig {
+    /// Reads within one window that make a key hot. Default: 3.
+    pub min_hits: u32,
+
+    /// Length of the access-counting window. Default: 60 seconds.
+    pub window: Duration,
+
+    /// How long before expiry a hot entry is reloaded. Default: 10 seconds.
+    pub lead_time: Duration,
+
+    /// How often keys are checked for upcoming expiry. Default: 1 second.
+    pub check_interval: Duration,
+
+    /// Reloads allowed to run at the same time. Default: 4.
+    pub max_concurrent: usize,
+
+    /// Keys tracked at once; reads of further keys are not counted. Default: 10,000.
+    pub max_tracked_keys: usize,
+}
+
+impl Default for RefreshAheadConfig {
+    fn default() -> Self {
+        RefreshAheadConfig {
+            min_hits: 3,
+            window: Duration::from_secs(60),
+            lead_time: Duration::from_secs(10),
+            check_interval: Duration::from_secs(1),
+            max_concurrent: 4,
+            max_tracked_keys: 10_000,
+        }
+    }
+}
+
+impl RefreshAheadConfig {
+    /// Set the reads per window that make a key hot.
+    pub fn with_min_hits(mut self, hits: u32) -> Self {
+        self.min_hits = hits.max(1);
+        self
+    }
+
+    /// Set the access-counting window.
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Set how long before expiry hot entries are reloaded.
+    pub fn with_lead_time(mut self, lead_time: Duration) -> Self {
+        self.lead_time = lead_time;
+        self
+    }
+
+    /// Set how often keys are checked.
+    pub fn with_check_interval(mut self, interval: Duration) -> Self {
+        self.check_interval = interval;
+        self
+    }
+
+    /// Set the number of reloads allowed to run at the same time.
+    pub fn with_max_concurrent(mut self, max: usize) -> Self {
+        self.max_concurrent = max.max(1);
+        self
+    }
+
+    /// Set the number of keys tracked at once.
+    pub fn with_max_tracked_keys(mut self, max: usize) -> Self {
+        self.max_tracked_keys = max;
+        self
+    }
+}
+
+/// Scheduler that reloads hot entries of one entity type before they expire.
+///
+/// Cheap to clone; clones share the same scheduler. Create one with
+/// `CacheService::refresh_ahead()` or [`RefreshAhead::new`]. Must be created
+/// inside a Tokio runtime, which runs the scheduling task.
+pub struct RefreshAhead<B, T, R>
+where
+    B: CacheBackend,
+    T: CacheEntity,
+    R: DataRepository<T>,
+{
+    inner: Arc<Inner<B, T, R>>,
+}
+
+struct Inner<B, T, R>
+where
+    B: CacheBackend,
+    T: CacheEntity,
+    R: DataRepository<T>,
+{
+    expander: Arc<CacheExpander<B>>,
+    repository: R,
+    config: RefreshAheadConfig,
+    state: Mutex<State<T::Key>>,
+    permits: Arc<Semaphore>,
+    stopped: AtomicBool,
+    wake: Arc<Notify>,
+}
+
+/// Access counts and expiry of tracked keys, by cache key.
+struct State<K> {
+    keys: HashMap<String, Tracked<K>>,
+    window_started: Instant,
+}
+
+struct Tracked<K> {
+    id: K,
+    hits: u32,
+    previous_hits: u32,
+    expiry: Expiry,
+    refreshing: bool,
+}
+
+/// What the scheduler knows about a tracked entry's expiry.
+#[derive(Clone, Copy)]
+enum Expiry {
+    /// Not looked up yet, or reloaded since it was.
+    Unknown,
+    /// Entry has no TTL.
+    Never,
+    /// Entry expires at this instant.
+    At(Instant),
+}
+
+impl Expiry {
+    /// Whether the entry expires within `lead_time` of `now`.
+    fn is_due(self, now: Instant, lead_time: Duration) -> bool {
+        matches!(self, Expiry::At(at) if at.saturating_duration_since(now) <= lead_time)
+    }
+}
+
+impl<B, T, R> Clone for RefreshAhead<B, T, R>
+where
+    B: CacheBackend,
+    T: CacheEntity,
+    R: DataRepository<T>,
+{
+    fn clone(&self) -> Self {
+        RefreshAhead {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<B, T, R> RefreshAhead<B, T, R>
+where
+    B: CacheBackend + 'static,
+    T: CacheEntity + 'static,
+    R: DataRepository<T> + 'static,
+{
+    /// Create a scheduler that reads through `expander` and reloads from `repository`.
+    ///
+    /// Every later read of a `T` through `expander` is counted. Spawns the
+    /// scheduling task, which stops on [`stop`](Self::stop) or once every
+    /// handle to the scheduler has been dropped.
+    pub fn new(expander: Arc<CacheExpander<B>>, repository: R, config: RefreshAheadConfig) -> Self {
+        let wake = Arc::new(Notify::new());
+        let inner = Arc::new(Inner {
+            expander,
+            repository,
+            permits: Arc::new(Semaphore::new(config.max_concurrent)),
+            config,
+            state: Mutex::new(State {
+                keys: HashMap::new(),
+                window_started: Instant::now(),
+            }),
+            stopped: AtomicBool::new(false),
+            wake: Arc::clone(&wake),
+        });
+
+        let recorder: Weak<dyn AccessRecorder> = Arc::downgrade(&inner) as _;
+        inner.expander.add_access_recorder::<T>(recorder);
+
+        tokio::spawn(Self::run(
+            Arc::downgrade(&inner),
+            wake,
+            inner.config.check_interval,
+        ));
+
+        RefreshAhead { inner }
+    }
+
+    /// Scheduling loop; holds only a weak reference between checks.
+    async fn run(inner: Weak<Inner<B, T, R>>, wake: Arc<Notify>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = wake.notified() => {}
+            }
+
+            let Some(inner) = inner.upgrade() else {
+                debug!("Refresh-ahead scheduler dropped, stopping");
+                return;
+            };
+            if inner.stopped.load(Ordering::SeqCst) {
+                debug!("Refresh-ahead scheduler stopped");
+                return;
+            }
+            Self::check(&inner).await;
+        }
+    }
+
+    /// Reload every hot key that is about to expire.
+    async fn check(inner: &Arc<Inner<B, T, R>>) {
+        let metrics = inner.expander.metrics();
+        let now = Instant::now();
+
+        let mut unknown = Vec::new();
+        let mut due = Vec::new();
+        {
+            let mut state = inner.state();
+            state.rotate(inner.config.window, now);
+
+            let lead_time = inner.config.lead_time;
+            let min_hits = inner.config.min_hits;
+            state.keys.retain(|cache_key, tracked| {
+                if tracked.refreshing {
+                    return true;
+                }
+                let hot = tracked.hits.max(tracked.previous_hits) >= min_hits;
+                match tracked.expiry {
+                    Expiry::Unknown if hot => unknown.push((cache_key.clone(), tracked.id.clone())),
+                    expiry if expiry.is_due(now, lead_time) => {
+                        if !hot {
+                            metrics.record_refresh_ahead_skipped(cache_key, "not hot");
+                            return false;
+                        }
+                        due.push(cache_key.clone());
+                    }
+                    _ => {}
+                }
+                true
+            });
+        }
+
+        for (cache_key, id) in unknown {
+            match Self::lookup_expiry(inner, &id).await {
+                // Hot but not cached: load it now
+                Ok(None) => due.push(cache_key),
+                Ok(Some(expiry)) => {
+                    if let Some(tracked) = inner.state().keys.get_mut(&cache_key) {
+                        tracked.expiry = expiry;
+                    }
+                    if expiry.is_due(now, inner.config.lead_time) {
+                        due.push(cache_key);
+                    }
+                }
+                Err(e) => debug!("Refresh-ahead lookup of {} failed: {}", cache_key, e),
+            }
+        }
+
+        for cache_key in due {
+            let Ok(permit) = Arc::clone(&inner.permits).try_acquire_owned() else {
+                metrics.record_refresh_ahead_skipped(&cache_key, "concurrency limit");
+                continue;
+            };
+            let id = {
+                let mut state = inner.state();
+                let Some(tracked) = state.keys.get_mut(&cache_key) else {
+                    continue;
+                };
+                tracked.refreshing = true;
+                tracked.id.clone()
+            };
+
+            let inner = Arc::clone(inner);
+            tokio::spawn(async move {
+                Self::reload(&inner, &cache_key, &id).await;
+                if let Some(tracked) = inner.state().keys.get_mut(&cache_key) {
+                    tracked.refreshing = false;
+                    tracked.expiry = Expiry::Unknown;
+                }
+                drop(permit);
+            });
+        }
+    }
+
+    /// Read a cached entry's expiry, or `None` if the entity is not cached.
+    async fn lookup_expiry(inner: &Inner<B, T, R>, id: &T::Key) -> Result<Option<Expiry>> {
+        let expander = &inner.expander;
+        let cache_key = expander
+            .cache_key::<T>(id, &OperationConfig::default())
+            .await?;
+        let Some(bytes) = expander.backend().get(&cache_key).await? else {
+            return Ok(None);
+        };
+        if serialization::is_tombstone(&bytes) {
+            return Ok(Some(Expiry::Never));
+        }
+
+        let (_, metadata) = serialization::deserialize_with_metadata::<T>(&bytes)?;
+        Ok(Some(match metadata.and_then(|m| m.remaining()) {
+            Some(remaining) => Expiry::At(Instant::now() + remaining),
+            None => Expiry::Never,
+        }))
+    }
+
+    /// Reload one entity from the repository and rewrite its cache entry.
+    async fn reload(inner: &Inner<B, T, R>, cache_key: &str, id: &T::Key) {
+        let metrics = inner.expander.metrics();
+        let started = Instant::now();
+
+        match inner.repository.fetch_by_id(id).await {
+            Ok(Some(entity)) => {
+                let expander = &inner.expander;
+                let stored = match expander
+                    .cache_key::<T>(id, &OperationConfig::default())
+                    .await
+                {
+                    Ok(entry_key) => {
+                        expander
+                            .store_entity(&entry_key, &entity, started.elapsed())
+                            .await
+                    }
+                    Err(e) => Err(e),
+                };
+                match stored {
+                    Ok(()) => {
+                        metrics.record_refresh_ahead(cache_key, started.elapsed());
+                        debug!("✓ Refreshed {} ahead of expiry", cache_key);
+                    }
+                    Err(e) => warn!("Refresh-ahead of {} failed to store: {}", cache_key, e),
+                }
+            }
+            Ok(None) => {
+                debug!("Entity for {} no longer exists, untracking", cache_key);
+                inner.state().keys.remove(cache_key);
+            }
+            Err(e) => {
+                metrics.record_error(cache_key, &e.to_string());
+                warn!("Refresh-ahead of {} failed: {}", cache_key, e);
+            }
+        }
+    }
+}
+
+impl<B, T, R> RefreshAhead<B, T, R>
+where
+    B: CacheBackend,
+    T: CacheEntity,
+    R: DataRepository<T>,
+{
+    /// Read an entity with `CacheStrategy::Refresh`.
+    ///
+    /// # Errors
+    ///
+    /// Same error cases as `CacheExpander::get_or_load`.
+    pub async fn get(&self, id: &T::Key) -> Result<Option<T>> {
+        self.inner
+            .expander
+            .get_or_load::<T, _>(id.clone(), CacheStrategy::Refresh, || {
+                self.inner.repository.fetch_by_id(id)
+            })
+            .await
+    }
+
+    /// Count a read of `id` made without the expander, e.g. from another cache.
+    pub fn record_access(&self, id: &T::Key) {
+        self.inner.record(id);
+    }
+
+    /// Number of keys currently hot.
+    pub fn hot_keys(&self) -> usize {
+        let min_hits = self.inner.config.min_hits;
+        self.inner
+            .state()
+            .keys
+            .values()
+            .filter(|tracked| tracked.hits.max(tracked.previous_hits) >= min_hits)
+            .count()
+    }
+
+    /// Stop scheduling reloads. Reloads already running are not interrupted.
+    pub fn stop(&self) {
+        self.inner.stopped.store(true, Ordering::SeqCst);
+        self.inner.wake.notify_one();
+    }
+
+    /// Stop scheduling reloads and wait for running reloads to finish.
+    pub async fn shutdown(&self) {
+        self.stop();
+        let slots = self.inner.config.max_concurrent as u32;
+        // The semaphore is never closed, so this only waits
+        let _ = self.inner.permits.acquire_many(slots).await;
+    }
+}
+
+impl<B, T, R> Inner<B, T, R>
+where
+    B: CacheBackend,
+    T: CacheEntity,
+    R: DataRepository<T>,
+{
+    fn state(&self) -> std::sync::MutexGuard<'_, State<T::Key>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Count a read of `id`.
+    fn record(&self, id: &T::Key) {
+        let cache_key = CacheKeyBuilder::build::<T>(id);
+        let mut state = self.state();
+        if let Some(tracked) = state.keys.get_mut(&cache_key) {
+            tracked.hits = tracked.hits.saturating_add(1);
+            return;
+        }
+        if state.keys.len() < self.config.max_tracked_keys {
+            state.keys.insert(
+                cache_key,
+                Tracked {
+                    id: id.clone(),
+                    hits: 1,
+                    previous_hits: 0,
+                    expiry: Expiry::Unknown,
+                    refreshing: false,
+                },
+            );
+        }
+    }
+}
+
+impl<B, T, R> AccessRecorder for Inner<B, T, R>
+where
+    B: CacheBackend,
+    T: CacheEntity,
+    R: DataRepository<T>,
+{
+    fn record_access(&self, id: &dyn Any) {
+        if let Some(id) = id.downcast_ref::<T::Key>() {
+            self.record(id);
+        }
+    }
+}
+
+impl<K> State<K> {
+    /// Start a new counting window if the current one has ended.
+    ///
+    /// Keys not read in either of the last two windows are dropped.
+    fn rotate(&mut self, window: Duration, now: Instant) {
+        if now.duration_since(self.window_started) < window {
+            return;
+        }
+        self.window_started = now;
+        self.keys.retain(|_, tracked| {
+            tracked.previous_hits = tracked.hits;
+            tracked.hits = 0;
+            tracked.previous_hits > 0 || tracked.refreshing
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::InMemoryBackend;
+    use crate::observability::{CacheMetrics, TtlPolicy};
+    use crate::repository::InMemoryRepository;
+    use serde::{Deserialize, Serialize};
+    use std::sync::atomic::AtomicUsize;
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Product {
+        id: String,
+        price: u64,
+    }
+
+    impl CacheEntity for Product {
+        type Key = String;
+
+        fn cache_key(&self) -> Self::Key {
+            self.id.clone()
+        }
+
+        fn cache_prefix() -> &'static str {
+            "product"
+        }
+    }
+
+    /// Repository that counts fetches.
+    struct CountingRepository {
+        inner: InMemoryRepository<Product>,
+        fetches: Arc<AtomicUsize>,
+    }
+
+    impl DataRepository<Product> for CountingRepository {
+        async fn fetch_by_id(&self, id: &String) -> Result<Option<Product>> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            self.inner.fetch_by_id(id).await
+        }
+    }
+
+    #[derive(Default)]
+    struct RefreshMetrics {
+        refreshed: Arc<AtomicUsize>,
+        skipped: Arc<AtomicUsize>,
+    }
+
+    impl CacheMetrics for RefreshMetrics {
+        fn record_refresh_ahead(&self, _key: &str, _duration: Duration) {
+            self.refreshed.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn record_refresh_ahead_skipped(&self, _key: &str, _reason: &str) {
+            self.skipped.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn setup(
+        ttl: Duration,
+        config: RefreshAheadConfig,
+    ) -> (
+        RefreshAhead<InMemoryBackend, Product, CountingRepository>,
+        InMemoryBackend,
+        Arc<AtomicUsize>,
+        RefreshMetrics,
+    ) {
+        let backend = InMemoryBackend::new();
+        let metrics = RefreshMetrics::default();
+        let expander = CacheExpander::new(backend.clone())
+            .with_ttl_policy(TtlPolicy::Fixed(ttl))
+            .with_metrics(Box::new(RefreshMetrics {
+                refreshed: metrics.refreshed.clone(),
+                skipped: metrics.skipped.clone(),
+            }));
+
+        let mut inner = InMemoryRepository::new();
+        for id in ["hot", "cold"] {
+            inner.insert(
+                id.to_string(),
+                Product {
+                    id: id.to_string(),
+                    price: 100,
+                },
+            );
+        }
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let repository = CountingRepository {
+            inner,
+            fetches: fetches.clone(),
+        };
+
+        let scheduler = RefreshAhead::new(Arc::new(expander), repository, config);
+        (scheduler, backend, fetches, metrics)
+    }
+
+    #[tokio::test]
+    async fn test_refresh_ahead_keeps_hot_keys_warm() {
+        let config = RefreshAheadConfig::default()
+            .with_min_hits(3)
+            .with_lead_time(Duration::from_millis(150))
+            .with_check_interval(Duration::from_millis(20));
+        let (scheduler, backend, fetches, metrics) = setup(Duration::from_millis(300), config);
+
+        let hot = "hot".to_string();
+        for _ in 0..3 {
+            let product = scheduler.get(&hot).await.expect("Failed to get");
+            assert_eq!(product.expect("Product not found").price, 100);
+        }
+        scheduler
+            .get(&"cold".to_string())
+            .await
+            .expect("Failed to get");
+        assert_eq!(scheduler.hot_keys(), 1);
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+        // Well past the original TTL, the hot entry is still cached
+        tokio::time::sleep(Duration::from_millis(700)).await;
+        assert!(backend
+            .get("product:hot")
+            .await
+            .expect("Failed to get")
+            .is_some());
+        assert!(metrics.refreshed.load(Ordering::SeqCst) >= 2);
+
+        scheduler.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_refresh_ahead_stop() {
+        let config = RefreshAheadConfig::default()
+            .with_min_hits(1)
+            .with_lead_time(Duration::from_millis(150))
+            .with_check_interval(Duration::from_millis(20));
+        let (scheduler, backend, _, metrics) = setup(Duration::from_millis(200), config);
+
+        scheduler
+            .get(&"hot".to_string())
+            .await
+            .expect("Failed to get");
+        scheduler.shutdown().await;
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert_eq!(metrics.refreshed.load(Ordering::SeqCst), 0);
+        assert!(backend
+            .get("product:hot")
+            .await
+            .expect("Failed to get")
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_ahead_counts_expander_reads() {
+        let config = RefreshAheadConfig::default()
+            .with_min_hits(2)
+            .with_check_interval(Duration::from_secs(3600));
+        let (scheduler, _, _, _) = setup(Duration::from_secs(60), config);
+        let expander = Arc::clone(&scheduler.inner.expander);
+        let hot = "hot".to_string();
+
+        // Reads that bypass the scheduler still count
+        expander
+            .get::<Product>(hot.clone())
+            .await
+            .expect("Failed to get");
+        assert_eq!(scheduler.hot_keys(), 0);
+        expander
+            .with_many::<Product, _>(
+                &[hot.clone(), "cold".to_string()],
+                &InMemoryRepository::new(),
+                CacheStrategy::Refresh,
+            )
+            .await
+            .expect("Failed to get");
+        assert_eq!(scheduler.hot_keys(), 1);
+
+        // A dropped scheduler stops receiving reads
+        scheduler.shutdown().await;
+        drop(scheduler);
+        expander.get::<Product>(hot).await.expect("Failed to get");
+    }
+
+    #[test]
+    fn test_refresh_ahead_window_rotation() {
+        let mut state = State {
+            keys: HashMap::new(),
+            window_started: Instant::now(),
+        };
+        state.keys.insert(
+            "product:1".to_string(),
+            Tracked {
+                id: "1".to_string(),
+                hits: 5,
+                previous_hits: 0,
+                expiry: Expiry::Unknown,
+                refreshing: false,
+            },
+        );
+
+        let window = Duration::from_secs(60);
+        let later = state.window_started + window;
+        state.rotate(window, later);
+        let tracked = &state.keys["product:1"];
+        assert_eq!((tracked.hits, tracked.previous_hits), (0, 5));
+
+        // Not read for two windows: dropped
+        state.rotate(window, later + window);
+        assert!(state.keys.is_empty());
+    }
+}
@@ -47,6 +47,12 @@
 //! }
 //! ```
 //!
+//! # Writing Through the Cache
+//!
+//! Repositories that can persist changes implement [`WritableRepository`] as well.
+//! `CacheService::write_through`, `write_around` and `delete_through` use it to
+//! keep the cache consistent with mutations without an extra database read.
+//!
 //! # Error Handling
 //!
 //! When implementing the trait for real databases, return `Err` for:
@@ -58,6 +64,9 @@
 
 use crate::entity::CacheEntity;
 use crate::error::Result;
+use crate::index::{SecondaryIndex, SecondaryKey};
+use std::future::Future;
+use std::sync::Arc;
 
 /// Trait for data repository implementations.
 ///
@@ -68,7 +77,9 @@ use crate::error::Result;
 ///
 /// This trait is designed to be mockable. Implement it with your database client,
 /// or use `InMemoryRepository` provided in this module for testing.
-#[allow(async_fn_in_trait)]
+///
+/// The returned futures are `Send` so fetches can run on spawned tasks (for
+/// example, background revalidation); implementations can still use `async fn`.
 pub trait DataRepository<T: CacheEntity>: Send + Sync {
     /// Fetch entity by ID from primary data source.
     ///
@@ -81,7 +92,7 @@ pub trait DataRepository<T: CacheEntity>: Send + Sync {
     ///
     /// # Errors
     /// Returns `Err` if data source is unavailable or fetch fails
-    async fn fetch_by_id(&self, id: &T::Key) -> Result<Option<T>>;
+    fn fetch_by_id(&self, id: &T::Key) -> impl Future<Output = Result<Option<T>>> + Send;
 
     /// Batch fetch entities by IDs (optional optimization).
     ///
@@ -90,32 +101,176 @@ pub trait DataRepository<T: CacheEntity>: Send + Sync {
     ///
     /// # Errors
     /// Returns `Err` if data source is unavailable or fetch fails
-    async fn fetch_by_ids(&self, ids: &[T::Key]) -> Result<Vec<Option<T>>> {
-        let mut results = Vec::with_capacity(ids.len());
-        for id in ids {
-            results.push(self.fetch_by_id(id).await?);
+    fn fetch_by_ids(&self, ids: &[T::Key]) -> impl Future<Output = Result<Vec<Option<T>>>> + Send {
+        async move {
+            let mut results = Vec::with_capacity(ids.len());
+            for id in ids {
+                results.push(self.fetch_by_id(id).await?);
+            }
+            Ok(results)
         }
-        Ok(results)
     }
 
     /// Count total entities (optional, for statistics).
     ///
     /// # Errors
     /// Returns `Err` if not implemented or if data source operation fails
-    async fn count(&self) -> Result<u64> {
-        Err(crate::error::Error::NotImplemented(
-            "count not implemented".to_string(),
-        ))
+    fn count(&self) -> impl Future<Output = Result<u64>> + Send {
+        async {
+            Err(crate::error::Error::NotImplemented(
+                "count not implemented".to_string(),
+            ))
+        }
     }
 
     /// Optional: Get all entities (use sparingly, potentially large result).
     ///
     /// # Errors
     /// Returns `Err` if not implemented or if data source operation fails
-    async fn fetch_all(&self) -> Result<Vec<T>> {
-        Err(crate::error::Error::NotImplemented(
-            "fetch_all not implemented for this repository".to_string(),
-        ))
+    fn fetch_all(&self) -> impl Future<Output = Result<Vec<T>>> + Send {
+        async {
+            Err(crate::error::Error::NotImplemented(
+                "fetch_all not implemented for this repository".to_string(),
+            ))
+        }
+    }
+
+    /// Optional: Get one page of entities, for cache warming.
+    ///
+    /// Returns up to `limit` entities starting at position `offset` in a
+    /// stable order (e.g. SQL `ORDER BY id LIMIT $limit OFFSET $offset`).
+    /// A page shorter than `limit` is the last one.
+    ///
+    /// # Errors
+    /// Returns `Err` if not implemented or if data source operation fails
+    fn fetch_page(
+        &self,
+        _offset: u64,
+        _limit: usize,
+    ) -> impl Future<Output = Result<Vec<T>>> + Send {
+        async {
+            Err(crate::error::Error::NotImplemented(
+                "fetch_page not implemented for this repository".to_string(),
+            ))
+        }
+    }
+}
+
+/// Trait for repositories that can persist changes.
+///
+/// Extends [`DataRepository`] with write operations, used by the expander's
+/// write-through, write-around and delete-through APIs. The repository is
+/// always written first; the cache is only updated once the write succeeds.
+pub trait WritableRepository<T: CacheEntity>: DataRepository<T> {
+    /// Insert or update an entity in the primary data source.
+    ///
+    /// # Errors
+    /// Returns `Err` if data source is unavailable or the write fails
+    fn save(&self, entity: &T) -> impl Future<Output = Result<()>> + Send;
+
+    /// Delete an entity by ID from the primary data source.
+    ///
+    /// Deleting an entity that doesn't exist is not an error.
+    ///
+    /// # Errors
+    /// Returns `Err` if data source is unavailable or the delete fails
+    fn delete(&self, id: &T::Key) -> impl Future<Output = Result<()>> + Send;
+
+    /// Batch save entities (optional optimization).
+    ///
+    /// Default implementation calls `save()` for each entity, stopping at the
+    /// first failure. Override for efficiency (e.g., a multi-row `INSERT`).
+    ///
+    /// # Errors
+    /// Returns `Err` if data source is unavailable or a write fails
+    fn save_many(&self, entities: &[T]) -> impl Future<Output = Result<()>> + Send {
+        async move {
+            for entity in entities {
+                self.save(entity).await?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Trait for repositories that can look entities up by an alternate key.
+///
+/// Used by `CacheExpander::with_index` / `CacheService::execute_by` when the
+/// cache has no valid pointer for the key. Implement it once per
+/// [`SecondaryIndex`] the repository supports.
+pub trait IndexedRepository<T: CacheEntity, I: SecondaryIndex<T>>: Send + Sync {
+    /// Fetch the entity whose alternate key in index `I` is `key`.
+    ///
+    /// # Errors
+    /// Returns `Err` if data source is unavailable or query fails
+    fn fetch_by_index(&self, key: &I::Key) -> impl Future<Output = Result<Option<T>>> + Send;
+}
+
+/// Shared repositories are repositories too.
+///
+/// Lets an `Arc<R>` be passed wherever a cloneable, `'static` repository is
+/// needed (e.g. background revalidation) without wrapping it yourself.
+impl<T: CacheEntity, R: DataRepository<T>> DataRepository<T> for Arc<R> {
+    fn fetch_by_id(&self, id: &T::Key) -> impl Future<Output = Result<Option<T>>> + Send {
+        (**self).fetch_by_id(id)
+    }
+
+    fn fetch_by_ids(&self, ids: &[T::Key]) -> impl Future<Output = Result<Vec<Option<T>>>> + Send {
+        (**self).fetch_by_ids(ids)
+    }
+
+    fn count(&self) -> impl Future<Output = Result<u64>> + Send {
+        (**self).count()
+    }
+
+    fn fetch_all(&self) -> impl Future<Output = Result<Vec<T>>> + Send {
+        (**self).fetch_all()
+    }
+
+    fn fetch_page(&self, offset: u64, limit: usize) -> impl Future<Output = Result<Vec<T>>> + Send {
+        (**self).fetch_page(offset, limit)
+    }
+}
+
+impl<T, I, R> IndexedRepository<T, I> for Arc<R>
+where
+    T: CacheEntity,
+    I: SecondaryIndex<T>,
+    R: IndexedRepository<T, I>,
+{
+    fn fetch_by_index(&self, key: &I::Key) -> impl Future<Output = Result<Option<T>>> + Send {
+        (**self).fetch_by_index(key)
+    }
+}
+
+impl<T: CacheEntity, R: WritableRepository<T>> WritableRepository<T> for Arc<R> {
+    fn save(&self, entity: &T) -> impl Future<Output = Result<()>> + Send {
+        (**self).save(entity)
+    }
+
+    fn delete(&self, id: &T::Key) -> impl Future<Output = Result<()>> + Send {
+        (**self).delete(id)
+    }
+
+    fn save_many(&self, entities: &[T]) -> impl Future<Output = Result<()>> + Send {
+        (**self).save_many(entities)
+    }
+}
+
+/// Repository backed by a closure that loads one specific entity.
+///
+/// Used by `CacheExpander::get_or_load`, where the cache key already identifies
+/// the entity, so the requested ID is ignored.
+pub(crate) struct LoaderRepository<L>(pub(crate) L);
+
+impl<T, L, Fut> DataRepository<T> for LoaderRepository<L>
+where
+    T: CacheEntity,
+    L: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = Result<Option<T>>> + Send,
+{
+    fn fetch_by_id(&self, _id: &T::Key) -> impl Future<Output = Result<Option<T>>> + Send {
+        (self.0)()
     }
 }
 
@@ -124,6 +279,7 @@ pub trait DataRepository<T: CacheEntity>: Send + Sync {
 // ============================================================================
 
 use std::collections::HashMap;
+use std::sync::{PoisonError, RwLock};
 
 /// Simple in-memory repository for testing cache-kit implementations.
 ///
@@ -160,8 +316,9 @@ use std::collections::HashMap;
 /// - **Cache miss**: Keep repo empty, cache will fallback to repo (which has nothing)
 /// - **Invalidation**: Clear repo between operations to test refresh behavior
 /// - **Batch operations**: Use `fetch_by_ids()` to test multi-key scenarios
+/// - **Writes**: Implements `WritableRepository`, so write-through paths can be tested
 pub struct InMemoryRepository<T: CacheEntity> {
-    data: HashMap<String, T>,
+    data: RwLock<HashMap<String, T>>,
 }
 
 impl<T: CacheEntity> InMemoryRepository<T> {
@@ -175,7 +332,7 @@ impl<T: CacheEntity> InMemoryRepository<T> {
     /// ```
     pub fn new() -> Self {
         InMemoryRepository {
-            data: HashMap::new(),
+            data: RwLock::new(HashMap::new()),
         }
     }
 
@@ -188,7 +345,7 @@ impl<T: CacheEntity> InMemoryRepository<T> {
     /// let found = repo.fetch_by_id(&"user:123".to_string()).await?;
     /// ```
     pub fn insert(&mut self, id: T::Key, value: T) {
-        self.data.insert(id.to_string(), value);
+        self.entries_mut().insert(id.to_string(), value);
     }
 
     /// Remove all entities from the repository.
@@ -204,17 +361,25 @@ impl<T: CacheEntity> InMemoryRepository<T> {
     /// assert!(repo.is_empty());
     /// ```
     pub fn clear(&mut self) {
-        self.data.clear();
+        self.entries_mut().clear();
     }
 
     /// Return the number of entities in the repository.
     pub fn len(&self) -> usize {
-        self.data.len()
+        self.entries().len()
     }
 
     /// Return true if the repository contains no entities.
     pub fn is_empty(&self) -> bool {
-        self.data.is_empty()
+        self.entries().is_empty()
+    }
+
+    fn entries(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, T>> {
+        self.data.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn entries_mut(&mut self) -> &mut HashMap<String, T> {
+        self.data.get_mut().unwrap_or_else(PoisonError::into_inner)
     }
 }
 
@@ -226,22 +391,65 @@ impl<T: CacheEntity> Default for InMemoryRepository<T> {
 
 impl<T: CacheEntity> DataRepository<T> for InMemoryRepository<T> {
     async fn fetch_by_id(&self, id: &T::Key) -> Result<Option<T>> {
-        Ok(self.data.get(&id.to_string()).cloned())
+        Ok(self.entries().get(&id.to_string()).cloned())
     }
 
     async fn fetch_by_ids(&self, ids: &[T::Key]) -> Result<Vec<Option<T>>> {
+        let entries = self.entries();
         Ok(ids
             .iter()
-            .map(|id| self.data.get(&id.to_string()).cloned())
+            .map(|id| entries.get(&id.to_string()).cloned())
             .collect())
     }
 
     async fn count(&self) -> Result<u64> {
-        Ok(self.data.len() as u64)
+        Ok(self.len() as u64)
     }
 
     async fn fetch_all(&self) -> Result<Vec<T>> {
-        Ok(self.data.values().cloned().collect())
+        Ok(self.entries().values().cloned().collect())
+    }
+
+    async fn fetch_page(&self, offset: u64, limit: usize) -> Result<Vec<T>> {
+        let entries = self.entries();
+        let mut ids: Vec<&String> = entries.keys().collect();
+        ids.sort();
+        Ok(ids
+            .into_iter()
+            .skip(usize::try_from(offset).unwrap_or(usize::MAX))
+            .take(limit)
+            .map(|id| entries[id].clone())
+            .collect())
+    }
+}
+
+impl<T: CacheEntity> WritableRepository<T> for InMemoryRepository<T> {
+    async fn save(&self, entity: &T) -> Result<()> {
+        self.data
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(entity.cache_key().to_string(), entity.clone());
+        Ok(())
+    }
+
+    async fn delete(&self, id: &T::Key) -> Result<()> {
+        self.data
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&id.to_string());
+        Ok(())
+    }
+}
+
+/// Looks entities up by scanning for the alternate key in `cache_secondary_keys()`.
+impl<T: CacheEntity, I: SecondaryIndex<T>> IndexedRepository<T, I> for InMemoryRepository<T> {
+    async fn fetch_by_index(&self, key: &I::Key) -> Result<Option<T>> {
+        let wanted = SecondaryKey::of::<T, I>(key);
+        Ok(self
+            .entries()
+            .values()
+            .find(|entity| entity.cache_secondary_keys().contains(&wanted))
+            .cloned())
     }
 }
 
@@ -344,4 +552,55 @@ mod tests {
 
         assert_eq!(repo.count().await.expect("Failed to count"), 1);
     }
+
+    #[tokio::test]
+    async fn test_in_memory_repository_fetch_page() {
+        let mut repo = InMemoryRepository::new();
+        for id in ["3", "1", "2"] {
+            repo.insert(
+                id.to_string(),
+                TestEntity {
+                    id: id.to_string(),
+                    value: "a".to_string(),
+                },
+            );
+        }
+
+        let page = |offset, limit| {
+            let repo = &repo;
+            async move {
+                let page = repo
+                    .fetch_page(offset, limit)
+                    .await
+                    .expect("Failed to fetch page");
+                page.into_iter().map(|e| e.id).collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(page(0, 2).await, vec!["1", "2"]);
+        assert_eq!(page(2, 2).await, vec!["3"]);
+        assert!(page(3, 2).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_repository_save_delete() {
+        let repo = InMemoryRepository::new();
+
+        repo.save(&TestEntity {
+            id: "1".to_string(),
+            value: "a".to_string(),
+        })
+        .await
+        .expect("Failed to save");
+        assert_eq!(repo.len(), 1);
+
+        repo.delete(&"1".to_string())
+            .await
+            .expect("Failed to delete");
+        assert!(repo
+            .fetch_by_id(&"1".to_string())
+            .await
+            .expect("Failed to fetch")
+            .is_none());
+    }
 }
@@ -0,0 +1,327 @@
+//! Retry policy for cache operations.
+//!
+//! A [`RetryPolicy`] decides how many times a failed operation is attempted,
+//! how long to wait between attempts, and which part of the operation is
+//! retried. Only errors classified by [`Error::is_retryable`] are retried;
+//! validation, serialization and configuration errors fail immediately.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use cache_kit::retry::{Jitter, RetryPolicy, RetryScope};
+//!
+//! // Retry only the backend calls, up to 3 attempts each
+//! let policy = RetryPolicy::new(3)
+//!     .with_base_delay(Duration::from_millis(20))
+//!     .with_jitter(Jitter::Decorrelated)
+//!     .with_scope(RetryScope::Backend);
+//!
+//! let config = OperationConfig::default().with_retry_policy(policy);
+//! ```
+
+use crate::error::{Error, Result};
+use std::future::Future;
+use std::time::Duration;
+
+/// How the delay between attempts is randomized.
+///
+/// Randomizing the delay keeps clients that failed together from retrying
+/// together.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Jitter {
+    /// Plain exponential backoff: `min(max_delay, base_delay * 2^n)`.
+    None,
+
+    /// Uniform in `[0, min(max_delay, base_delay * 2^n)]` (default).
+    #[default]
+    Full,
+
+    /// Uniform in `[base_delay, previous_delay * 3]`, capped at `max_delay`.
+    Decorrelated,
+}
+
+/// Part of an operation that a [`RetryPolicy`] retries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum RetryScope {
+    /// Re-run the whole operation (default).
+    #[default]
+    Operation,
+
+    /// Re-run individual cache backend calls.
+    Backend,
+
+    /// Re-run individual repository calls.
+    Repository,
+}
+
+/// Retry policy for an operation, set with `OperationConfig::with_retry_policy`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first. `1` disables retries. Default: 1.
+    pub max_attempts: u32,
+
+    /// Delay before the first retry. Default: 100 milliseconds.
+    pub base_delay: Duration,
+
+    /// Upper bound on any delay. Default: 5 seconds.
+    pub max_delay: Duration,
+
+    /// Randomization of delays. Default: `Jitter::Full`.
+    pub jitter: Jitter,
+
+    /// What is retried. Default: `RetryScope::Operation`.
+    pub scope: RetryScope,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+            jitter: Jitter::default(),
+            scope: RetryScope::default(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Create a policy making up to `max_attempts` attempts (at least one).
+    pub fn new(max_attempts: u32) -> Self {
+        RetryPolicy {
+            max_attempts: max_attempts.max(1),
+            ..Self::default()
+        }
+    }
+
+    /// Set the delay before the first retry.
+    pub fn with_base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Set the upper bound on any delay.
+    pub fn with_max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Set how delays are randomized.
+    pub fn with_jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set which part of the operation is retried.
+    pub fn with_scope(mut self, scope: RetryScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Delay after failed attempt number `attempt` (1-based), using the thread-local RNG.
+    ///
+    /// `previous` is the delay used before this attempt (ignored unless the
+    /// jitter is `Jitter::Decorrelated`).
+    pub fn delay(&self, attempt: u32, previous: Duration) -> Duration {
+        self.delay_with(attempt, previous, rand::random::<f64>())
+    }
+
+    /// Delay after failed attempt number `attempt`, given a random draw in `[0, 1)`.
+    pub fn delay_with(&self, attempt: u32, previous: Duration, random: f64) -> Duration {
+        match self.jitter {
+            Jitter::None => self.exponential(attempt),
+            Jitter::Full => self.exponential(attempt).mul_f64(random),
+            Jitter::Decorrelated => {
+                let upper = previous.max(self.base_delay).saturating_mul(3);
+                let delay = self.base_delay + (upper - self.base_delay).mul_f64(random);
+                delay.min(self.max_delay)
+            }
+        }
+    }
+
+    /// `min(max_delay, base_delay * 2^(attempt - 1))`.
+    fn exponential(&self, attempt: u32) -> Duration {
+        let factor = 2_u32
+            .checked_pow(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// Start tracking attempts of one retried call.
+    pub(crate) fn backoff(&self) -> Backoff<'_> {
+        Backoff {
+            policy: self,
+            attempt: 1,
+            previous: self.base_delay,
+        }
+    }
+
+    /// Run `call`, retrying it if this policy's scope is `scope`.
+    ///
+    /// For any other scope `call` runs exactly once.
+    pub(crate) async fn run_in<V, F, Fut>(&self, scope: RetryScope, mut call: F) -> Result<V>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<V>>,
+    {
+        if self.scope != scope {
+            return call().await;
+        }
+
+        let mut backoff = self.backoff();
+        loop {
+            match call().await {
+                Ok(value) => return Ok(value),
+                Err(e) if backoff.retry(&e).await => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Attempt counter for one retried call.
+pub(crate) struct Backoff<'a> {
+    policy: &'a RetryPolicy,
+    attempt: u32,
+    previous: Duration,
+}
+
+impl Backoff<'_> {
+    /// Sleep before the next attempt, or return `false` if `error` is final.
+    ///
+    /// An error is final if it is not retryable or the policy's attempts are used up.
+    pub(crate) async fn retry(&mut self, error: &Error) -> bool {
+        if self.attempt >= self.policy.max_attempts || !error.is_retryable() {
+            return false;
+        }
+
+        let delay = self.policy.delay(self.attempt, self.previous);
+        debug!(
+            "Attempt {}/{} failed ({}), retrying in {:?}",
+            self.attempt, self.policy.max_attempts, error, delay
+        );
+
+        self.attempt += 1;
+        self.previous = delay;
+        tokio::time::sleep(delay).await;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn policy(jitter: Jitter) -> RetryPolicy {
+        RetryPolicy::new(5)
+            .with_base_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_secs(1))
+            .with_jitter(jitter)
+    }
+
+    #[test]
+    fn test_exponential_delay_is_capped() {
+        let policy = policy(Jitter::None);
+        let previous = Duration::ZERO;
+
+        assert_eq!(policy.delay(1, previous), Duration::from_millis(100));
+        assert_eq!(policy.delay(2, previous), Duration::from_millis(200));
+        assert_eq!(policy.delay(4, previous), Duration::from_millis(800));
+        assert_eq!(policy.delay(5, previous), Duration::from_secs(1));
+        assert_eq!(policy.delay(100, previous), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_full_jitter_bounds() {
+        let policy = policy(Jitter::Full);
+
+        assert_eq!(policy.delay_with(3, Duration::ZERO, 0.0), Duration::ZERO);
+        assert_eq!(
+            policy.delay_with(3, Duration::ZERO, 0.5),
+            Duration::from_millis(200)
+        );
+        for _ in 0..100 {
+            assert!(policy.delay(3, Duration::ZERO) <= Duration::from_millis(400));
+        }
+    }
+
+    #[test]
+    fn test_decorrelated_jitter_bounds() {
+        let policy = policy(Jitter::Decorrelated);
+        let previous = Duration::from_millis(200);
+
+        assert_eq!(
+            policy.delay_with(2, previous, 0.0),
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            policy.delay_with(2, previous, 0.5),
+            Duration::from_millis(350)
+        );
+        assert_eq!(
+            policy.delay_with(2, Duration::from_secs(10), 0.99),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_in_retries_retryable_errors() {
+        let policy = RetryPolicy::new(3)
+            .with_base_delay(Duration::from_millis(1))
+            .with_scope(RetryScope::Backend);
+        let calls = AtomicUsize::new(0);
+
+        let result = policy
+            .run_in(RetryScope::Backend, || async {
+                match calls.fetch_add(1, Ordering::SeqCst) {
+                    0 | 1 => Err(Error::BackendError("flaky".to_string())),
+                    _ => Ok("value"),
+                }
+            })
+            .await;
+
+        assert_eq!(result.expect("Retry failed"), "value");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_run_in_stops_on_final_errors() {
+        let policy = RetryPolicy::new(3)
+            .with_base_delay(Duration::from_millis(1))
+            .with_scope(RetryScope::Repository);
+        let calls = AtomicUsize::new(0);
+
+        // Not retryable
+        let result: Result<()> = policy
+            .run_in(RetryScope::Repository, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(Error::ValidationError("bad id".to_string()))
+            })
+            .await;
+        assert!(matches!(result, Err(Error::ValidationError(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Retryable, but outside the policy's scope
+        calls.store(0, Ordering::SeqCst);
+        let result: Result<()> = policy
+            .run_in(RetryScope::Backend, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(Error::BackendError("down".to_string()))
+            })
+            .await;
+        assert!(matches!(result, Err(Error::BackendError(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Retryable, until attempts run out
+        calls.store(0, Ordering::SeqCst);
+        let result: Result<()> = policy
+            .run_in(RetryScope::Repository, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(Error::RepositoryError("down".to_string()))
+            })
+            .await;
+        assert!(matches!(result, Err(Error::RepositoryError(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
@@ -14,6 +14,11 @@
 //!   "CKIT"              u32 (LE)           postcard::to_allocvec(T)
 //! ```
 //!
+//! Entries written by the expander may carry an optional [`EntryMetadata`]
+//! trailer after the payload (e.g. the write timestamp used by
+//! stale-while-revalidate). Readers that do not need it ignore the trailer, so
+//! entries with and without metadata are interchangeable.
+//!
 //! # Safety Guarantees
 //!
 //! - **Deterministic:** Same value always produces identical bytes
@@ -48,6 +53,7 @@
 
 use crate::error::{Error, Result};
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Magic header for cache-kit entries: b"CKIT"
 ///
@@ -67,6 +73,13 @@ pub const CACHE_MAGIC: [u8; 4] = *b"CKIT";
 /// evicted and recomputed from the source of truth.
 pub const CURRENT_SCHEMA_VERSION: u32 = 1;
 
+/// Magic header for negative-cache tombstones: b"CKNF"
+///
+/// Marks a cache entry recording that the entity does not exist in the
+/// repository. Never produced by [`serialize_for_cache`], so a tombstone can
+/// not be mistaken for a cached entity.
+pub const TOMBSTONE_MAGIC: [u8; 4] = *b"CKNF";
+
 /// Versioned envelope for cache entries.
 ///
 /// Every cache entry is wrapped in this envelope to enable:
@@ -213,11 +226,166 @@ pub fn serialize_for_cache<T: Serialize>(value: &T) -> Result<Vec<u8>> {
 /// - `Error::VersionMismatch`: Schema version mismatch
 /// - `Error::DeserializationError`: Corrupted Postcard payload
 pub fn deserialize_from_cache<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> Result<T> {
+    take_envelope(bytes).map(|(payload, _)| payload)
+}
+
+/// Metadata stored alongside a cache entry's payload.
+///
+/// Written as an optional trailer after the envelope, so it does not affect the
+/// envelope format or readers that ignore it.
+///
+/// # Example
+///
+/// ```rust
+/// use cache_kit::serialization::{serialize_with_metadata, deserialize_with_metadata, EntryMetadata};
+///
+/// # fn main() -> cache_kit::Result<()> {
+/// let bytes = serialize_with_metadata(&42u64, &EntryMetadata::now())?;
+/// let (value, metadata): (u64, _) = deserialize_with_metadata(&bytes)?;
+/// assert_eq!(value, 42);
+/// assert!(metadata.is_some());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EntryMetadata {
+    /// When the entry was written, in milliseconds since the Unix epoch.
+    pub created_at_ms: u64,
+
+    /// When the entry's TTL elapses, in milliseconds since the Unix epoch.
+    ///
+    /// `None` if the entry was written without a TTL.
+    pub expires_at_ms: Option<u64>,
+
+    /// How long it took to load the entry from its source, in milliseconds.
+    pub compute_ms: u64,
+}
+
+impl EntryMetadata {
+    /// Metadata for an entry written now.
+    pub fn now() -> Self {
+        EntryMetadata {
+            created_at_ms: unix_millis(),
+            ..Default::default()
+        }
+    }
+
+    /// Record the entry's TTL as an absolute expiry time.
+    pub fn with_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.expires_at_ms = ttl.map(|d| self.created_at_ms.saturating_add(d.as_millis() as u64));
+        self
+    }
+
+    /// Record how long the entry took to load from its source.
+    pub fn with_compute_time(mut self, compute: Duration) -> Self {
+        self.compute_ms = compute.as_millis() as u64;
+        self
+    }
+
+    /// Time elapsed since the entry was written.
+    ///
+    /// Returns zero if the entry appears to come from the future (clock skew
+    /// between app instances).
+    pub fn age(&self) -> Duration {
+        Duration::from_millis(unix_millis().saturating_sub(self.created_at_ms))
+    }
+
+    /// Time left until the entry's TTL elapses, if it has one.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.expires_at_ms
+            .map(|expires| Duration::from_millis(expires.saturating_sub(unix_millis())))
+    }
+}
+
+/// Serialize a value with envelope and a metadata trailer.
+///
+/// The result is readable by [`deserialize_from_cache`], which ignores the trailer.
+///
+/// # Errors
+///
+/// Returns `Error::SerializationError` if Postcard serialization fails.
+pub fn serialize_with_metadata<T: Serialize>(
+    value: &T,
+    metadata: &EntryMetadata,
+) -> Result<Vec<u8>> {
+    let bytes = serialize_for_cache(value)?;
+    postcard::to_extend(metadata, bytes).map_err(|e| {
+        log::error!("Cache metadata serialization failed: {}", e);
+        Error::SerializationError(e.to_string())
+    })
+}
+
+/// Deserialize a value and its metadata trailer, if present.
+///
+/// Performs the same validation as [`deserialize_from_cache`]. Entries without a
+/// trailer (or with one this version cannot read) yield `None` metadata.
+///
+/// # Errors
+///
+/// - `Error::InvalidCacheEntry`: Invalid magic header
+/// - `Error::VersionMismatch`: Schema version mismatch
+/// - `Error::DeserializationError`: Corrupted Postcard payload
+pub fn deserialize_with_metadata<'de, T: Deserialize<'de>>(
+    bytes: &'de [u8],
+) -> Result<(T, Option<EntryMetadata>)> {
+    let (payload, rest) = take_envelope(bytes)?;
+    let metadata = postcard::from_bytes(rest).ok();
+    Ok((payload, metadata))
+}
+
+/// Serialize a negative-cache tombstone for an entity type.
+///
+/// The tombstone is an envelope with [`TOMBSTONE_MAGIC`] whose payload is the
+/// entity's cache prefix. [`deserialize_from_cache`] rejects it as an error.
+///
+/// # Example
+///
+/// ```rust
+/// use cache_kit::serialization::{serialize_tombstone, is_tombstone};
+///
+/// # fn main() -> cache_kit::Result<()> {
+/// let bytes = serialize_tombstone("user")?;
+/// assert!(is_tombstone(&bytes));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns `Error::SerializationError` if Postcard serialization fails.
+pub fn serialize_tombstone(entity_type: &str) -> Result<Vec<u8>> {
+    let envelope = CacheEnvelope {
+        magic: TOMBSTONE_MAGIC,
+        version: CURRENT_SCHEMA_VERSION,
+        payload: entity_type,
+    };
+    postcard::to_allocvec(&envelope).map_err(|e| {
+        log::error!("Tombstone serialization failed: {}", e);
+        Error::SerializationError(e.to_string())
+    })
+}
+
+/// Check whether cache bytes are a negative-cache tombstone.
+pub fn is_tombstone(bytes: &[u8]) -> bool {
+    bytes.starts_with(&TOMBSTONE_MAGIC)
+}
+
+/// Current time in milliseconds since the Unix epoch.
+pub(crate) fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Deserialize and validate an envelope, returning its payload and any trailing bytes.
+fn take_envelope<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> Result<(T, &'de [u8])> {
     // Attempt to deserialize envelope
-    let envelope: CacheEnvelope<T> = postcard::from_bytes(bytes).map_err(|e| {
-        log::error!("Cache deserialization failed: {}", e);
-        Error::DeserializationError(e.to_string())
-    })?;
+    let (envelope, rest): (CacheEnvelope<T>, &[u8]) =
+        postcard::take_from_bytes(bytes).map_err(|e| {
+            log::error!("Cache deserialization failed: {}", e);
+            Error::DeserializationError(e.to_string())
+        })?;
 
     // Validate magic header
     if envelope.magic != CACHE_MAGIC {
@@ -245,7 +413,7 @@ pub fn deserialize_from_cache<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> Res
         });
     }
 
-    Ok(envelope.payload)
+    Ok((envelope.payload, rest))
 }
 
 #[cfg(test)]
@@ -411,6 +579,93 @@ mod tests {
         assert_eq!(data, deserialized);
     }
 
+    #[test]
+    fn test_metadata_roundtrip() {
+        let data = TestData {
+            id: 123,
+            name: "test".to_string(),
+            active: true,
+        };
+        let metadata = EntryMetadata {
+            created_at_ms: 42,
+            expires_at_ms: Some(1042),
+            compute_ms: 7,
+        };
+
+        let bytes = serialize_with_metadata(&data, &metadata).expect("Failed to serialize");
+        let (deserialized, read): (TestData, _) =
+            deserialize_with_metadata(&bytes).expect("Failed to deserialize");
+
+        assert_eq!(deserialized, data);
+        assert_eq!(read, Some(metadata));
+
+        // Plain readers ignore the trailer
+        let plain: TestData = deserialize_from_cache(&bytes).expect("Failed to deserialize");
+        assert_eq!(plain, data);
+    }
+
+    #[test]
+    fn test_metadata_absent() {
+        let data = TestData {
+            id: 123,
+            name: "test".to_string(),
+            active: true,
+        };
+
+        let bytes = serialize_for_cache(&data).expect("Failed to serialize");
+        let (deserialized, read): (TestData, _) =
+            deserialize_with_metadata(&bytes).expect("Failed to deserialize");
+
+        assert_eq!(deserialized, data);
+        assert_eq!(read, None);
+    }
+
+    #[test]
+    fn test_metadata_age() {
+        let metadata = EntryMetadata::now();
+        assert!(metadata.age() < Duration::from_secs(5));
+
+        let future = EntryMetadata {
+            created_at_ms: u64::MAX,
+            ..Default::default()
+        };
+        assert_eq!(future.age(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_metadata_ttl_and_compute_time() {
+        let metadata = EntryMetadata::now()
+            .with_ttl(Some(Duration::from_secs(60)))
+            .with_compute_time(Duration::from_millis(25));
+
+        assert_eq!(
+            metadata.expires_at_ms,
+            Some(metadata.created_at_ms + 60_000)
+        );
+        assert_eq!(metadata.compute_ms, 25);
+        assert!(metadata.remaining().expect("Missing expiry") > Duration::from_secs(50));
+
+        assert_eq!(EntryMetadata::now().with_ttl(None).remaining(), None);
+    }
+
+    #[test]
+    fn test_tombstone_is_not_an_entry() {
+        let bytes = serialize_tombstone("test").expect("Failed to serialize");
+        assert!(is_tombstone(&bytes));
+
+        let result: Result<TestData> = deserialize_from_cache(&bytes);
+        assert!(result.is_err());
+
+        let data = TestData {
+            id: 123,
+            name: "test".to_string(),
+            active: true,
+        };
+        assert!(!is_tombstone(
+            &serialize_for_cache(&data).expect("Failed to serialize")
+        ));
+    }
+
     #[test]
     fn test_postcard_smaller_than_json() {
         let data = TestData {
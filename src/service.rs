@@ -7,10 +7,15 @@ use crate::entity::CacheEntity;
 use crate::error::Result;
 use crate::expander::{CacheExpander, OperationConfig};
 use crate::feed::CacheFeed;
+use crate::index::SecondaryIndex;
 use crate::observability::CacheMetrics;
-use crate::repository::DataRepository;
+use crate::query::CacheQuery;
+use crate::refresh_ahead::{RefreshAhead, RefreshAheadConfig};
+use crate::repository::{DataRepository, IndexedRepository, WritableRepository};
 use crate::strategy::CacheStrategy;
-use std::str::FromStr;
+use crate::warm::{WarmOptions, WarmProgress};
+use crate::write_behind::{WriteBehindConfig, WriteBehindQueue};
+use std::future::Future;
 use std::sync::Arc;
 
 /// High-level cache service for web applications.
@@ -66,6 +71,23 @@ impl<B: CacheBackend> CacheService<B> {
         }
     }
 
+    /// Create a new cache service from a configured expander.
+    ///
+    /// Use this to share an expander built with options such as
+    /// `with_request_coalescing()` or `with_ttl_policy()`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let expander = CacheExpander::new(InMemoryBackend::new()).with_request_coalescing();
+    /// let cache = CacheService::from_expander(expander);
+    /// ```
+    pub fn from_expander(expander: CacheExpander<B>) -> Self {
+        CacheService {
+            expander: Arc::new(expander),
+        }
+    }
+
     /// Execute a cache operation.
     ///
     /// This is equivalent to calling `expander.with()` but more ergonomic
@@ -108,7 +130,6 @@ impl<B: CacheBackend> CacheService<B> {
         T: CacheEntity,
         F: CacheFeed<T>,
         R: DataRepository<T>,
-        T::Key: FromStr,
     {
         self.expander
             .with::<T, F, R>(feeder, repository, strategy)
@@ -156,13 +177,450 @@ impl<B: CacheBackend> CacheService<B> {
         T: CacheEntity,
         F: CacheFeed<T>,
         R: DataRepository<T>,
-        T::Key: FromStr,
     {
         self.expander
             .with_config::<T, F, R>(feeder, repository, strategy, config)
             .await
     }
 
+    /// Execute a cache operation, refreshing stale entries in the background.
+    ///
+    /// Equivalent to `expander.with_background_refresh()`. Use with
+    /// `CacheStrategy::StaleWhileRevalidate` to serve stale entries immediately
+    /// while a spawned task reloads them from the repository.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let repo = Arc::new(UserRepository::new(pool));
+    /// let strategy = CacheStrategy::StaleWhileRevalidate {
+    ///     stale_after: Duration::from_secs(60),
+    /// };
+    ///
+    /// cache
+    ///     .execute_with_background_refresh(&mut feeder, &repo, strategy, OperationConfig::default())
+    ///     .await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same error cases as `execute_with_config()`.
+    pub async fn execute_with_background_refresh<T, F, R>(
+        &self,
+        feeder: &mut F,
+        repository: &R,
+        strategy: CacheStrategy,
+        config: OperationConfig,
+    ) -> Result<()>
+    where
+        B: 'static,
+        T: CacheEntity + 'static,
+        F: CacheFeed<T>,
+        R: DataRepository<T> + Clone + 'static,
+    {
+        self.expander
+            .with_background_refresh::<T, F, R>(feeder, repository, strategy, config)
+            .await
+    }
+
+    /// Read one entity, loading it with a closure on a miss.
+    ///
+    /// Equivalent to `expander.get_or_load()`: no `CacheFeed` or
+    /// `DataRepository` needed, and the entity is returned directly.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let user = cache
+    ///     .get_or_load::<User, _>(id.clone(), CacheStrategy::Refresh, || async {
+    ///         sqlx::query_as("SELECT * FROM users WHERE id = $1")
+    ///             .bind(&id)
+    ///             .fetch_optional(&pool)
+    ///             .await
+    ///             .map_err(|e| Error::RepositoryError(e.to_string()))
+    ///     })
+    ///     .await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same error cases as `execute()`; errors returned by `load` are passed
+    /// through unchanged.
+    pub async fn get_or_load<T, Fut>(
+        &self,
+        id: T::Key,
+        strategy: CacheStrategy,
+        load: impl Fn() -> Fut + Send + Sync,
+    ) -> Result<Option<T>>
+    where
+        T: CacheEntity,
+        Fut: Future<Output = Result<Option<T>>> + Send,
+    {
+        self.expander
+            .get_or_load::<T, Fut>(id, strategy, load)
+            .await
+    }
+
+    /// Read one entity from the cache only, without a repository fallback.
+    ///
+    /// Equivalent to `expander.get()`.
+    ///
+    /// # Errors
+    ///
+    /// Same error cases as `execute()` with `CacheStrategy::Fresh`.
+    pub async fn get<T>(&self, id: T::Key) -> Result<Option<T>>
+    where
+        T: CacheEntity,
+    {
+        self.expander.get::<T>(id).await
+    }
+
+    /// Read one entity by an alternate unique key (e.g. a user's email).
+    ///
+    /// Equivalent to `expander.with_index()`: follows the secondary-key
+    /// pointer to the primary entry, falling back to
+    /// `IndexedRepository::fetch_by_index`. See the [`index`](crate::index) module.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let user = cache
+    ///     .execute_by::<User, ByEmail, _>(&"ada@example.com".to_string(), &user_repo)
+    ///     .await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same error cases as `expander.with_index()`.
+    pub async fn execute_by<T, I, R>(&self, key: &I::Key, repository: &R) -> Result<Option<T>>
+    where
+        T: CacheEntity,
+        I: SecondaryIndex<T>,
+        R: IndexedRepository<T, I>,
+    {
+        self.expander.with_index::<T, I, R>(key, repository).await
+    }
+
+    /// Execute a batch cache operation for many entities of one type.
+    ///
+    /// Equivalent to `expander.with_many()`: one `mget`, one `fetch_by_ids`
+    /// for the misses only, and one `mset` to back-fill the cache. Results are
+    /// returned in input order.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let products = cache
+    ///     .execute_many::<Product, _>(&ids, &repo, CacheStrategy::Refresh)
+    ///     .await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same error cases as `execute()`.
+    pub async fn execute_many<T, R>(
+        &self,
+        ids: &[T::Key],
+        repository: &R,
+        strategy: CacheStrategy,
+    ) -> Result<Vec<Option<T>>>
+    where
+        T: CacheEntity,
+        R: DataRepository<T>,
+    {
+        self.expander
+            .with_many::<T, R>(ids, repository, strategy)
+            .await
+    }
+
+    /// Execute a batch cache operation with custom configuration.
+    ///
+    /// Equivalent to `expander.with_many_config()`: deadlines, retries and the
+    /// failure mode in `config` apply to the `mget` and `fetch_by_ids` calls.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let config = OperationConfig::default().with_timeout(Duration::from_secs(1));
+    /// let products = cache
+    ///     .execute_many_with_config::<Product, _>(&ids, &repo, CacheStrategy::Refresh, config)
+    ///     .await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same error cases as `execute_with_config()`.
+    pub async fn execute_many_with_config<T, R>(
+        &self,
+        ids: &[T::Key],
+        repository: &R,
+        strategy: CacheStrategy,
+        config: OperationConfig,
+    ) -> Result<Vec<Option<T>>>
+    where
+        T: CacheEntity,
+        R: DataRepository<T>,
+    {
+        self.expander
+            .with_many_config::<T, R>(ids, repository, strategy, config)
+            .await
+    }
+
+    /// Pre-populate the cache with every entity in `repository`.
+    ///
+    /// Equivalent to `expander.warm()`: entities are read a page at a time and
+    /// written to the cache in batches. See [`warm`](crate::warm) for details.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let options = WarmOptions::default()
+    ///     .with_progress(|progress| info!("{} products warmed", progress.written));
+    ///
+    /// cache.warm::<Product, _>(&product_repo, options).await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `Error::RepositoryError`: Reading a page from the repository fails
+    /// - `Error::NotImplemented`: The repository implements neither `fetch_page` nor `fetch_all`
+    pub async fn warm<T, R>(&self, repository: &R, options: WarmOptions<T>) -> Result<WarmProgress>
+    where
+        T: CacheEntity,
+        R: DataRepository<T>,
+    {
+        self.expander.warm::<T, R>(repository, options).await
+    }
+
+    /// Persist an entity, then store it in the cache.
+    ///
+    /// Equivalent to `expander.write_through()`; see it for ordering and
+    /// failure handling.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// cache.write_through(&user, &repo).await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `Error::ValidationError`: Entity validation fails
+    /// - `Error::RepositoryError`: Repository write fails (cache untouched)
+    /// - `Error::SerializationError`: Entity serialization fails
+    /// - `Error::BackendError`: Cache write fails after the repository write succeeded
+    pub async fn write_through<T, R>(&self, entity: &T, repository: &R) -> Result<()>
+    where
+        T: CacheEntity,
+        R: WritableRepository<T>,
+    {
+        self.expander.write_through(entity, repository).await
+    }
+
+    /// Persist an entity, then evict its cache entry.
+    ///
+    /// Equivalent to `expander.write_around()`.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::ValidationError`: Entity validation fails
+    /// - `Error::RepositoryError`: Repository write fails (cache untouched)
+    /// - `Error::BackendError`: Cache eviction fails after the repository write succeeded
+    pub async fn write_around<T, R>(&self, entity: &T, repository: &R) -> Result<()>
+    where
+        T: CacheEntity,
+        R: WritableRepository<T>,
+    {
+        self.expander.write_around(entity, repository).await
+    }
+
+    /// Delete an entity from the repository, then evict its cache entry.
+    ///
+    /// Equivalent to `expander.delete_through()`.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::RepositoryError`: Repository delete fails (cache untouched)
+    /// - `Error::BackendError`: Cache eviction fails after the repository delete succeeded
+    pub async fn delete_through<T, R>(&self, id: &T::Key, repository: &R) -> Result<()>
+    where
+        T: CacheEntity,
+        R: WritableRepository<T>,
+    {
+        self.expander.delete_through::<T, R>(id, repository).await
+    }
+
+    /// Evict every cache entry tagged with `tag`.
+    ///
+    /// Equivalent to `expander.invalidate_tag()`. Entries are tagged when they
+    /// are written, from `CacheEntity::cache_tags()`. Returns the number of
+    /// keys evicted.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // After customer 42's billing details change
+    /// cache.invalidate_tag("customer:42").await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `Error::BackendError`: Reading the tag set or deleting entries fails
+    pub async fn invalidate_tag(&self, tag: &str) -> Result<usize> {
+        self.expander.invalidate_tag(tag).await
+    }
+
+    /// Read a query result, loading it with a closure on a miss.
+    ///
+    /// Equivalent to `expander.query()`. The result is evicted when one of
+    /// its members is written or invalidated through this service; see
+    /// [`CacheQuery`] for how results are stored and keyed.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let query = InvoiceList { customer_id, limit: 20, offset: 0 };
+    /// let invoices = cache
+    ///     .query(&query, || async { db.list_invoices(&customer_id, 20, 0).await })
+    ///     .await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same error cases as `expander.query()`; errors returned by `load` are
+    /// passed through unchanged.
+    pub async fn query<Q, Fut>(
+        &self,
+        query: &Q,
+        load: impl FnOnce() -> Fut,
+    ) -> Result<Vec<Q::Entity>>
+    where
+        Q: CacheQuery,
+        Fut: Future<Output = Result<Vec<Q::Entity>>>,
+    {
+        self.expander.query(query, load).await
+    }
+
+    /// Evict one cached query result.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::BackendError`: Deleting the result fails
+    pub async fn invalidate_query<Q: CacheQuery>(&self, query: &Q) -> Result<()> {
+        self.expander.invalidate_query(query).await
+    }
+
+    /// Evict the cache entry of the entity with key `id`, and its dependents.
+    ///
+    /// Equivalent to `expander.invalidate()`. Dependents (see
+    /// `CacheEntity::cache_dependencies`) are only evicted when the expander is
+    /// built with `with_dependency_cascade()`. Returns the number of dependent
+    /// entries evicted.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let cache = CacheService::from_expander(
+    ///     CacheExpander::new(backend).with_dependency_cascade(3),
+    /// );
+    /// cache.invalidate::<Customer>(&42).await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `Error::BackendError`: Deleting the entry or its dependents fails
+    pub async fn invalidate<T: CacheEntity>(&self, id: &T::Key) -> Result<usize> {
+        self.expander.invalidate::<T>(id).await
+    }
+
+    /// Invalidate every cached entity of type `T` in O(1).
+    ///
+    /// Equivalent to `expander.invalidate_all()`: bumps the type's generation
+    /// counter, so the expander must be built with `with_generations()` (see
+    /// [`from_expander`](Self::from_expander)). Returns the new generation.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let cache = CacheService::from_expander(CacheExpander::new(backend).with_generations());
+    /// cache.invalidate_all::<Product>().await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `Error::ConfigError`: Generation counters are not enabled
+    /// - `Error::BackendError`: Incrementing the generation fails
+    pub async fn invalidate_all<T: CacheEntity>(&self) -> Result<u64> {
+        self.expander.invalidate_all::<T>().await
+    }
+
+    /// Invalidate every cached entity in `namespace` (e.g. a tenant) in O(1).
+    ///
+    /// Equivalent to `expander.invalidate_namespace()`; see
+    /// `CacheEntity::cache_namespace`. Returns the new generation.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::ConfigError`: Generation counters are not enabled
+    /// - `Error::BackendError`: Incrementing the generation fails
+    pub async fn invalidate_namespace(&self, namespace: &str) -> Result<u64> {
+        self.expander.invalidate_namespace(namespace).await
+    }
+
+    /// Create a write-behind queue that persists writes to `repository`.
+    ///
+    /// Writes through the queue are stored in the cache immediately and saved
+    /// to the repository in batches. See [`WriteBehindQueue`] for flush and
+    /// retry behavior. Must be called inside a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let queue = cache.write_behind(counter_repo, WriteBehindConfig::default());
+    /// queue.write(&counter).await?;
+    ///
+    /// // On shutdown
+    /// queue.flush().await?;
+    /// ```
+    pub fn write_behind<T, R>(
+        &self,
+        repository: R,
+        config: WriteBehindConfig,
+    ) -> WriteBehindQueue<B, T, R>
+    where
+        B: 'static,
+        T: CacheEntity + 'static,
+        R: WritableRepository<T> + 'static,
+    {
+        WriteBehindQueue::new(Arc::clone(&self.expander), repository, config)
+    }
+
+    /// Create a refresh-ahead scheduler for one entity type.
+    ///
+    /// Reads of `T` made through this service or the returned handle are
+    /// counted, and keys read often enough are reloaded from `repository`
+    /// shortly before they expire. See [`refresh_ahead`](crate::refresh_ahead)
+    /// for details.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let products = cache.refresh_ahead(product_repo.clone(), RefreshAheadConfig::default());
+    /// cache.execute(&mut feeder, &product_repo, CacheStrategy::Refresh).await?;
+    /// ```
+    pub fn refresh_ahead<T, R>(
+        &self,
+        repository: R,
+        config: RefreshAheadConfig,
+    ) -> RefreshAhead<B, T, R>
+    where
+        B: 'static,
+        T: CacheEntity + 'static,
+        R: DataRepository<T> + 'static,
+    {
+        RefreshAhead::new(Arc::clone(&self.expander), repository, config)
+    }
+
     /// Get a reference to the underlying expander.
     ///
     /// Use this if you need direct access to expander methods.
@@ -228,6 +686,51 @@ mod tests {
         assert_eq!(feeder.data.expect("Data not found").value, "test_value");
     }
 
+    #[tokio::test]
+    async fn test_cache_service_get_or_load() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let service = CacheService::new(InMemoryBackend::new());
+        let loads = AtomicUsize::new(0);
+        let load = || async {
+            loads.fetch_add(1, Ordering::SeqCst);
+            Ok(Some(TestEntity {
+                id: "1".to_string(),
+                value: "loaded".to_string(),
+            }))
+        };
+
+        assert!(service
+            .get::<TestEntity>("1".to_string())
+            .await
+            .expect("Failed to get")
+            .is_none());
+
+        for _ in 0..2 {
+            let entity = service
+                .get_or_load::<TestEntity, _>("1".to_string(), CacheStrategy::Refresh, load)
+                .await
+                .expect("Failed to load")
+                .expect("Entity not found");
+            assert_eq!(entity.value, "loaded");
+        }
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+
+        let cached = service
+            .get::<TestEntity>("1".to_string())
+            .await
+            .expect("Failed to get");
+        assert_eq!(cached.expect("Entity not cached").value, "loaded");
+
+        // Loader errors pass through
+        let result = service
+            .get_or_load::<TestEntity, _>("2".to_string(), CacheStrategy::Refresh, || async {
+                Err(crate::Error::RepositoryError("db down".to_string()))
+            })
+            .await;
+        assert!(matches!(result, Err(crate::Error::RepositoryError(_))));
+    }
+
     #[test]
     fn test_cache_service_clone() {
         let backend = InMemoryBackend::new();
@@ -282,6 +785,36 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_cache_service_from_expander_coalesces() {
+        let backend = InMemoryBackend::new();
+        let service = CacheService::from_expander(
+            CacheExpander::new(backend.clone()).with_request_coalescing(),
+        );
+
+        let mut repo = InMemoryRepository::new();
+        repo.insert(
+            "1".to_string(),
+            TestEntity {
+                id: "1".to_string(),
+                value: "test_value".to_string(),
+            },
+        );
+
+        let mut feeder = GenericFeeder::new("1".to_string());
+        service
+            .execute::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute");
+
+        assert_eq!(feeder.data.expect("Data not found").value, "test_value");
+        assert!(backend
+            .get("test:1")
+            .await
+            .expect("Failed to get")
+            .is_some());
+    }
+
     #[tokio::test]
     async fn test_cache_service_execute_with_config() {
         use std::time::Duration;
@@ -318,4 +851,83 @@ mod tests {
         assert!(feeder.data.is_some());
         assert_eq!(feeder.data.expect("Data not found").value, "test_value");
     }
+
+    #[tokio::test]
+    async fn test_cache_service_write_through() {
+        let service = CacheService::new(InMemoryBackend::new());
+        let repo = InMemoryRepository::new();
+
+        service
+            .write_through(
+                &TestEntity {
+                    id: "1".to_string(),
+                    value: "written".to_string(),
+                },
+                &repo,
+            )
+            .await
+            .expect("Failed to write through");
+
+        // Served from cache without a repository read
+        let mut feeder = GenericFeeder::new("1".to_string());
+        service
+            .execute::<TestEntity, _, _>(&mut feeder, &repo, CacheStrategy::Fresh)
+            .await
+            .expect("Failed to execute");
+        assert_eq!(feeder.data.expect("Data not found").value, "written");
+    }
+
+    #[tokio::test]
+    async fn test_cache_service_write_behind() {
+        let backend = InMemoryBackend::new();
+        let service = CacheService::new(backend.clone());
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let queue = service.write_behind(repo.clone(), WriteBehindConfig::default());
+        queue
+            .write(&TestEntity {
+                id: "1".to_string(),
+                value: "queued".to_string(),
+            })
+            .await
+            .expect("Failed to write");
+
+        assert!(backend
+            .get("test:1")
+            .await
+            .expect("Failed to get")
+            .is_some());
+        assert_eq!(queue.depth(), 1);
+
+        queue.flush().await.expect("Failed to flush");
+        assert_eq!(queue.depth(), 0);
+        assert_eq!(repo.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cache_service_execute_many() {
+        let service = CacheService::new(InMemoryBackend::new());
+
+        let mut repo = InMemoryRepository::new();
+        for id in ["1", "2"] {
+            repo.insert(
+                id.to_string(),
+                TestEntity {
+                    id: id.to_string(),
+                    value: format!("value_{}", id),
+                },
+            );
+        }
+
+        let ids = vec!["2".to_string(), "missing".to_string(), "1".to_string()];
+        let results = service
+            .execute_many::<TestEntity, _>(&ids, &repo, CacheStrategy::Refresh)
+            .await
+            .expect("Failed to execute batch");
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().map(|e| e.id.as_str()), Some("2"));
+        assert!(results[1].is_none());
+        assert_eq!(results[2].as_ref().map(|e| e.id.as_str()), Some("1"));
+    }
 }
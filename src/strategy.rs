@@ -8,9 +8,9 @@
 //! Cache-kit uses an enum-based strategy pattern to replace ad-hoc boolean flags.
 //! This makes cache behavior explicit and type-safe.
 //!
-//! # The Four Strategies
+//! # The Strategies
 //!
-//! Every cache operation uses one of four strategies:
+//! Every cache operation uses one of these strategies:
 //!
 //! ```
 //! use cache_kit::strategy::CacheStrategy;
@@ -26,6 +26,11 @@
 //!
 //! // 4. Bypass - Skip cache entirely
 //! let _s = CacheStrategy::Bypass;
+//!
+//! // 5. StaleWhileRevalidate - Serve cached data, refresh stale entries behind the scenes
+//! let _s = CacheStrategy::StaleWhileRevalidate {
+//!     stale_after: std::time::Duration::from_secs(60),
+//! };
 //! ```
 //!
 //! # Decision Tree
@@ -50,6 +55,7 @@
 //! | **Refresh** | Return | DB fallback | Default; prefer cache, ensure availability |
 //! | **Invalidate** | Delete | Fetch DB | After mutations; need fresh data |
 //! | **Bypass** | Ignore | DB always | Testing or temporary disable |
+//! | **StaleWhileRevalidate** | Return (refresh if stale) | DB fallback | Hot reads sensitive to tail latency |
 //!
 //! # Examples by Scenario
 //!
@@ -62,6 +68,7 @@
 //! - **Fresh**: Fastest if hit, but fails on cache miss.
 //! - **Invalidate**: Ensures freshness but increases DB load after mutations.
 //! - **Bypass**: Simplest for testing, but defeats caching benefits.
+//! - **StaleWhileRevalidate**: Keeps latency flat on expiry, at the cost of briefly serving stale data.
 
 use std::time::Duration;
 
@@ -132,6 +139,29 @@ pub enum CacheStrategy {
     /// 2. Store in cache (for others)
     /// 3. Return value
     Bypass,
+
+    /// **StaleWhileRevalidate**: Serve cached data immediately, refreshing it when stale.
+    ///
+    /// Use when: Tail latency matters more than perfectly fresh reads.
+    ///
+    /// An entry is stale once it is older than `stale_after` (its soft expiry);
+    /// it is still served until the backend TTL (its hard expiry) removes it.
+    /// Entries written without metadata are treated as stale.
+    ///
+    /// Flow:
+    /// 1. Check cache
+    /// 2. If fresh hit: return cached value
+    /// 3. If stale hit: return cached value and refresh it from the database
+    ///    on a background task
+    /// 4. If miss: fetch from database, store in cache, return value
+    ///
+    /// Only runs through `CacheExpander::with_background_refresh` (or
+    /// `CacheService::execute_with_background_refresh`); other entry points
+    /// fail with `Error::ConfigError`.
+    StaleWhileRevalidate {
+        /// Age after which a cached entry is refreshed.
+        stale_after: Duration,
+    },
 }
 
 impl std::fmt::Display for CacheStrategy {
@@ -141,6 +171,9 @@ impl std::fmt::Display for CacheStrategy {
             CacheStrategy::Refresh => write!(f, "Refresh"),
             CacheStrategy::Invalidate => write!(f, "Invalidate"),
             CacheStrategy::Bypass => write!(f, "Bypass"),
+            CacheStrategy::StaleWhileRevalidate { stale_after } => {
+                write!(f, "StaleWhileRevalidate({:?})", stale_after)
+            }
         }
     }
 }
@@ -196,6 +229,13 @@ mod tests {
         assert_eq!(CacheStrategy::Refresh.to_string(), "Refresh");
         assert_eq!(CacheStrategy::Invalidate.to_string(), "Invalidate");
         assert_eq!(CacheStrategy::Bypass.to_string(), "Bypass");
+        assert_eq!(
+            CacheStrategy::StaleWhileRevalidate {
+                stale_after: Duration::from_secs(30)
+            }
+            .to_string(),
+            "StaleWhileRevalidate(30s)"
+        );
     }
 
     #[test]
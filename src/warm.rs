@@ -0,0 +1,159 @@
+//! Cache warming from a repository.
+//!
+//! `CacheExpander::warm` (or `CacheService::warm`) reads every entity of one
+//! type from a [`DataRepository`](crate::repository::DataRepository) and writes
+//! it to the cache ahead of traffic, e.g. to pre-populate Redis before a deploy
+//! flips over to a new cluster.
+//!
+//! Entities are read a page at a time with `DataRepository::fetch_page`, so the
+//! full data set is never held in memory. Repositories that don't implement
+//! paging fall back to a single `fetch_all`. Each page is written in batches
+//! with `CacheBackend::mset`, at most `concurrency` batches at a time, using the
+//! expander's TTL policy.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use cache_kit::warm::WarmOptions;
+//!
+//! let options = WarmOptions::default()
+//!     .with_page_size(1_000)
+//!     .with_filter(|id: &String| !id.starts_with("archived-"))
+//!     .with_progress(|progress| info!("warmed {}/{:?}", progress.written, progress.total));
+//!
+//! let progress = cache.warm::<Product, _>(&product_repo, options).await?;
+//! assert_eq!(progress.failed, 0);
+//! ```
+
+use crate::entity::CacheEntity;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Options for a cache warming run.
+///
+/// # Example
+///
+/// ```ignore
+/// let options = WarmOptions::<Product>::default()
+///     .with_batch_size(200)
+///     .with_concurrency(8);
+/// ```
+pub struct WarmOptions<T: CacheEntity> {
+    /// Entities requested from the repository per page. Default: 500.
+    pub page_size: usize,
+
+    /// Entries written per `mset` call. Default: 100.
+    pub batch_size: usize,
+
+    /// Batches written to the backend concurrently. Default: 4.
+    pub concurrency: usize,
+
+    filter: Option<IdFilter<T::Key>>,
+    progress: Option<ProgressCallback>,
+}
+
+type IdFilter<K> = Arc<dyn Fn(&K) -> bool + Send + Sync>;
+type ProgressCallback = Arc<dyn Fn(&WarmProgress) + Send + Sync>;
+
+impl<T: CacheEntity> Default for WarmOptions<T> {
+    fn default() -> Self {
+        WarmOptions {
+            page_size: 500,
+            batch_size: 100,
+            concurrency: 4,
+            filter: None,
+            progress: None,
+        }
+    }
+}
+
+impl<T: CacheEntity> Clone for WarmOptions<T> {
+    fn clone(&self) -> Self {
+        WarmOptions {
+            page_size: self.page_size,
+            batch_size: self.batch_size,
+            concurrency: self.concurrency,
+            filter: self.filter.clone(),
+            progress: self.progress.clone(),
+        }
+    }
+}
+
+impl<T: CacheEntity> fmt::Debug for WarmOptions<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WarmOptions")
+            .field("page_size", &self.page_size)
+            .field("batch_size", &self.batch_size)
+            .field("concurrency", &self.concurrency)
+            .field("filter", &self.filter.is_some())
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+impl<T: CacheEntity> WarmOptions<T> {
+    /// Set the number of entities requested per page.
+    pub fn with_page_size(mut self, size: usize) -> Self {
+        self.page_size = size.max(1);
+        self
+    }
+
+    /// Set the number of entries written per `mset` call.
+    pub fn with_batch_size(mut self, size: usize) -> Self {
+        self.batch_size = size.max(1);
+        self
+    }
+
+    /// Set the number of batches written concurrently.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Only warm entities whose ID passes `filter`; the rest are counted as skipped.
+    pub fn with_filter(mut self, filter: impl Fn(&T::Key) -> bool + Send + Sync + 'static) -> Self {
+        self.filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Call `report` after each page with the progress so far.
+    pub fn with_progress(mut self, report: impl Fn(&WarmProgress) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Arc::new(report));
+        self
+    }
+
+    /// Whether the entity with `id` should be warmed.
+    pub(crate) fn accepts(&self, id: &T::Key) -> bool {
+        self.filter.as_ref().map_or(true, |filter| filter(id))
+    }
+
+    /// Pass `progress` to the progress callback, if any.
+    pub(crate) fn report(&self, progress: &WarmProgress) {
+        if let Some(report) = &self.progress {
+            report(progress);
+        }
+    }
+}
+
+/// Progress of a cache warming run, reported after each page and returned at the end.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WarmProgress {
+    /// Entities in the repository, if `DataRepository::count` is implemented.
+    pub total: Option<u64>,
+
+    /// Entities read from the repository so far.
+    pub fetched: u64,
+
+    /// Entities written to the cache.
+    pub written: u64,
+
+    /// Entities rejected by the ID filter.
+    pub skipped: u64,
+
+    /// Entities that failed validation, serialization or the cache write.
+    pub failed: u64,
+
+    /// Time since the run started.
+    pub elapsed: Duration,
+}
@@ -0,0 +1,602 @@
+//! Write-behind (write-back) queue with periodic flush.
+//!
+//! A [`WriteBehindQueue`] stores writes in the cache immediately and persists
+//! them to a [`WritableRepository`] later, in batches. Writes to the same key
+//! are coalesced while they wait, so only the latest value of a frequently
+//! updated entity (e.g. a counter) reaches the repository.
+//!
+//! Batches are flushed every `flush_interval`, or as soon as `max_batch_size`
+//! writes are pending. A failed batch is retried according to a [`RetryPolicy`];
+//! if every attempt fails, its writes are put back on the queue (unless a newer
+//! write for the same key arrived in the meantime) and retried on the next flush.
+//!
+//! # Durability
+//!
+//! Pending writes live in process memory only. Call [`WriteBehindQueue::flush`]
+//! during graceful shutdown; writes still queued when the last handle is dropped
+//! are lost.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use cache_kit::write_behind::WriteBehindConfig;
+//!
+//! let queue = cache.write_behind(counter_repo, WriteBehindConfig::default());
+//!
+//! queue.write(&counter).await?; // cached now, persisted later
+//!
+//! // On shutdown
+//! queue.flush().await?;
+//! ```
+
+use crate::backend::CacheBackend;
+use crate::entity::CacheEntity;
+use crate::error::Result;
+use crate::expander::{CacheExpander, OperationConfig};
+use crate::key::CacheKeyBuilder;
+use crate::repository::WritableRepository;
+use crate::retry::{Jitter, RetryPolicy};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+/// Configuration for a [`WriteBehindQueue`].
+///
+/// # Example
+///
+/// ```ignore
+/// let config = WriteBehindConfig::default()
+///     .with_flush_interval(Duration::from_millis(500))
+///     .with_max_batch_size(500);
+/// ```
+#[derive(Clone, Debug)]
+pub struct WriteBehindConfig {
+    /// How often pending writes are flushed. Default: 1 second.
+    pub flush_interval: Duration,
+
+    /// Pending writes that trigger an early flush, and the largest batch
+    /// passed to `WritableRepository::save_many`. Default: 100.
+    pub max_batch_size: usize,
+
+    /// Retry policy for a failed batch within one flush. Default: 4 attempts,
+    /// 100 milliseconds doubling up to 5 seconds, no jitter.
+    pub retry: RetryPolicy,
+}
+
+impl Default for WriteBehindConfig {
+    fn default() -> Self {
+        WriteBehindConfig {
+            flush_interval: Duration::from_secs(1),
+            max_batch_size: 100,
+            retry: RetryPolicy::new(4).with_jitter(Jitter::None),
+        }
+    }
+}
+
+impl WriteBehindConfig {
+    /// Set the flush interval.
+    pub fn with_flush_interval(mut self, interval: Duration) -> Self {
+        self.flush_interval = interval;
+        self
+    }
+
+    /// Set the batch size that triggers an early flush.
+    pub fn with_max_batch_size(mut self, size: usize) -> Self {
+        self.max_batch_size = size.max(1);
+        self
+    }
+
+    /// Set the number of retries for a failed batch.
+    pub fn with_max_retries(mut self, retries: u32) -> Self {
+        self.retry.max_attempts = retries.saturating_add(1);
+        self
+    }
+
+    /// Set the initial retry backoff.
+    pub fn with_retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry.base_delay = backoff;
+        self
+    }
+
+    /// Set the retry policy for failed batches. Its scope is ignored.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+}
+
+/// Queue of cache writes waiting to be persisted to a repository.
+///
+/// Cheap to clone; clones share the same queue. Create one with
+/// `CacheService::write_behind()` or [`WriteBehindQueue::new`]. Must be created
+/// inside a Tokio runtime, which runs the periodic flush task.
+pub struct WriteBehindQueue<B, T, R>
+where
+    B: CacheBackend,
+    T: CacheEntity,
+    R: WritableRepository<T>,
+{
+    inner: Arc<Inner<B, T, R>>,
+}
+
+struct Inner<B, T, R>
+where
+    B: CacheBackend,
+    T: CacheEntity,
+    R: WritableRepository<T>,
+{
+    expander: Arc<CacheExpander<B>>,
+    repository: R,
+    config: WriteBehindConfig,
+    pending: Mutex<HashMap<String, T>>,
+    flushing: tokio::sync::Mutex<()>,
+    wake: Arc<Notify>,
+}
+
+impl<B, T, R> Clone for WriteBehindQueue<B, T, R>
+where
+    B: CacheBackend,
+    T: CacheEntity,
+    R: WritableRepository<T>,
+{
+    fn clone(&self) -> Self {
+        WriteBehindQueue {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<B, T, R> WriteBehindQueue<B, T, R>
+where
+    B: CacheBackend + 'static,
+    T: CacheEntity + 'static,
+    R: WritableRepository<T> + 'static,
+{
+    /// Create a queue that caches through `expander` and persists to `repository`.
+    ///
+    /// Spawns the periodic flush task, which stops once every handle to the
+    /// queue has been dropped.
+    pub fn new(expander: Arc<CacheExpander<B>>, repository: R, config: WriteBehindConfig) -> Self {
+        let wake = Arc::new(Notify::new());
+        let inner = Arc::new(Inner {
+            expander,
+            repository,
+            config,
+            pending: Mutex::new(HashMap::new()),
+            flushing: tokio::sync::Mutex::new(()),
+            wake: Arc::clone(&wake),
+        });
+
+        tokio::spawn(Self::run(
+            Arc::downgrade(&inner),
+            wake,
+            inner.config.flush_interval,
+        ));
+
+        WriteBehindQueue { inner }
+    }
+
+    /// Periodic flush loop; holds only a weak reference between flushes.
+    async fn run(inner: Weak<Inner<B, T, R>>, wake: Arc<Notify>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        ticker.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = wake.notified() => {}
+            }
+
+            let Some(inner) = inner.upgrade() else {
+                debug!("Write-behind queue dropped, stopping flush task");
+                return;
+            };
+            // Failures are reported through metrics and the writes re-queued
+            let _ = Self::flush_inner(&inner).await;
+        }
+    }
+}
+
+impl<B, T, R> WriteBehindQueue<B, T, R>
+where
+    B: CacheBackend,
+    T: CacheEntity,
+    R: WritableRepository<T>,
+{
+    /// Store an entity in the cache now and queue it for the repository.
+    ///
+    /// A pending write for the same key is replaced, so only the latest value
+    /// is persisted.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::ValidationError`: Entity validation fails (nothing is queued)
+    /// - `Error::SerializationError`: Entity serialization fails (nothing is queued)
+    /// - `Error::BackendError`: Cache write or dependent eviction fails (nothing
+    ///   is queued). In `FailureMode::Open` the error is ignored and the write
+    ///   is still queued.
+    pub async fn write(&self, entity: &T) -> Result<()> {
+        entity.validate()?;
+        let id = entity.cache_key();
+        let expander = &self.inner.expander;
+        let cache_key = expander
+            .cache_key::<T>(&id, &OperationConfig::default())
+            .await?;
+
+        if let Err(e) = expander
+            .store_entity(&cache_key, entity, Duration::ZERO)
+            .await
+        {
+            expander.tolerate(&cache_key, expander.failure_mode(), e)?;
+        }
+        if let Err(e) = expander.evict_dependents(&cache_key).await {
+            expander.tolerate(&cache_key, expander.failure_mode(), e)?;
+        }
+
+        // Pending writes are keyed without generations, so a write queued
+        // before a generation bump is still replaced by a later one
+        let depth = {
+            let mut pending = self.inner.pending();
+            pending.insert(CacheKeyBuilder::build::<T>(&id), entity.clone());
+            pending.len()
+        };
+        self.inner.expander.metrics().record_queue_depth(depth);
+
+        if depth >= self.inner.config.max_batch_size {
+            self.inner.wake.notify_one();
+        }
+
+        Ok(())
+    }
+
+    /// Persist all pending writes now.
+    ///
+    /// Waits for any flush already in progress. Call this on shutdown.
+    ///
+    /// # Errors
+    ///
+    /// Returns the repository error if a batch still fails after all retries.
+    /// Its writes (and any not yet attempted) stay queued.
+    pub async fn flush(&self) -> Result<()> {
+        Self::flush_inner(&self.inner).await
+    }
+
+    /// Number of writes waiting to be persisted.
+    pub fn depth(&self) -> usize {
+        self.inner.pending().len()
+    }
+
+    async fn flush_inner(inner: &Inner<B, T, R>) -> Result<()> {
+        let _flushing = inner.flushing.lock().await;
+        let metrics = inner.expander.metrics();
+
+        let mut batch: Vec<(String, T)> = inner.pending().drain().collect();
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        let mut result = Ok(());
+        while !batch.is_empty() {
+            let rest = batch.split_off(batch.len().min(inner.config.max_batch_size));
+            if let Err(e) = Self::save_batch(inner, &batch).await {
+                // Keep anything newer that was written while we were flushing
+                let mut pending = inner.pending();
+                for (key, entity) in batch.into_iter().chain(rest) {
+                    pending.entry(key).or_insert(entity);
+                }
+                result = Err(e);
+                break;
+            }
+            batch = rest;
+        }
+
+        metrics.record_queue_depth(inner.pending().len());
+        result
+    }
+
+    /// Save one batch, retrying retryable errors per the configured policy.
+    async fn save_batch(inner: &Inner<B, T, R>, batch: &[(String, T)]) -> Result<()> {
+        let metrics = inner.expander.metrics();
+        let entities: Vec<T> = batch.iter().map(|(_, entity)| entity.clone()).collect();
+
+        let mut backoff = inner.config.retry.backoff();
+        let mut attempts = 1;
+        loop {
+            let started = Instant::now();
+            match inner.repository.save_many(&entities).await {
+                Ok(()) => {
+                    metrics.record_flush(entities.len(), started.elapsed());
+                    debug!("✓ Write-behind flushed {} entries", entities.len());
+                    return Ok(());
+                }
+                Err(e) => {
+                    metrics.record_flush_error(entities.len(), &e.to_string());
+                    if backoff.retry(&e).await {
+                        attempts += 1;
+                        continue;
+                    }
+
+                    warn!(
+                        "Write-behind flush of {} entries failed after {} attempts: {}",
+                        entities.len(),
+                        attempts,
+                        e
+                    );
+                    return Err(e);
+                }
+            }
+        }
+    }
+}
+
+impl<B, T, R> Inner<B, T, R>
+where
+    B: CacheBackend,
+    T: CacheEntity,
+    R: WritableRepository<T>,
+{
+    fn pending(&self) -> std::sync::MutexGuard<'_, HashMap<String, T>> {
+        self.pending.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<B, T, R> Drop for Inner<B, T, R>
+where
+    B: CacheBackend,
+    T: CacheEntity,
+    R: WritableRepository<T>,
+{
+    fn drop(&mut self) {
+        let lost = self
+            .pending
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len();
+        if lost > 0 {
+            warn!(
+                "Write-behind queue dropped with {} unflushed writes; call flush() before shutdown",
+                lost
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::InMemoryBackend;
+    use crate::error::Error;
+    use crate::observability::CacheMetrics;
+    use crate::repository::{DataRepository, InMemoryRepository};
+    use serde::{Deserialize, Serialize};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Counter {
+        id: String,
+        count: u64,
+    }
+
+    impl CacheEntity for Counter {
+        type Key = String;
+
+        fn cache_key(&self) -> Self::Key {
+            self.id.clone()
+        }
+
+        fn cache_prefix() -> &'static str {
+            "counter"
+        }
+    }
+
+    fn counter(id: &str, count: u64) -> Counter {
+        Counter {
+            id: id.to_string(),
+            count,
+        }
+    }
+
+    /// Repository that fails the first `failures` batches.
+    struct FlakyRepository {
+        inner: InMemoryRepository<Counter>,
+        failures: AtomicUsize,
+        batches: AtomicUsize,
+    }
+
+    impl FlakyRepository {
+        fn new(failures: usize) -> Self {
+            FlakyRepository {
+                inner: InMemoryRepository::new(),
+                failures: AtomicUsize::new(failures),
+                batches: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    impl DataRepository<Counter> for FlakyRepository {
+        async fn fetch_by_id(&self, id: &String) -> Result<Option<Counter>> {
+            self.inner.fetch_by_id(id).await
+        }
+    }
+
+    impl WritableRepository<Counter> for FlakyRepository {
+        async fn save(&self, entity: &Counter) -> Result<()> {
+            self.inner.save(entity).await
+        }
+
+        async fn delete(&self, id: &String) -> Result<()> {
+            self.inner.delete(id).await
+        }
+
+        async fn save_many(&self, entities: &[Counter]) -> Result<()> {
+            self.batches.fetch_add(1, Ordering::SeqCst);
+            let remaining = self.failures.load(Ordering::SeqCst);
+            if remaining > 0 {
+                self.failures.store(remaining - 1, Ordering::SeqCst);
+                return Err(Error::RepositoryError("db down".to_string()));
+            }
+            for entity in entities {
+                self.inner.save(entity).await?;
+            }
+            Ok(())
+        }
+    }
+
+    fn queue(
+        repo: Arc<FlakyRepository>,
+        config: WriteBehindConfig,
+    ) -> (
+        InMemoryBackend,
+        WriteBehindQueue<InMemoryBackend, Counter, Arc<FlakyRepository>>,
+    ) {
+        let backend = InMemoryBackend::new();
+        let expander = Arc::new(CacheExpander::new(backend.clone()));
+        (backend, WriteBehindQueue::new(expander, repo, config))
+    }
+
+    #[tokio::test]
+    async fn test_write_behind_caches_now_and_coalesces() {
+        let repo = Arc::new(FlakyRepository::new(0));
+        let config = WriteBehindConfig::default().with_flush_interval(Duration::from_secs(3600));
+        let (backend, queue) = queue(repo.clone(), config);
+
+        for count in 1..=5 {
+            queue
+                .write(&counter("a", count))
+                .await
+                .expect("Failed to write");
+        }
+        queue
+            .write(&counter("b", 1))
+            .await
+            .expect("Failed to write");
+
+        assert!(backend
+            .get("counter:a")
+            .await
+            .expect("Failed to get")
+            .is_some());
+        assert_eq!(queue.depth(), 2);
+        assert!(repo.inner.is_empty());
+
+        queue.flush().await.expect("Failed to flush");
+
+        assert_eq!(queue.depth(), 0);
+        assert_eq!(repo.batches.load(Ordering::SeqCst), 1);
+        let saved = repo
+            .fetch_by_id(&"a".to_string())
+            .await
+            .expect("Failed to fetch");
+        assert_eq!(saved, Some(counter("a", 5)));
+    }
+
+    #[tokio::test]
+    async fn test_write_behind_flushes_on_batch_size() {
+        let repo = Arc::new(FlakyRepository::new(0));
+        let config = WriteBehindConfig::default()
+            .with_flush_interval(Duration::from_secs(3600))
+            .with_max_batch_size(3);
+        let (_backend, queue) = queue(repo.clone(), config);
+
+        for id in ["a", "b", "c"] {
+            queue.write(&counter(id, 1)).await.expect("Failed to write");
+        }
+
+        for _ in 0..50 {
+            if repo.inner.len() == 3 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(repo.inner.len(), 3);
+        assert_eq!(queue.depth(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_write_behind_flushes_on_interval() {
+        let repo = Arc::new(FlakyRepository::new(0));
+        let config = WriteBehindConfig::default().with_flush_interval(Duration::from_millis(20));
+        let (_backend, queue) = queue(repo.clone(), config);
+
+        queue
+            .write(&counter("a", 1))
+            .await
+            .expect("Failed to write");
+
+        for _ in 0..50 {
+            if !repo.inner.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(repo.inner.len(), 1);
+    }
+
+    #[test]
+    fn test_write_behind_retry_backoff_saturates() {
+        let config = WriteBehindConfig::default()
+            .with_max_retries(u32::MAX)
+            .with_retry_backoff(Duration::from_secs(60));
+
+        assert_eq!(config.retry.max_attempts, u32::MAX);
+        assert_eq!(
+            config.retry.delay(64, Duration::ZERO),
+            config.retry.max_delay
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_behind_retries_and_requeues() {
+        struct FlushMetrics {
+            errors: Arc<AtomicUsize>,
+            flushes: Arc<AtomicUsize>,
+        }
+
+        impl CacheMetrics for FlushMetrics {
+            fn record_flush(&self, _batch_size: usize, _duration: Duration) {
+                self.flushes.fetch_add(1, Ordering::SeqCst);
+            }
+
+            fn record_flush_error(&self, _batch_size: usize, _error: &str) {
+                self.errors.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let errors = Arc::new(AtomicUsize::new(0));
+        let flushes = Arc::new(AtomicUsize::new(0));
+        let expander = Arc::new(
+            CacheExpander::new(InMemoryBackend::new()).with_metrics(Box::new(FlushMetrics {
+                errors: errors.clone(),
+                flushes: flushes.clone(),
+            })),
+        );
+        let repo = Arc::new(FlakyRepository::new(3));
+        let config = WriteBehindConfig::default()
+            .with_flush_interval(Duration::from_secs(3600))
+            .with_max_retries(1)
+            .with_retry_backoff(Duration::from_millis(1));
+        let queue = WriteBehindQueue::new(expander, repo.clone(), config);
+
+        queue
+            .write(&counter("a", 1))
+            .await
+            .expect("Failed to write");
+
+        // Initial attempt and one retry both fail; the write stays queued
+        assert!(matches!(
+            queue.flush().await,
+            Err(Error::RepositoryError(_))
+        ));
+        assert_eq!(queue.depth(), 1);
+        assert_eq!(errors.load(Ordering::SeqCst), 2);
+
+        // Third failure, then success on retry
+        queue.flush().await.expect("Failed to flush");
+        assert_eq!(queue.depth(), 0);
+        assert_eq!(errors.load(Ordering::SeqCst), 3);
+        assert_eq!(flushes.load(Ordering::SeqCst), 1);
+        assert_eq!(repo.inner.len(), 1);
+    }
+}